@@ -177,13 +177,20 @@ SniperApp.prototype.startScanner = async function() {
         document.getElementById('start-scanner').disabled = true;
         document.getElementById('stop-scanner').disabled = false;
         
-        // Set up interval to fetch opportunities
-        const refreshRate = parseInt(document.getElementById('auto-refresh').value) || 5;
-        
-        if (refreshRate > 0) {
-            this.scanInterval = setInterval(() => {
-                this.fetchOpportunities();
-            }, refreshRate * 1000);
+        // Load what the scanner already has, then follow the server's event stream
+        await this.fetchOpportunities();
+        
+        if (window.EventSource) {
+            this.subscribeToEvents();
+        } else {
+            // Fall back to polling in browsers without Server-Sent Events
+            const refreshRate = parseInt(document.getElementById('auto-refresh').value) || 5;
+            
+            if (refreshRate > 0) {
+                this.scanInterval = setInterval(() => {
+                    this.fetchOpportunities();
+                }, refreshRate * 1000);
+            }
         }
         
         this.showNotification('Scanner started', 'success');
@@ -220,6 +227,11 @@ SniperApp.prototype.stopScanner = async function() {
             this.scanInterval = null;
         }
         
+        if (this.eventSource) {
+            this.eventSource.close();
+            this.eventSource = null;
+        }
+        
         this.showNotification('Scanner stopped', 'info');
         
         return true;
@@ -230,6 +242,49 @@ SniperApp.prototype.stopScanner = async function() {
     }
 };
 
+// Live updates pushed from /api/events; the browser resumes from the last event id on reconnect
+SniperApp.prototype.subscribeToEvents = function() {
+    if (this.eventSource) return;
+    
+    // EventSource cannot send headers, so the API token goes in the query
+    const token = localStorage.getItem('wolf63x.apiToken');
+    const auth = token ? `&access_token=${encodeURIComponent(token)}` : '';
+    this.eventSource = new EventSource(`${this.apiBaseUrl}/events?topics=opportunities,verdicts,orders,wallet${auth}`);
+    
+    this.eventSource.addEventListener('new_opportunity', (e) => {
+        const opportunity = JSON.parse(e.data).data;
+        this.opportunities = [opportunity, ...this.opportunities.filter(o => o.address !== opportunity.address)];
+        this.updateOpportunitiesUI();
+    });
+    
+    this.eventSource.addEventListener('filter_verdict', (e) => {
+        const { token_address, verdict } = JSON.parse(e.data).data;
+        const opportunity = this.opportunities.find(o => o.address === token_address);
+        if (opportunity) {
+            opportunity.verdict = verdict;
+            this.updateOpportunitiesUI();
+        }
+    });
+    
+    // The server restarted since the last event seen, so what was missed cannot be replayed
+    this.eventSource.addEventListener('reset', () => {
+        this.fetchOpportunities();
+        this.fetchActiveTrades();
+    });
+    
+    this.eventSource.addEventListener('order_confirmed', () => this.fetchActiveTrades());
+    
+    this.eventSource.addEventListener('order_failed', (e) => {
+        const { token_address, error } = JSON.parse(e.data).data;
+        this.showNotification(`Order for ${token_address} failed: ${error}`, 'error');
+    });
+    
+    this.eventSource.addEventListener('wallet_balance', (e) => {
+        this.balance = JSON.parse(e.data).data.balance;
+        this.updateWalletUI();
+    });
+};
+
 SniperApp.prototype.updateBalance = async function() {
     try {
         if (!this.isConnected) return;
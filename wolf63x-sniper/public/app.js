@@ -2,6 +2,19 @@
 /*global Chart */
 
 // Wolf63x Solana Sniper Bot - Frontend JavaScript
+
+// Send the API token saved in this browser, if the server requires one
+const apiToken = localStorage.getItem('wolf63x.apiToken');
+if (apiToken) {
+    const fetchWithoutToken = window.fetch.bind(window);
+    window.fetch = (resource, options = {}) => {
+        const url = typeof resource === 'string' ? resource : resource.url;
+        if (url.startsWith('/api/')) {
+            options = { ...options, headers: { ...options.headers, Authorization: `Bearer ${apiToken}` } };
+        }
+        return fetchWithoutToken(resource, options);
+    };
+}
 document.addEventListener('DOMContentLoaded', async () => {
     // Initialize the application
     const app = new SniperApp();
@@ -0,0 +1,498 @@
+//! Startup and the trading operations shared by the HTTP server and the CLI
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use anyhow::Result;
+use log::{info, warn};
+use metrics::histogram;
+use solana_sdk::{pubkey::Pubkey, signature::Keypair};
+use wolf63x_core::{
+    wallet::Wallet,
+    trader::Trader,
+    scanner::{Scanner, TokenOpportunity},
+    filters::TokenFilter,
+    logger::{TradeLog, TradeType},
+    config::{BotConfig, ConfigManager},
+    config_store::ConfigStore,
+    error::SniperError,
+    events::{Event, EventBus, Topic, DEFAULT_EVENT_HISTORY},
+    profiles::{ExitPlan, StrategyProfile},
+    pump_fun::{self, BondingCurveState, BuyQuote},
+    reputation::ReputationStore,
+    secrets::Secrets,
+    telemetry,
+};
+use crate::services::{LoggerService, NotifierService, Position, PositionService, ScannerService, TraderService, WalletService};
+
+/// Config file used when `--config` is not given
+const DEFAULT_CONFIG_PATH: &str = "config.json";
+
+/// Directory holding saved configuration versions
+const CONFIG_HISTORY_DIR: &str = "config_history";
+
+/// Creator reputation survives restarts
+const REPUTATION_PATH: &str = "creator_reputation.json";
+
+/// How often unsaved reputation changes are written out
+const REPUTATION_FLUSH_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Open positions, shared between the server and one-shot CLI commands
+const POSITIONS_PATH: &str = "positions.json";
+
+/// Buys counted towards today's limit, shared by the server and CLI commands
+const BUY_COUNT_PATH: &str = "buy_count.json";
+
+/// Trade log, shared so realized PnL counts sells made by CLI commands too
+const TRADES_PATH: &str = "trades.json";
+
+/// The bot's services and shared state
+pub struct App {
+    pub wallet: WalletService,
+    pub trader: TraderService,
+    pub scanner: ScannerService,
+    pub positions: PositionService,
+    pub logger: LoggerService,
+    pub notifier: NotifierService,
+    pub events: Arc<EventBus>,
+    pub config: Arc<Mutex<ConfigManager>>,
+    pub reputation: Arc<Mutex<ReputationStore>>,
+    pub secrets: Arc<Secrets>,
+    /// Set by the kill switch; no buys go out while it is
+    halted: AtomicBool,
+}
+
+impl App {
+    /// Load the configuration and start every service
+    ///
+    /// With a keypair, e.g. from the keystore, the wallet is connected from
+    /// the start; otherwise one is connected through the API.
+    pub fn start(secrets: Arc<Secrets>, config_path: Option<&str>, keypair: Option<Keypair>) -> Result<Arc<Self>> {
+        let reputation = Arc::new(Mutex::new(ReputationStore::load(REPUTATION_PATH)?));
+
+        // One set of filter settings, so /api/config changes what the scanner accepts
+        let filter = Arc::new(Mutex::new(TokenFilter::new()));
+        let filter_settings = filter.lock().unwrap().shared_settings();
+        let profiles = filter.lock().unwrap().shared_profiles();
+
+        // Pushed to clients over /api/events instead of polling
+        let events = Arc::new(EventBus::new(DEFAULT_EVENT_HISTORY));
+
+        let mut config_manager = ConfigManager::with_filter_settings(filter_settings).with_profiles(profiles);
+        let config_path = config_path_or_default(config_path);
+        if let Some(path) = &config_path {
+            config_manager.load_file(path)?;
+            info!("Loaded configuration from {}", path);
+        }
+        // Changes made through the API survive restarts
+        let kept_versions = config_manager.config().advanced_settings.kept_config_versions;
+        config_manager.attach_store(ConfigStore::open(history_dir(config_path.as_deref()), kept_versions)?)?;
+        let config = config_manager.config();
+
+        let rpc_url = secrets.resolve_config(&config)?.rpc_url;
+        let mut scanner = Scanner::with_rpc_url(&rpc_url);
+        scanner.set_reputation_store(reputation.clone());
+        scanner.set_filter(filter);
+        scanner.set_event_bus(events.clone());
+
+        let wallet = keypair.map(Wallet::from_keypair).unwrap_or_default();
+        if wallet.has_keypair() {
+            // The wallet pays for honeypot simulations
+            scanner.set_simulation_payer(&wallet.address)?;
+        }
+        let trader = Trader::new(wallet.session(), rpc_url);
+
+        let scanner = ScannerService::spawn(scanner);
+        let app = Arc::new(Self {
+            wallet: WalletService::spawn(wallet, events.clone()),
+            trader: TraderService::spawn(trader, events.clone(), Some(PathBuf::from(BUY_COUNT_PATH))),
+            positions: PositionService::spawn(events.clone(), Some(PathBuf::from(POSITIONS_PATH)))?,
+            scanner,
+            logger: LoggerService::spawn(Some(PathBuf::from(TRADES_PATH)))?,
+            notifier: NotifierService::spawn(events.clone()),
+            events,
+            config: Arc::new(Mutex::new(config_manager)),
+            reputation,
+            secrets,
+            halted: AtomicBool::new(false),
+        });
+        app.apply_config(&config)?;
+        app.spawn_reputation_flusher();
+        Ok(app)
+    }
+
+    /// Write reputation changes in the background rather than on every launch
+    fn spawn_reputation_flusher(self: &Arc<Self>) {
+        let app = Arc::downgrade(self);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(REPUTATION_FLUSH_INTERVAL);
+            loop {
+                ticker.tick().await;
+                let Some(app) = app.upgrade() else { break };
+                app.flush_reputation().await;
+            }
+        });
+    }
+
+    /// Prune stale creators and write unsaved reputation changes to disk
+    pub async fn flush_reputation(&self) {
+        let snapshot = {
+            let mut reputation = self.reputation.lock().unwrap();
+            reputation.prune_stale(chrono::Utc::now());
+            reputation.snapshot()
+        };
+
+        let snapshot = match snapshot {
+            Ok(Some(snapshot)) => snapshot,
+            Ok(None) => return,
+            Err(e) => {
+                warn!("Failed to serialize creator reputation: {}", e);
+                return;
+            }
+        };
+
+        let revision = snapshot.revision();
+        match tokio::task::spawn_blocking(move || snapshot.write()).await {
+            Ok(Ok(())) => self.reputation.lock().unwrap().mark_saved(revision),
+            Ok(Err(e)) => warn!("Failed to save creator reputation: {}", e),
+            Err(e) => warn!("Failed to save creator reputation: {}", e),
+        }
+    }
+
+    /// Start the scan loop unless the configuration disables the scanner
+    pub async fn start_scanner(&self) -> Result<()> {
+        let enabled = self.config.lock().unwrap().config().scanner_settings.enabled;
+        if !enabled {
+            info!("Scanner is disabled in the configuration");
+            return Ok(());
+        }
+        self.scanner.start().await
+    }
+
+    /// Reload config edits without a restart; filter settings are shared, the rest is pushed here
+    ///
+    /// Watching stops when the returned watcher is dropped.
+    pub fn watch_config(self: &Arc<Self>) -> Result<Option<notify::RecommendedWatcher>> {
+        if self.config.lock().unwrap().source_path().is_none() {
+            return Ok(None);
+        }
+
+        let app = self.clone();
+        let watcher = ConfigManager::watch(self.config.clone(), move |config| {
+            if let Err(e) = app.apply_config(config) {
+                warn!("Reloaded configuration not applied: {}", e);
+            }
+        })?;
+        Ok(Some(watcher))
+    }
+
+    /// RPC endpoint from the configuration, with secrets resolved
+    pub fn rpc_url(&self, config: &BotConfig) -> Result<String> {
+        Ok(self.secrets.resolve_config(config)?.rpc_url)
+    }
+
+    /// Push settings the services keep their own copy of, with secrets resolved
+    pub fn apply_config(&self, config: &BotConfig) -> Result<()> {
+        let resolved = self.secrets.resolve_config(config)?;
+        self.scanner.apply_settings(&resolved.scanner_settings, &resolved.rpc_url);
+        self.trader.apply_settings(&resolved.trade_settings, &resolved.advanced_settings, &resolved.rpc_url);
+        self.wallet.set_rpc_url(&resolved.rpc_url);
+        self.positions.set_rpc_url(&resolved.rpc_url);
+        self.notifier.apply_settings(&resolved.notification_settings);
+        Ok(())
+    }
+
+    /// Profile to trade under: the one requested, else the one the token was routed to, else the active one
+    ///
+    /// Fails only when a named profile does not exist.
+    pub fn resolve_profile(&self, requested: Option<&str>, opportunity: Option<&TokenOpportunity>) -> Result<Option<StrategyProfile>> {
+        let config_manager = self.config.lock().unwrap();
+        let name = requested.map(str::to_string)
+            .or_else(|| opportunity.and_then(|t| t.profile.clone()))
+            .or_else(|| config_manager.active_profile().map(str::to_string));
+
+        match name {
+            Some(name) => config_manager.profile(&name)
+                .cloned()
+                .map(Some)
+                .ok_or_else(|| SniperError::NotFound(format!("Unknown profile: {}", name)).into()),
+            None => Ok(None),
+        }
+    }
+
+    /// Buy a token, open a position for it and log the trade
+    ///
+    /// Positions without a profile exit on the default take profit and stop loss.
+    /// Tokens the filters rejected are refused; ones the scanner has not seen are not.
+    pub async fn buy(
+        &self,
+        token_address: &str,
+        amount: f64,
+        slippage: f64,
+        profile: Option<StrategyProfile>,
+    ) -> Result<String> {
+        if self.is_halted() {
+            return Err(SniperError::TradingHalted.into());
+        }
+
+        let opportunity = self.scanner.opportunity(token_address);
+        if let Some(verdict) = opportunity.as_ref().and_then(|t| t.verdict.as_ref()).filter(|verdict| !verdict.passed) {
+            let reasons = verdict.failures().map(|result| result.reason.clone()).collect();
+            return Err(SniperError::FilterRejected(reasons).into());
+        }
+        let exit_plan = match &profile {
+            Some(profile) => profile.exit_plan.clone(),
+            None => {
+                let trade_settings = self.config.lock().unwrap().config().trade_settings;
+                ExitPlan {
+                    take_profit_percent: trade_settings.default_take_profit,
+                    stop_loss_percent: trade_settings.default_stop_loss,
+                    ..ExitPlan::default()
+                }
+            }
+        };
+
+        if let Some(detected) = opportunity.as_ref().and_then(|t| (chrono::Utc::now() - t.detected_at).to_std().ok()) {
+            histogram!(telemetry::DETECTION_TO_SEND_SECONDS).record(detected.as_secs_f64());
+        }
+        let signature = self.trader.snipe(
+            token_address,
+            amount,
+            slippage,
+            profile.as_ref().map(|profile| profile.trade_settings.clone()),
+            profile.as_ref().map(|profile| profile.exit_plan.clone()),
+        ).await?;
+
+        let token_symbol = opportunity.as_ref().map(|t| t.symbol.clone()).unwrap_or_default();
+        // Without a price the position takes the first one it reads as its entry
+        let price = self.price(token_address).await.unwrap_or_else(|e| {
+            warn!("No entry price for {}: {}", token_address, e);
+            0.0
+        });
+        let profile = profile.map(|profile| profile.name);
+
+        self.positions.open(Position {
+            token_address: token_address.to_string(),
+            token_symbol: token_symbol.clone(),
+            profile: profile.clone(),
+            amount_sol: amount,
+            entry_price: price,
+            opened_at: chrono::Utc::now(),
+            exit_plan: Some(exit_plan),
+            last_price: price,
+            high_price: price,
+            exit_triggered: None,
+        });
+
+        self.logger.record(TradeLog {
+            id: signature.clone(),
+            token_address: token_address.to_string(),
+            token_symbol,
+            token_name: opportunity.as_ref().map(|t| t.name.clone()).unwrap_or_default(),
+            trade_type: TradeType::Buy,
+            amount_in: amount,
+            amount_out: 0.0,
+            price,
+            timestamp: chrono::Utc::now(),
+            tx_signature: signature.clone(),
+            profit_loss: None,
+            profit_loss_percent: None,
+            time_held: None,
+            profile,
+        });
+
+        Ok(signature)
+    }
+
+    /// Buy with the default amount and slippage of the profile the token is routed to
+    pub async fn buy_default(&self, token_address: &str) -> Result<String> {
+        let opportunity = self.scanner.opportunity(token_address);
+        let profile = self.resolve_profile(None, opportunity.as_ref())?;
+        let trade_settings = match &profile {
+            Some(profile) => profile.trade_settings.clone(),
+            None => self.config.lock().unwrap().config().trade_settings,
+        };
+
+        self.buy(token_address, trade_settings.default_buy_amount, trade_settings.default_slippage, profile).await
+    }
+
+    /// Sell the share of a position that cost `amount` SOL, reducing it and logging the trade
+    ///
+    /// Without a position, the wallet's whole balance of the token is sold.
+    pub async fn sell(&self, token_address: &str, amount: f64, slippage: f64) -> Result<String> {
+        let held = self.positions.get(token_address).await?;
+        let percent = held.as_ref()
+            .filter(|held| held.amount_sol > 0.0)
+            .map_or(100.0, |held| (amount / held.amount_sol * 100.0).min(100.0));
+        let signature = self.trader.sell(token_address, amount, percent, slippage).await?;
+
+        let position = self.positions.take(token_address, percent).await?;
+        let opportunity = self.scanner.opportunity(token_address);
+        let now = chrono::Utc::now();
+
+        // Priced at the position's last curve price, the same source as its entry
+        let price = position.as_ref().map_or(0.0, |p| p.last_price);
+        let pnl = position.as_ref().map(|p| p.pnl_at(price));
+        if let Some((_, percent)) = pnl {
+            histogram!(telemetry::TRADE_PNL_PERCENT).record(percent);
+        }
+
+        self.logger.record(TradeLog {
+            id: signature.clone(),
+            token_address: token_address.to_string(),
+            token_symbol: position.as_ref().map(|p| p.token_symbol.clone()).unwrap_or_default(),
+            token_name: opportunity.as_ref().map(|t| t.name.clone()).unwrap_or_default(),
+            trade_type: TradeType::Sell,
+            amount_in: amount,
+            amount_out: 0.0,
+            price,
+            timestamp: now,
+            tx_signature: signature.clone(),
+            profit_loss: pnl.map(|(sol, _)| sol),
+            profit_loss_percent: pnl.map(|(_, percent)| percent),
+            time_held: position.as_ref().map(|p| format!("{}m", (now - p.opened_at).num_minutes())),
+            profile: position.and_then(|p| p.profile),
+        });
+
+        Ok(signature)
+    }
+
+    /// Sell a percentage of an open position
+    pub async fn sell_percent(&self, token_address: &str, percent: f64, slippage: f64) -> Result<String> {
+        if !(percent > 0.0 && percent <= 100.0) {
+            return Err(SniperError::InvalidRequest(format!("Sell percentage must be between 0 and 100 (got {})", percent)).into());
+        }
+        let position = self.positions.get(token_address).await?
+            .ok_or_else(|| SniperError::NotFound(format!("No open position in {}", token_address)))?;
+
+        self.sell(token_address, position.amount_sol * percent / 100.0, slippage).await
+    }
+
+    /// Kill switch: stop scanning and refuse every buy until `resume`
+    ///
+    /// Open positions are left alone so they can still be sold.
+    pub async fn halt(&self) -> Result<()> {
+        self.halted.store(true, Ordering::SeqCst);
+        warn!("Kill switch engaged, trading halted");
+        self.scanner.stop().await
+    }
+
+    /// Release the kill switch and scan again
+    pub async fn resume(&self) -> Result<()> {
+        self.halted.store(false, Ordering::SeqCst);
+        info!("Kill switch released, trading resumed");
+        self.scanner.start().await
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.halted.load(Ordering::SeqCst)
+    }
+
+    /// Turn auto-snipe on or off, saved as a new configuration version
+    pub fn set_auto_snipe(&self, enabled: bool) -> Result<()> {
+        let mut config_manager = self.config.lock().unwrap();
+        let mut config = config_manager.config();
+        config.auto_snipe = enabled;
+        config_manager.set_config(config)
+    }
+
+    /// Trade on events in the background, as the configuration allows
+    ///
+    /// With `auto_snipe` on, tokens passing the filters are bought with
+    /// `buy_default`, each at most once; with
+    /// `auto_sell` on, positions are sold when an exit rule fires.
+    pub fn spawn_auto_trader(self: &Arc<Self>) -> tokio::task::JoinHandle<()> {
+        let topics = [Topic::Opportunities, Topic::Verdicts, Topic::Positions].into_iter().collect();
+        let mut subscription = self.events.subscribe(topics, None);
+        let app = self.clone();
+
+        tokio::spawn(async move {
+            let mut bought = HashSet::new();
+            while let Some(event) = subscription.next().await {
+                app.auto_trade(&event.event, &mut bought).await;
+            }
+        })
+    }
+
+    async fn auto_trade(&self, event: &Event, bought: &mut HashSet<String>) {
+        let config = self.config.lock().unwrap().config();
+        let passed = match event {
+            Event::NewOpportunity(token) => token.verdict.as_ref().map(|verdict| (token.address.clone(), verdict.passed)),
+            Event::FilterVerdict { token_address, verdict } => Some((token_address.clone(), verdict.passed)),
+            Event::ExitTriggered { token_address, .. } if config.auto_sell => {
+                let slippage = config.trade_settings.default_slippage;
+                if let Err(e) = self.sell_percent(token_address, 100.0, slippage).await {
+                    warn!("Auto-sell of {} failed: {}", token_address, e);
+                    // Let the exit fire again on the next price check, so the sell is retried
+                    self.positions.clear_exit(token_address);
+                }
+                None
+            }
+            _ => None,
+        };
+
+        let Some((token_address, true)) = passed else { return };
+        if !config.auto_snipe || self.is_halted() || !bought.insert(token_address.clone()) {
+            return;
+        }
+
+        if let Err(e) = self.buy_default(&token_address).await {
+            warn!("Auto-snipe of {} failed: {}", token_address, e);
+        }
+    }
+
+    /// Expected tokens for buying `sol` SOL of a Pump.fun token on its bonding curve
+    pub async fn quote(&self, token_address: &str, sol: f64) -> Result<BuyQuote> {
+        let (mint, curve) = self.bonding_curve(token_address).await?;
+        BuyQuote::new(&mint, &curve, sol)
+    }
+
+    /// Spot price of a Pump.fun token in SOL, as positions are priced
+    pub async fn price(&self, token_address: &str) -> Result<f64> {
+        let (_, curve) = self.bonding_curve(token_address).await?;
+        Ok(curve.price_sol())
+    }
+
+    async fn bonding_curve(&self, token_address: &str) -> Result<(Pubkey, BondingCurveState)> {
+        let mint = Pubkey::from_str(token_address)
+            .map_err(|_| SniperError::InvalidRequest(format!("Invalid mint address: {}", token_address)))?;
+        let rpc_url = self.rpc_url(&self.config.lock().unwrap().config())?;
+
+        let curve = pump_fun::fetch_bonding_curve(&telemetry::rpc_client(rpc_url), &mint).await?;
+        Ok((mint, curve))
+    }
+}
+
+/// Version history of the configuration loaded from `path`
+///
+/// Each config file gets its own history, so versions saved while running
+/// one file are never restored over another.
+fn history_dir(path: Option<&str>) -> PathBuf {
+    let base = Path::new(CONFIG_HISTORY_DIR);
+    let Some(path) = path else {
+        return base.join("default");
+    };
+
+    let path = std::fs::canonicalize(path).unwrap_or_else(|_| PathBuf::from(path));
+    let stem = path.file_stem().and_then(|stem| stem.to_str()).unwrap_or("config");
+    let digest = solana_sdk::hash::hash(path.to_string_lossy().as_bytes()).to_string();
+    base.join(format!("{}-{}", stem, &digest[..8]))
+}
+
+/// Config file named on the command line, or `config.json` if it exists
+///
+/// A missing default file means built-in defaults; a file named explicitly
+/// must exist, which loading it checks.
+fn config_path_or_default(path: Option<&str>) -> Option<String> {
+    match path {
+        Some(path) => Some(path.to_string()),
+        None if Path::new(DEFAULT_CONFIG_PATH).exists() => Some(DEFAULT_CONFIG_PATH.to_string()),
+        None => {
+            warn!("No {} found, using default configuration", DEFAULT_CONFIG_PATH);
+            None
+        }
+    }
+}
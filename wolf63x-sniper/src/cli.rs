@@ -0,0 +1,289 @@
+//! Command line interface; every command except `serve` prints JSON for scripting
+
+use std::collections::HashSet;
+use std::io::Read;
+use std::path::PathBuf;
+use std::sync::Arc;
+use anyhow::{Result, anyhow};
+use clap::{Parser, Subcommand};
+use log::info;
+use serde::Serialize;
+use solana_sdk::{signature::Keypair, signer::Signer};
+use wolf63x_core::{
+    config_file,
+    error::SniperError,
+    keystore::{self, Keystore, DEFAULT_KEYSTORE_PATH, KEYSTORE_PASSWORD_SECRET},
+    secrets::Secrets,
+};
+use crate::app::App;
+use crate::{server, tui};
+
+#[derive(Parser)]
+#[command(name = "wolf63x-sniper", version, about = "Wolf63x Solana Sniper Bot")]
+pub struct Cli {
+    /// Config file, defaults to config.json when it exists
+    #[arg(long, global = true)]
+    pub config: Option<String>,
+
+    /// Secrets file, defaults to secrets.toml when it exists
+    #[arg(long, global = true)]
+    pub secrets: Option<String>,
+
+    /// Encrypted wallet keystore
+    #[arg(long, global = true, default_value = DEFAULT_KEYSTORE_PATH)]
+    pub keystore: PathBuf,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Serve the API and dashboard (the default)
+    Serve,
+    /// Scan and trade headless, printing events as JSON lines
+    Run,
+    /// Scan and trade in an interactive terminal dashboard, logging to a file
+    Tui,
+    /// Buy a token
+    Buy {
+        mint: String,
+        /// SOL to spend
+        sol: f64,
+        /// Slippage in percent, defaults to the configured slippage
+        #[arg(long)]
+        slippage: Option<f64>,
+        /// Profile to trade under, defaults to the active one
+        #[arg(long)]
+        profile: Option<String>,
+    },
+    /// Sell a percentage of an open position
+    Sell {
+        mint: String,
+        pct: f64,
+        /// Slippage in percent, defaults to the configured slippage
+        #[arg(long)]
+        slippage: Option<f64>,
+    },
+    /// List open positions
+    Positions,
+    /// SOL balance of the keystore wallet
+    Balance,
+    /// Quote a buy on a Pump.fun bonding curve
+    Quote {
+        mint: String,
+        /// SOL to spend
+        sol: f64,
+    },
+    /// Configuration file commands
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommand,
+    },
+    /// Wallet keystore commands
+    Keys {
+        #[command(subcommand)]
+        command: KeysCommand,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ConfigCommand {
+    /// Check a config file and the secrets it references
+    Validate {
+        /// Defaults to --config, then config.json
+        path: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum KeysCommand {
+    /// Encrypt a keypair into the keystore, read from a file or stdin
+    Import {
+        /// Keypair file as written by solana-keygen, or a base58 secret key
+        #[arg(long)]
+        file: Option<PathBuf>,
+        /// Replace an existing keystore
+        #[arg(long)]
+        force: bool,
+    },
+    /// Print the decrypted keypair as a solana-keygen byte array
+    Export,
+}
+
+/// Run the parsed command, printing failures as `{"error": ..., "code": ...}` for scripts
+pub async fn run(cli: Cli, secrets: Arc<Secrets>) -> Result<()> {
+    let command = cli.command.unwrap_or(Command::Serve);
+    if matches!(command, Command::Serve) {
+        let metrics = server::install_metrics()?;
+        let keypair = load_keypair_if_present(&cli.keystore, &secrets)?;
+        return server::serve(App::start(secrets, cli.config.as_deref(), keypair)?, metrics).await;
+    }
+
+    let result = execute(command, cli.config.as_deref(), &cli.keystore, secrets).await;
+    if let Err(e) = &result {
+        let code = e.downcast_ref::<SniperError>().map_or("internal", SniperError::code);
+        print_json(&serde_json::json!({ "error": e.to_string(), "code": code }))?;
+        std::process::exit(1);
+    }
+    result
+}
+
+async fn execute(command: Command, config: Option<&str>, keystore: &std::path::Path, secrets: Arc<Secrets>) -> Result<()> {
+    match command {
+        Command::Serve => unreachable!("serve is handled before other commands"),
+        Command::Run => {
+            let keypair = load_keypair(keystore, &secrets)?;
+            run_headless(App::start(secrets, config, Some(keypair))?).await
+        }
+        Command::Tui => {
+            let keypair = load_keypair(keystore, &secrets)?;
+            tui::run(App::start(secrets, config, Some(keypair))?).await
+        }
+        Command::Buy { mint, sol, slippage, profile } => {
+            let keypair = load_keypair(keystore, &secrets)?;
+            let app = App::start(secrets, config, Some(keypair))?;
+            let profile = app.resolve_profile(profile.as_deref(), app.scanner.opportunity(&mint).as_ref())?;
+            let slippage = slippage.unwrap_or_else(|| app.config.lock().unwrap().config().trade_settings.default_slippage);
+
+            // The scanner is not running, so the bonding curve is the only price the position can be opened at
+            app.quote(&mint, sol).await
+                .map_err(|e| SniperError::InvalidRequest(format!("Cannot price {} from its bonding curve: {}", mint, e)))?;
+            let signature = app.buy(&mint, sol, slippage, profile).await?;
+            settle(&app).await?;
+            print_json(&serde_json::json!({ "signature": signature }))
+        }
+        Command::Sell { mint, pct, slippage } => {
+            let keypair = load_keypair(keystore, &secrets)?;
+            let app = App::start(secrets, config, Some(keypair))?;
+            let slippage = slippage.unwrap_or_else(|| app.config.lock().unwrap().config().trade_settings.default_slippage);
+
+            let signature = app.sell_percent(&mint, pct, slippage).await?;
+            settle(&app).await?;
+            print_json(&serde_json::json!({ "signature": signature }))
+        }
+        Command::Positions => {
+            let app = App::start(secrets, config, None)?;
+            print_json(&app.positions.list().await?)
+        }
+        Command::Balance => {
+            let keypair = load_keypair(keystore, &secrets)?;
+            let address = keypair.pubkey().to_string();
+            let app = App::start(secrets, config, Some(keypair))?;
+            print_json(&serde_json::json!({
+                "address": address,
+                "balance": app.wallet.balance().await?,
+            }))
+        }
+        Command::Quote { mint, sol } => {
+            let app = App::start(secrets, config, None)?;
+            print_json(&app.quote(&mint, sol).await?)
+        }
+        Command::Config { command: ConfigCommand::Validate { path } } => {
+            let path = path.or(config.map(str::to_string)).unwrap_or_else(|| "config.json".to_string());
+            validate_config(&path, &secrets)
+        }
+        Command::Keys { command: KeysCommand::Import { file, force } } => import_keys(keystore, file, force, &secrets),
+        Command::Keys { command: KeysCommand::Export } => {
+            let keypair = load_keypair(keystore, &secrets)?;
+            print_json(&serde_json::json!({
+                "address": keypair.pubkey().to_string(),
+                "keypair": keypair.to_bytes().to_vec(),
+            }))
+        }
+    }
+}
+
+fn print_json(value: &impl Serialize) -> Result<()> {
+    println!("{}", serde_json::to_string(value)?);
+    Ok(())
+}
+
+/// Wait until the position and trade log actors have handled what this command sent them, and save reputation changes
+///
+/// Commands are handled in order, so a round trip to each is enough.
+async fn settle(app: &App) -> Result<()> {
+    app.positions.list().await?;
+    app.logger.logs().await?;
+    app.flush_reputation().await;
+    Ok(())
+}
+
+fn keystore_password(secrets: &Secrets) -> Result<String> {
+    secrets.get(KEYSTORE_PASSWORD_SECRET)
+        .ok_or_else(|| anyhow!("Set {} in the environment or the secrets file to unlock the keystore", KEYSTORE_PASSWORD_SECRET))
+}
+
+fn load_keypair(path: &std::path::Path, secrets: &Secrets) -> Result<Keypair> {
+    Keystore::load(path)?.decrypt(&keystore_password(secrets)?)
+}
+
+/// The server trades with the keystore wallet when there is one, otherwise with one connected through the API
+fn load_keypair_if_present(path: &std::path::Path, secrets: &Secrets) -> Result<Option<Keypair>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let keypair = load_keypair(path, secrets)?;
+    info!("Unlocked keystore {}", path.display());
+    Ok(Some(keypair))
+}
+
+fn import_keys(path: &std::path::Path, file: Option<PathBuf>, force: bool, secrets: &Secrets) -> Result<()> {
+    if path.exists() && !force {
+        return Err(anyhow!("Keystore {} already exists, pass --force to replace it", path.display()));
+    }
+
+    let text = match file {
+        Some(file) => std::fs::read_to_string(&file)
+            .map_err(|e| anyhow!("Cannot read keypair {}: {}", file.display(), e))?,
+        None => {
+            let mut text = String::new();
+            std::io::stdin().read_to_string(&mut text)?;
+            text
+        }
+    };
+    let keypair = keystore::parse_keypair(&text)?;
+
+    let keystore = Keystore::encrypt(&keypair, &keystore_password(secrets)?)?;
+    keystore.save(path)?;
+    print_json(&serde_json::json!({
+        "address": keystore.address,
+        "path": path.display().to_string(),
+    }))
+}
+
+/// Check the file as the server would load it, including that every secret it references resolves
+fn validate_config(path: &str, secrets: &Secrets) -> Result<()> {
+    let (config, server_settings) = config_file::load_settings(path)?;
+    secrets.resolve_config(&config)?;
+    secrets.resolve_api_tokens(&server_settings.api_tokens)?;
+
+    print_json(&serde_json::json!({
+        "valid": true,
+        "path": path,
+    }))
+}
+
+/// Scan and trade without the web server until interrupted, printing every event as a JSON line
+async fn run_headless(app: Arc<App>) -> Result<()> {
+    let _config_watcher = app.watch_config()?;
+    let mut subscription = app.events.subscribe(HashSet::new(), None);
+    let auto_trader = app.spawn_auto_trader();
+    app.scanner.listen();
+    app.start_scanner().await?;
+    info!("Running headless, press Ctrl-C to stop");
+
+    loop {
+        tokio::select! {
+            event = subscription.next() => match event {
+                Some(event) => print_json(&event)?,
+                None => break,
+            },
+            _ = tokio::signal::ctrl_c() => break,
+        }
+    }
+
+    auto_trader.abort();
+    app.scanner.stop().await?;
+    settle(&app).await
+}
@@ -0,0 +1,289 @@
+use serde::{Serialize, Deserialize};
+use anyhow::Result;
+use futures::stream::{self, StreamExt};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_config::RpcTransactionConfig;
+use solana_sdk::{pubkey::Pubkey, signature::Signature};
+use solana_transaction_status::UiTransactionEncoding;
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use crate::holders::{self, HolderAnalyzer};
+use crate::pump_fun;
+
+/// Trades inspected after the creation transaction
+const DEFAULT_EARLY_TRADES: usize = 30;
+
+/// Signature pages walked back on the bonding curve before giving up
+const MAX_CURVE_PAGES: usize = 5;
+
+/// Transactions fetched in parallel
+const FETCH_CONCURRENCY: usize = 4;
+
+/// Result of inspecting the first trades of a launch
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BundleReport {
+    /// Share of supply bought by bundled wallets
+    pub bundle_percent: f64,
+    /// Wallets identified as part of the bundle
+    pub bundled_wallets: Vec<String>,
+    /// Early buys landing in the creation slot
+    pub same_slot_buys: u32,
+    /// Wallets behind those buys
+    #[serde(default)]
+    pub launch_buyers: Vec<String>,
+    /// Trades inspected to build this report
+    pub trades_inspected: u32,
+}
+
+/// A buy decoded from one of the earliest curve transactions
+#[derive(Debug, Clone)]
+struct EarlyBuy {
+    slot: u64,
+    buyer: String,
+    amount: u64,
+}
+
+/// Detects launches where the creator bought through many wallets up front
+pub struct BundleDetector {
+    rpc: Arc<RpcClient>,
+    holders: Arc<HolderAnalyzer>,
+    early_trades: usize,
+    cache: Mutex<HashMap<String, BundleReport>>,
+}
+
+impl BundleDetector {
+    pub fn new(rpc: Arc<RpcClient>, holders: Arc<HolderAnalyzer>) -> Self {
+        Self {
+            rpc,
+            holders,
+            early_trades: DEFAULT_EARLY_TRADES,
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Inspect the first trades of a Pump.fun mint
+    ///
+    /// Returns `None` when the curve already has too much history to reach
+    /// its creation transaction.
+    pub async fn detect(&self, mint: &str, creator: Option<&str>, supply: u64) -> Result<Option<BundleReport>> {
+        if let Some(report) = self.cache.lock().unwrap().get(mint) {
+            return Ok(Some(report.clone()));
+        }
+
+        let mint_key = Pubkey::from_str(mint)?;
+        let curve = pump_fun::bonding_curve_address(&mint_key)?;
+
+        // Creation transaction plus the early trades that follow it
+        let Some(history) = holders::oldest_signatures(&self.rpc, &curve, self.early_trades + 1, MAX_CURVE_PAGES).await? else {
+            return Ok(None);
+        };
+
+        let signatures: Vec<String> = history.iter().map(|entry| entry.signature.clone()).collect();
+        let curve = curve.to_string();
+        let decoded: Vec<Result<Vec<EarlyBuy>>> = stream::iter(signatures)
+            .map(|signature| {
+                let curve = &curve;
+                async move { self.decode_buys(&signature, mint, curve).await }
+            })
+            .buffered(FETCH_CONCURRENCY)
+            .collect()
+            .await;
+
+        let buys: Vec<EarlyBuy> = decoded.into_iter()
+            .flat_map(|buys| buys.unwrap_or_else(|e| {
+                log::debug!("Failed to decode early trade: {}", e);
+                Vec::new()
+            }))
+            .collect();
+
+        let creation_slot = history.first().map(|entry| entry.slot);
+        let report = self.build_report(&buys, creation_slot, creator, supply, history.len()).await;
+
+        // Launches keep trading, so only cache once the early window is full
+        if history.len() > self.early_trades {
+            self.cache.lock().unwrap().insert(mint.to_string(), report.clone());
+        }
+
+        Ok(Some(report))
+    }
+
+    /// Classify early buyers as bundled by slot and funding source
+    async fn build_report(
+        &self,
+        buys: &[EarlyBuy],
+        creation_slot: Option<u64>,
+        creator: Option<&str>,
+        supply: u64,
+        trades_inspected: usize,
+    ) -> BundleReport {
+        let buys = without_dev_buy(buys, creation_slot, creator);
+        let (same_slot_buys, launch_buyers) = launch_buys(&buys, creation_slot);
+        let mut bundled: HashSet<String> = launch_buyers.iter().cloned().collect();
+
+        // Wallets sharing a funder with the creator or with another early buyer
+        let buyers: HashSet<String> = buys.iter().map(|buy| buy.buyer.clone()).collect();
+        if let Some(creator) = creator.filter(|creator| buyers.contains(*creator)) {
+            bundled.insert(creator.to_string());
+        }
+        let others: Vec<String> = buyers.into_iter().filter(|buyer| Some(buyer.as_str()) != creator).collect();
+
+        let creator_funder = async {
+            match creator {
+                Some(creator) => self.holders.funding_source(creator).await.ok().flatten(),
+                None => None,
+            }
+        };
+        let (creator_funder, funders) = tokio::join!(creator_funder, self.holders.funding_sources(others));
+
+        let mut by_funder: HashMap<String, Vec<String>> = HashMap::new();
+        for (buyer, funder) in funders {
+            let Some(funder) = funder else { continue };
+            if Some(funder.as_str()) == creator || Some(&funder) == creator_funder.as_ref() {
+                bundled.insert(buyer.clone());
+            }
+            by_funder.entry(funder).or_default().push(buyer);
+        }
+
+        for wallets in by_funder.into_values().filter(|wallets| wallets.len() > 1) {
+            bundled.extend(wallets);
+        }
+
+        let bundled_amount: u64 = buys.iter()
+            .filter(|buy| bundled.contains(&buy.buyer))
+            .map(|buy| buy.amount)
+            .sum();
+
+        let bundle_percent = if supply > 0 {
+            bundled_amount as f64 / supply as f64 * 100.0
+        } else {
+            0.0
+        };
+
+        BundleReport {
+            bundle_percent,
+            bundled_wallets: bundled.into_iter().collect(),
+            same_slot_buys,
+            launch_buyers,
+            trades_inspected: trades_inspected as u32,
+        }
+    }
+
+    /// Decode every wallet's token gain for `mint` from a curve transaction
+    async fn decode_buys(&self, signature: &str, mint: &str, curve: &str) -> Result<Vec<EarlyBuy>> {
+        let signature = Signature::from_str(signature)?;
+        let transaction = self.rpc.get_transaction_with_config(
+            &signature,
+            RpcTransactionConfig {
+                encoding: Some(UiTransactionEncoding::JsonParsed),
+                max_supported_transaction_version: Some(0),
+                ..RpcTransactionConfig::default()
+            },
+        ).await?;
+
+        let json = serde_json::to_value(&transaction)?;
+        Ok(token_gains(&json["transaction"]["meta"], mint, curve).into_iter()
+            .map(|(buyer, amount)| EarlyBuy { slot: transaction.slot, buyer, amount })
+            .collect())
+    }
+}
+
+/// Owners whose balance of `mint` grew in a transaction, and by how much
+///
+/// The fee payer is not necessarily the buyer, and one transaction can carry
+/// several buys. The curve itself gains tokens on sells, so it is left out.
+fn token_gains(meta: &serde_json::Value, mint: &str, curve: &str) -> Vec<(String, u64)> {
+    let mut balances: HashMap<&str, (u64, u64)> = HashMap::new();
+    for (field, post) in [("preTokenBalances", false), ("postTokenBalances", true)] {
+        for balance in meta[field].as_array().into_iter().flatten().filter(|b| b["mint"] == mint) {
+            let (Some(owner), Some(amount)) = (
+                balance["owner"].as_str(),
+                balance["uiTokenAmount"]["amount"].as_str().and_then(|amount| amount.parse::<u64>().ok()),
+            ) else {
+                continue;
+            };
+            let entry = balances.entry(owner).or_default();
+            if post { entry.1 += amount } else { entry.0 += amount }
+        }
+    }
+
+    let mut gains: Vec<(String, u64)> = balances.into_iter()
+        .filter(|&(owner, (before, after))| owner != curve && after > before)
+        .map(|(owner, (before, after))| (owner.to_string(), after - before))
+        .collect();
+    gains.sort();
+    gains
+}
+
+/// Early buys without the creator's own first buy in the creation slot, which every launch has
+fn without_dev_buy<'a>(buys: &'a [EarlyBuy], creation_slot: Option<u64>, creator: Option<&str>) -> Vec<&'a EarlyBuy> {
+    let dev_buy = buys.iter().position(|buy| Some(buy.slot) == creation_slot && Some(buy.buyer.as_str()) == creator);
+    buys.iter().enumerate()
+        .filter(|&(index, _)| Some(index) != dev_buy)
+        .map(|(_, buy)| buy)
+        .collect()
+}
+
+/// Buys landing in the creation slot, and the wallets behind them in order
+fn launch_buys(buys: &[&EarlyBuy], creation_slot: Option<u64>) -> (u32, Vec<String>) {
+    let mut same_slot_buys = 0;
+    let mut launch_buyers: Vec<String> = Vec::new();
+
+    for buy in buys.iter().filter(|buy| Some(buy.slot) == creation_slot) {
+        same_slot_buys += 1;
+        if !launch_buyers.contains(&buy.buyer) {
+            launch_buyers.push(buy.buyer.clone());
+        }
+    }
+    (same_slot_buys, launch_buyers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn balance(owner: &str, mint: &str, amount: u64) -> serde_json::Value {
+        json!({ "mint": mint, "owner": owner, "uiTokenAmount": { "amount": amount.to_string() } })
+    }
+
+    fn buy(slot: u64, buyer: &str) -> EarlyBuy {
+        EarlyBuy { slot, buyer: buyer.to_string(), amount: 1_000 }
+    }
+
+    #[test]
+    fn buyers_are_the_owners_whose_balance_grew() {
+        // Paid for by a relayer, buying for two wallets, while the curve's balance shrinks
+        let meta = json!({
+            "preTokenBalances": [balance("curve", "mint", 900), balance("alice", "mint", 10), balance("alice", "other", 5)],
+            "postTokenBalances": [
+                balance("curve", "mint", 600),
+                balance("alice", "mint", 110),
+                balance("bob", "mint", 200),
+                balance("alice", "other", 50),
+            ],
+        });
+        assert_eq!(token_gains(&meta, "mint", "curve"), [("alice".to_string(), 100), ("bob".to_string(), 200)]);
+
+        // A sell only grows the curve's balance
+        let sell = json!({
+            "preTokenBalances": [balance("curve", "mint", 600), balance("bob", "mint", 200)],
+            "postTokenBalances": [balance("curve", "mint", 800), balance("bob", "mint", 0)],
+        });
+        assert!(token_gains(&sell, "mint", "curve").is_empty());
+    }
+
+    #[test]
+    fn the_creators_initial_buy_is_not_a_launch_buy() {
+        let buys = [buy(10, "creator"), buy(10, "sniper"), buy(10, "creator"), buy(11, "late")];
+
+        let buys = without_dev_buy(&buys, Some(10), Some("creator"));
+        let (same_slot_buys, launch_buyers) = launch_buys(&buys, Some(10));
+        assert_eq!(same_slot_buys, 2);
+        assert_eq!(launch_buyers, ["sniper", "creator"]);
+
+        let buys = [buy(10, "creator")];
+        let buys = without_dev_buy(&buys, Some(10), Some("creator"));
+        assert_eq!(launch_buys(&buys, Some(10)), (0, Vec::new()));
+    }
+}
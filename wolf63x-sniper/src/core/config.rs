@@ -2,6 +2,29 @@ use wasm_bindgen::prelude::*;
 use serde::{Serialize, Deserialize};
 use std::collections::HashMap;
 use crate::console_log;
+use crate::filters::{FilterSettings, SharedFilterSettings};
+use crate::profiles::{ProfileRoute, ProfileSet, SharedProfiles, StrategyProfile};
+use crate::config_store::{ConfigChange, ConfigStore, ConfigVersion, DEFAULT_KEPT_VERSIONS};
+use crate::error::SniperError;
+use crate::rules;
+use crate::secrets;
+use crate::scanner::MIN_SCAN_INTERVAL_MS;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+use chrono::{DateTime, Utc};
+
+/// Quiet period after a config file change before it is reloaded
+const RELOAD_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Shortest API token accepted in plain text
+const MIN_API_TOKEN_LEN: usize = 16;
+
+/// RPC endpoint used until a config names another
+pub const DEFAULT_RPC_URL: &str = "https://api.mainnet-beta.solana.com";
+
+/// Fastest the terminal dashboard may redraw
+const MIN_UI_REFRESH_MS: u32 = 100;
 
 /// Bot configuration settings
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -12,8 +35,26 @@ pub struct BotConfig {
     pub trade_settings: TradeSettings,
     pub filter_settings: FilterSettings,
     pub notification_settings: NotificationSettings,
+    #[serde(default)]
+    pub scanner_settings: ScannerSettings,
+    #[serde(default)]
+    pub ui_settings: UiSettings,
+    #[serde(default)]
+    pub advanced_settings: AdvancedSettings,
     pub auto_snipe: bool,
     pub auto_sell: bool,
+    /// Start the scanner as soon as the bot starts
+    #[serde(default)]
+    pub auto_start: bool,
+    /// Named strategies with their own trade, filter and exit settings
+    #[serde(default)]
+    pub profiles: Vec<StrategyProfile>,
+    /// Routes tokens to profiles so several can run at once
+    #[serde(default)]
+    pub profile_routes: Vec<ProfileRoute>,
+    /// Profile whose settings are currently the top-level ones
+    #[serde(default)]
+    pub active_profile: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -31,19 +72,191 @@ pub struct TradeSettings {
     pub default_stop_loss: f64,
     pub max_trades_per_day: u32,
     pub min_time_between_trades_seconds: u32,
+    #[serde(default = "default_min_buy_amount")]
+    pub min_buy_amount: f64,
+    #[serde(default = "default_max_buy_amount")]
+    pub max_buy_amount: f64,
+}
+
+impl Default for TradeSettings {
+    fn default() -> Self {
+        Self {
+            default_buy_amount: 0.5,
+            default_slippage: 0.6,
+            default_take_profit: 50.0,
+            default_stop_loss: 30.0,
+            max_trades_per_day: 100,
+            min_time_between_trades_seconds: 60,
+            min_buy_amount: default_min_buy_amount(),
+            max_buy_amount: default_max_buy_amount(),
+        }
+    }
+}
+
+fn default_min_buy_amount() -> f64 {
+    0.1
+}
+
+fn default_max_buy_amount() -> f64 {
+    10.0
+}
+
+/// Token scanner settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScannerSettings {
+    /// Off keeps the scanner from starting and stops a running one
+    pub enabled: bool,
+    pub interval_ms: u32,
+    /// Opportunities kept at most
+    pub max_pairs: usize,
+}
+
+impl Default for ScannerSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval_ms: 5000,
+            max_pairs: 1000,
+        }
+    }
+}
+
+/// Dashboard display settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UiSettings {
+    /// How often the terminal dashboard redraws prices, opportunities and positions
+    pub refresh_rate_ms: u32,
+}
+
+impl Default for UiSettings {
+    fn default() -> Self {
+        Self {
+            refresh_rate_ms: 1000,
+        }
+    }
+}
+
+/// RPC and transaction retry settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdvancedSettings {
+    /// Fallback endpoints tried after `rpc_url`
+    pub rpc_endpoints: Vec<String>,
+    /// Reserved for Jito bundle submission, which is not implemented yet; these are
+    /// validated and their `${NAME}` auth tokens resolved, but no order is sent to them
+    #[serde(default)]
+    pub jito_endpoints: Vec<String>,
+    /// Orders in flight at once; more are refused until one completes
+    pub max_pending_tx: u32,
+    /// Retries of a failed RPC call, each on the next endpoint
+    pub retry_attempts: u32,
+    pub retry_delay_ms: u32,
+    /// Saved configuration versions kept for rollback
+    #[serde(default = "default_kept_config_versions")]
+    pub kept_config_versions: usize,
+}
+
+fn default_kept_config_versions() -> usize {
+    DEFAULT_KEPT_VERSIONS
+}
+
+impl Default for AdvancedSettings {
+    fn default() -> Self {
+        Self {
+            rpc_endpoints: vec![],
+            jito_endpoints: vec![],
+            max_pending_tx: 5,
+            retry_attempts: 3,
+            retry_delay_ms: 1000,
+            kept_config_versions: default_kept_config_versions(),
+        }
+    }
+}
+
+/// What an API token is allowed to do; each role includes the ones before it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiRole {
+    /// Dashboards: every `GET` endpoint and the event stream
+    ReadOnly,
+    /// Orders, wallet and config changes
+    Trader,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct FilterSettings {
-    pub min_liquidity: f64,
-    pub min_holders: u32,
-    pub max_buy_tax: u8,
-    pub max_sell_tax: u8,
-    pub require_lp_lock: bool,
-    pub min_lp_lock_days: u32,
-    pub min_token_age_minutes: u32,
-    pub blacklisted_creators: Vec<String>,
-    pub min_score: u8,
+pub struct ApiToken {
+    /// Identifies the caller in logs and for rate limiting
+    pub name: String,
+    /// Usually a `${NAME}` secret reference rather than the token itself
+    pub token: String,
+    pub role: ApiRole,
+}
+
+/// HTTP server settings
+///
+/// Only read from the config file, never from the API, so a token cannot
+/// grant itself more access. The bind address and CORS origins apply at
+/// startup; tokens and the rate limit also apply on reload.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerSettings {
+    /// `ip:port` to listen on
+    pub bind_address: String,
+    /// Origins allowed to call the API from a browser, `*` for any
+    pub cors_origins: Vec<String>,
+    /// No tokens means no authentication, which is only allowed on loopback
+    pub api_tokens: Vec<ApiToken>,
+    /// Per caller, across all endpoints that change state
+    pub mutating_requests_per_minute: u32,
+}
+
+impl Default for ServerSettings {
+    fn default() -> Self {
+        Self {
+            bind_address: "127.0.0.1:3000".to_string(),
+            cors_origins: Vec::new(),
+            api_tokens: Vec::new(),
+            mutating_requests_per_minute: 60,
+        }
+    }
+}
+
+impl ServerSettings {
+    pub fn validate(&self) -> anyhow::Result<()> {
+        let mut errors = Vec::new();
+        
+        match self.bind_address.parse::<std::net::SocketAddr>() {
+            Ok(address) if !address.ip().is_loopback() && self.api_tokens.is_empty() => {
+                errors.push(format!("server.apiTokens must be set to bind to non-loopback address {}", address));
+            }
+            Ok(_) => {}
+            Err(_) => errors.push(format!("server.bindAddress must be ip:port (got {:?})", self.bind_address)),
+        }
+        
+        for origin in self.cors_origins.iter().filter(|origin| *origin != "*") {
+            if url::Url::parse(origin).is_err() {
+                errors.push(format!("server.corsOrigins has an invalid origin {:?}", origin));
+            }
+        }
+        
+        let mut names = std::collections::HashSet::new();
+        for token in &self.api_tokens {
+            if token.name.is_empty() || !names.insert(token.name.as_str()) {
+                errors.push(format!("server.apiTokens names must be unique and non-empty (got {:?})", token.name));
+            }
+            if token.token.len() < MIN_API_TOKEN_LEN && !secrets::contains_reference(&token.token) {
+                errors.push(format!("server.apiTokens token {:?} must be at least {} characters", token.name, MIN_API_TOKEN_LEN));
+            }
+        }
+        
+        if self.mutating_requests_per_minute == 0 {
+            errors.push("server.mutatingRequestsPerMinute must be greater than 0".to_string());
+        }
+        
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(SniperError::InvalidRequest(format!("Invalid server configuration:\n  {}", errors.join("\n  "))).into())
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -55,33 +268,19 @@ pub struct NotificationSettings {
     pub notify_on_trade: bool,
     pub notify_on_profit: bool,
     pub notify_on_loss: bool,
+    /// Discord or Slack webhook trades and exits are posted to, usually a `${NAME}` secret reference
+    #[serde(default)]
+    pub webhook_url: Option<String>,
 }
 
 impl Default for BotConfig {
     fn default() -> Self {
         Self {
             wallet_type: "phantom".to_string(),
-            rpc_url: "https://api.mainnet-beta.solana.com".to_string(),
+            rpc_url: DEFAULT_RPC_URL.to_string(),
             theme: Theme::Green,
-            trade_settings: TradeSettings {
-                default_buy_amount: 0.5,
-                default_slippage: 0.6,
-                default_take_profit: 50.0,
-                default_stop_loss: 30.0,
-                max_trades_per_day: 100,
-                min_time_between_trades_seconds: 60,
-            },
-            filter_settings: FilterSettings {
-                min_liquidity: 25.0,
-                min_holders: 50,
-                max_buy_tax: 10,
-                max_sell_tax: 10,
-                require_lp_lock: true,
-                min_lp_lock_days: 30,
-                min_token_age_minutes: 3,
-                blacklisted_creators: vec![],
-                min_score: 75,
-            },
+            trade_settings: TradeSettings::default(),
+            filter_settings: FilterSettings::default(),
             notification_settings: NotificationSettings {
                 enable_sound: true,
                 enable_desktop: true,
@@ -90,9 +289,17 @@ impl Default for BotConfig {
                 notify_on_trade: true,
                 notify_on_profit: true,
                 notify_on_loss: true,
+                webhook_url: None,
             },
+            scanner_settings: ScannerSettings::default(),
+            ui_settings: UiSettings::default(),
+            advanced_settings: AdvancedSettings::default(),
             auto_snipe: false,
             auto_sell: false,
+            auto_start: false,
+            profiles: Vec::new(),
+            profile_routes: Vec::new(),
+            active_profile: None,
         }
     }
 }
@@ -101,58 +308,70 @@ impl Default for BotConfig {
 #[wasm_bindgen]
 pub struct ConfigManager {
     config: BotConfig,
-    custom_settings: HashMap<String, JsValue>,
+    filter_settings: SharedFilterSettings,
+    profiles: SharedProfiles,
+    /// Stored as JSON so the manager can be shared across threads
+    custom_settings: HashMap<String, serde_json::Value>,
+    /// File the configuration was loaded from, if any
+    source_path: Option<PathBuf>,
+    /// Why the last reload from `source_path` was rejected
+    last_reload_error: Option<String>,
+    /// Kept out of `config` so the API can neither read nor change it
+    server_settings: ServerSettings,
+    /// Version history on disk, native targets only
+    store: Option<ConfigStore>,
 }
 
 #[wasm_bindgen]
 impl ConfigManager {
     #[wasm_bindgen(constructor)]
     pub fn new() -> Self {
-        Self {
-            config: BotConfig::default(),
-            custom_settings: HashMap::new(),
-        }
+        Self::with_filter_settings(Arc::new(RwLock::new(FilterSettings::default())))
     }
 
     /// Get the current configuration
     pub fn get_config(&self) -> Result<JsValue, JsValue> {
-        serde_wasm_bindgen::to_value(&self.config).map_err(|e| JsValue::from_str(&e.to_string()))
+        serde_wasm_bindgen::to_value(&self.config()).map_err(|e| JsValue::from_str(&e.to_string()))
     }
     
     /// Update the configuration
     pub fn update_config(&mut self, config: JsValue) -> Result<(), JsValue> {
         let new_config: BotConfig = serde_wasm_bindgen::from_value(config)?;
-        self.config = new_config;
+        self.set_config(new_config).map_err(|e| JsValue::from_str(&e.to_string()))?;
         console_log!("Configuration updated");
         Ok(())
     }
     
     /// Update trade settings
     pub fn update_trade_settings(&mut self, settings: JsValue) -> Result<(), JsValue> {
-        let new_settings: TradeSettings = serde_wasm_bindgen::from_value(settings)?;
-        self.config.trade_settings = new_settings;
+        let mut config = self.config();
+        config.trade_settings = serde_wasm_bindgen::from_value(settings)?;
+        self.set_config(config).map_err(|e| JsValue::from_str(&e.to_string()))?;
         console_log!("Trade settings updated");
         Ok(())
     }
     
     /// Update filter settings
     pub fn update_filter_settings(&mut self, settings: JsValue) -> Result<(), JsValue> {
-        let new_settings: FilterSettings = serde_wasm_bindgen::from_value(settings)?;
-        self.config.filter_settings = new_settings;
+        let mut config = self.config();
+        config.filter_settings = serde_wasm_bindgen::from_value(settings)?;
+        self.set_config(config).map_err(|e| JsValue::from_str(&e.to_string()))?;
         console_log!("Filter settings updated");
         Ok(())
     }
     
     /// Update notification settings
     pub fn update_notification_settings(&mut self, settings: JsValue) -> Result<(), JsValue> {
-        let new_settings: NotificationSettings = serde_wasm_bindgen::from_value(settings)?;
-        self.config.notification_settings = new_settings;
+        let mut config = self.config();
+        config.notification_settings = serde_wasm_bindgen::from_value(settings)?;
+        self.set_config(config).map_err(|e| JsValue::from_str(&e.to_string()))?;
         console_log!("Notification settings updated");
         Ok(())
     }
     
     /// Set a custom setting
     pub fn set_custom_setting(&mut self, key: &str, value: JsValue) -> Result<(), JsValue> {
+        let value: serde_json::Value = serde_wasm_bindgen::from_value(value)?;
         self.custom_settings.insert(key.to_string(), value);
         console_log!("Custom setting '{}' updated", key);
         Ok(())
@@ -160,7 +379,9 @@ impl ConfigManager {
     
     /// Get a custom setting
     pub fn get_custom_setting(&self, key: &str) -> JsValue {
-        self.custom_settings.get(key).cloned().unwrap_or(JsValue::NULL)
+        self.custom_settings.get(key)
+            .and_then(|value| serde_wasm_bindgen::to_value(value).ok())
+            .unwrap_or(JsValue::NULL)
     }
     
     /// Save configuration to local storage
@@ -171,7 +392,7 @@ impl ConfigManager {
             let storage = window.local_storage().map_err(|_| JsValue::from_str("Failed to get local storage"))?
                 .expect("local storage not available");
             
-            let config_json = serde_json::to_string(&self.config)
+            let config_json = serde_json::to_string(&self.config())
                 .map_err(|e| JsValue::from_str(&e.to_string()))?;
             
             storage.set_item("wolf63x_config", &config_json)
@@ -180,6 +401,12 @@ impl ConfigManager {
             console_log!("Configuration saved to local storage");
         }
         
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(store) = &self.store {
+            let version = store.save(&self.config()).map_err(|e| JsValue::from_str(&e.to_string()))?;
+            log::info!("Configuration saved as version {}", version.version);
+        }
+        
         Ok(())
     }
     
@@ -195,11 +422,561 @@ impl ConfigManager {
                 let config: BotConfig = serde_json::from_str(&config_json)
                     .map_err(|e| JsValue::from_str(&e.to_string()))?;
                 
-                self.config = config;
+                self.set_config(config).map_err(|e| JsValue::from_str(&e.to_string()))?;
                 console_log!("Configuration loaded from local storage");
             }
         }
         
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(store) = &self.store {
+            let latest = store.latest().map_err(|e| JsValue::from_str(&e.to_string()))?;
+            if let Some((version, config)) = latest {
+                self.swap_config(config).map_err(|e| JsValue::from_str(&e.to_string()))?;
+                log::info!("Configuration loaded from version {}", version.version);
+            }
+        }
+        
+        Ok(())
+    }
+}
+
+// Native Rust implementation (not exposed to WASM)
+impl ConfigManager {
+    /// Config manager whose filter settings are shared with a `TokenFilter`
+    pub fn with_filter_settings(filter_settings: SharedFilterSettings) -> Self {
+        let config = BotConfig {
+            filter_settings: filter_settings.read().unwrap().clone(),
+            ..BotConfig::default()
+        };
+        
+        Self {
+            config,
+            filter_settings,
+            profiles: Arc::new(RwLock::new(ProfileSet::default())),
+            custom_settings: HashMap::new(),
+            source_path: None,
+            last_reload_error: None,
+            server_settings: ServerSettings::default(),
+            store: None,
+        }
+    }
+    
+    /// Current configuration, with the live filter settings
+    pub fn config(&self) -> BotConfig {
+        let mut config = self.config.clone();
+        config.filter_settings = self.filter_settings.read().unwrap().clone();
+        config
+    }
+    
+    /// Share profiles with a `TokenFilter` so it routes tokens by this configuration
+    pub fn with_profiles(mut self, profiles: SharedProfiles) -> Self {
+        *profiles.write().unwrap() = self.profile_set();
+        self.profiles = profiles;
+        self
+    }
+    
+    /// Replace the configuration, pushing its filter settings and profiles to every component sharing them
+    ///
+    /// With a store attached the new configuration is also saved as a version.
+    pub fn set_config(&mut self, config: BotConfig) -> anyhow::Result<()> {
+        self.swap_config(config)?;
+        
+        // The new config is live either way; a failed save only costs history
+        if let Some(store) = &self.store {
+            if let Err(e) = store.save(&self.config()) {
+                log::warn!("Failed to save configuration version: {}", e);
+            }
+        }
+        Ok(())
+    }
+    
+    fn swap_config(&mut self, config: BotConfig) -> anyhow::Result<()> {
+        config.validate()?;
+        *self.filter_settings.write().unwrap() = config.filter_settings.clone();
+        if let Some(store) = &mut self.store {
+            store.set_keep(config.advanced_settings.kept_config_versions);
+        }
+        self.config = config;
+        *self.profiles.write().unwrap() = self.profile_set();
+        Ok(())
+    }
+    
+    /// Keep a version history on disk and restore the latest saved version
+    ///
+    /// The store should only hold versions of the file the configuration was
+    /// loaded from, if any. A file edited since the latest version was saved
+    /// is applied on top of that version, so its keys win while settings only
+    /// changed through the API are kept, and the result is saved as a new version.
+    pub fn attach_store(&mut self, mut store: ConfigStore) -> anyhow::Result<()> {
+        let latest = store.latest()?;
+        let file_modified = self.source_path.as_ref()
+            .and_then(|path| std::fs::metadata(path).and_then(|meta| meta.modified()).ok())
+            .map(DateTime::<Utc>::from);
+        
+        let restored = match (latest, self.source_path.clone()) {
+            (Some((version, config)), _) if file_modified.is_none_or(|modified| modified <= version.saved_at) => {
+                self.swap_config(config)?;
+                log::info!("Restored configuration version {}", version.version);
+                true
+            }
+            (Some((version, config)), Some(path)) => {
+                let (config, _) = crate::config_file::load_settings_onto(&path, config)?;
+                self.swap_config(config)?;
+                log::info!("Applied edits to {} on top of configuration version {}", path.display(), version.version);
+                false
+            }
+            _ => false,
+        };
+        
+        store.set_keep(self.config.advanced_settings.kept_config_versions);
+        if !restored {
+            store.save(&self.config())?;
+        }
+        self.store = Some(store);
+        Ok(())
+    }
+    
+    fn store(&self) -> anyhow::Result<&ConfigStore> {
+        self.store.as_ref().ok_or_else(|| SniperError::NotFound("Configuration history is not enabled".to_string()).into())
+    }
+    
+    /// Saved configuration versions, oldest first
+    pub fn versions(&self) -> anyhow::Result<Vec<ConfigVersion>> {
+        self.store()?.versions()
+    }
+    
+    /// Settings that changed between two saved versions
+    pub fn diff(&self, from: u64, to: u64) -> anyhow::Result<Vec<ConfigChange>> {
+        self.store()?.diff(from, to)
+    }
+    
+    /// Restore a saved version; the rollback itself is saved as a new version
+    pub fn rollback(&mut self, version: u64) -> anyhow::Result<BotConfig> {
+        let config = self.store()?.load(version)?;
+        self.set_config(config.clone())?;
+        Ok(config)
+    }
+    
+    fn profile_set(&self) -> ProfileSet {
+        ProfileSet {
+            profiles: self.config.profiles.clone(),
+            routes: self.config.profile_routes.clone(),
+        }
+    }
+    
+    /// Profile whose settings are currently the top-level ones
+    pub fn active_profile(&self) -> Option<&str> {
+        self.config.active_profile.as_deref()
+    }
+    
+    /// Profile by name
+    pub fn profile(&self, name: &str) -> Option<&StrategyProfile> {
+        self.config.profiles.iter().find(|profile| profile.name == name)
+    }
+    
+    /// Make a profile's trade and filter settings the top-level ones
+    pub fn activate_profile(&mut self, name: &str) -> anyhow::Result<BotConfig> {
+        let mut config = self.config();
+        config.activate_profile(name)?;
+        self.set_config(config.clone())?;
+        Ok(config)
+    }
+    
+    /// Load a config file and remember it for later reloads
+    pub fn load_file(&mut self, path: impl AsRef<Path>) -> anyhow::Result<()> {
+        let (config, server_settings) = crate::config_file::load_settings(&path)?;
+        self.set_config(config)?;
+        self.server_settings = server_settings;
+        self.source_path = Some(path.as_ref().to_path_buf());
+        self.last_reload_error = None;
+        Ok(())
+    }
+    
+    /// File the configuration was loaded from
+    pub fn source_path(&self) -> Option<&Path> {
+        self.source_path.as_deref()
+    }
+    
+    /// Why the last reload was rejected, `None` once a reload succeeds
+    pub fn last_reload_error(&self) -> Option<&str> {
+        self.last_reload_error.as_deref()
+    }
+    
+    /// Reload the config file, keeping the current configuration if the file is invalid
+    ///
+    /// The file is applied on top of the current configuration, so settings it
+    /// does not set, e.g. a scoring model set through the API, are kept.
+    pub fn reload(&mut self) -> anyhow::Result<BotConfig> {
+        let path = self.source_path.clone()
+            .ok_or_else(|| anyhow::anyhow!("Configuration was not loaded from a file"))?;
+        
+        let result = crate::config_file::load_settings_onto(&path, self.config())
+            .and_then(|(config, server_settings)| {
+                self.set_config(config.clone())?;
+                if server_settings.bind_address != self.server_settings.bind_address
+                    || server_settings.cors_origins != self.server_settings.cors_origins
+                {
+                    log::warn!("Server bind address and CORS origins only change on restart");
+                }
+                self.server_settings = server_settings;
+                Ok(config)
+            });
+        self.last_reload_error = result.as_ref().err().map(|e| e.to_string());
+        result
+    }
+    
+    /// HTTP server settings from the config file, defaults otherwise
+    pub fn server_settings(&self) -> &ServerSettings {
+        &self.server_settings
+    }
+    
+    /// Reload the config file whenever it changes on disk
+    ///
+    /// The parent directory is watched because many editors replace the file
+    /// rather than writing it in place. `on_reload` receives every config that
+    /// was swapped in, inside the Tokio runtime `watch` was called from, so it
+    /// may spawn tasks; rejected edits only update `last_reload_error`.
+    /// Watching stops when the returned watcher is dropped.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn watch(
+        manager: Arc<Mutex<Self>>,
+        on_reload: impl Fn(&BotConfig) + Send + 'static,
+    ) -> anyhow::Result<notify::RecommendedWatcher> {
+        use notify::{RecursiveMode, Watcher};
+        
+        let runtime = tokio::runtime::Handle::try_current()
+            .map_err(|_| anyhow::anyhow!("Watching the config file needs a Tokio runtime"))?;
+        let path = manager.lock().unwrap().source_path.clone()
+            .ok_or_else(|| anyhow::anyhow!("Configuration was not loaded from a file"))?;
+        let file_name = path.file_name()
+            .ok_or_else(|| anyhow::anyhow!("Invalid config path {}", path.display()))?
+            .to_os_string();
+        let dir = path.parent()
+            .filter(|dir| !dir.as_os_str().is_empty())
+            .unwrap_or(Path::new("."))
+            .to_path_buf();
+        
+        let (changed_tx, changed_rx) = std::sync::mpsc::channel();
+        let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+            let Ok(event) = event else { return };
+            let touches_config = event.paths.iter().any(|p| p.file_name() == Some(file_name.as_os_str()));
+            if touches_config && (event.kind.is_create() || event.kind.is_modify()) {
+                let _ = changed_tx.send(());
+            }
+        })?;
+        watcher.watch(&dir, RecursiveMode::NonRecursive)?;
+        
+        // Ends once the watcher, and with it the sender, is dropped
+        std::thread::spawn(move || {
+            let _runtime = runtime.enter();
+            while changed_rx.recv().is_ok() {
+                // A save usually fires several events; reload once they settle
+                while changed_rx.recv_timeout(RELOAD_DEBOUNCE).is_ok() {}
+                
+                let reloaded = manager.lock().unwrap().reload();
+                match reloaded {
+                    Ok(config) => {
+                        log::info!("Reloaded configuration from {}", path.display());
+                        on_reload(&config);
+                    }
+                    Err(e) => log::warn!("Keeping previous configuration: {}", e),
+                }
+            }
+        });
+        
+        Ok(watcher)
+    }
+}
+
+impl BotConfig {
+    /// Copy a profile's trade and filter settings into the top-level settings
+    pub fn activate_profile(&mut self, name: &str) -> Result<(), SniperError> {
+        let profile = self.profiles.iter().find(|profile| profile.name == name)
+            .ok_or_else(|| SniperError::NotFound(format!("Unknown profile: {}", name)))?;
+        
+        self.trade_settings = profile.trade_settings.clone();
+        self.filter_settings = profile.filter_settings.clone();
+        self.active_profile = Some(name.to_string());
         Ok(())
     }
+    
+    /// Check the configuration for out-of-range and inconsistent values
+    ///
+    /// Every problem is reported at once, one per line.
+    pub fn validate(&self) -> anyhow::Result<()> {
+        let mut errors = trade_settings_errors(&self.trade_settings);
+        errors.extend(filter_settings_errors(&self.filter_settings));
+        
+        if self.scanner_settings.interval_ms < MIN_SCAN_INTERVAL_MS {
+            errors.push(format!(
+                "scanner.interval must be at least {} ms (got {})",
+                MIN_SCAN_INTERVAL_MS, self.scanner_settings.interval_ms
+            ));
+        }
+        if self.scanner_settings.max_pairs == 0 {
+            errors.push("scanner.maxPairs must be greater than 0".to_string());
+        }
+        if self.ui_settings.refresh_rate_ms < MIN_UI_REFRESH_MS {
+            errors.push(format!(
+                "ui.refreshRate must be at least {} ms (got {})",
+                MIN_UI_REFRESH_MS, self.ui_settings.refresh_rate_ms
+            ));
+        }
+        if self.advanced_settings.max_pending_tx == 0 {
+            errors.push("advanced.maxPendingTx must be greater than 0".to_string());
+        }
+        if self.advanced_settings.kept_config_versions == 0 {
+            errors.push("advanced.keptConfigVersions must be greater than 0".to_string());
+        }
+        
+        // Endpoints referencing secrets are checked once resolved
+        let endpoints = std::iter::once(("rpcUrl", &self.rpc_url))
+            .chain(self.advanced_settings.rpc_endpoints.iter().map(|endpoint| ("advanced.rpcEndpoints", endpoint)))
+            .chain(self.advanced_settings.jito_endpoints.iter().map(|endpoint| ("advanced.jitoEndpoints", endpoint)))
+            .chain(self.notification_settings.webhook_url.iter().map(|url| ("ui.notifications.webhookUrl", url)));
+        for (setting, endpoint) in endpoints.filter(|(_, endpoint)| !secrets::contains_reference(endpoint)) {
+            if let Err(e) = url::Url::parse(endpoint) {
+                errors.push(format!("{}: {:?} is not a valid URL ({})", setting, endpoint, e));
+            }
+        }
+        
+        errors.extend(self.profile_errors());
+        
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(SniperError::InvalidRequest(format!("Invalid configuration:\n  {}", errors.join("\n  "))).into())
+        }
+    }
+    
+    fn profile_errors(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        let mut names = std::collections::HashSet::new();
+        
+        for profile in &self.profiles {
+            if profile.name.trim().is_empty() {
+                errors.push("profiles: profile names must not be empty".to_string());
+            } else if !names.insert(profile.name.as_str()) {
+                errors.push(format!("profiles: duplicate profile {:?}", profile.name));
+            }
+            
+            let prefix = format!("profile {:?}: ", profile.name);
+            let exit = &profile.exit_plan;
+            let mut profile_errors = trade_settings_errors(&profile.trade_settings);
+            profile_errors.extend(filter_settings_errors(&profile.filter_settings));
+            if exit.take_profit_percent <= 0.0 {
+                profile_errors.push(format!("exit take profit must be greater than 0 (got {})", exit.take_profit_percent));
+            }
+            if exit.stop_loss_percent <= 0.0 || exit.stop_loss_percent >= 100.0 {
+                profile_errors.push(format!("exit stop loss must be between 0 and 100 percent (got {})", exit.stop_loss_percent));
+            }
+            if let Some(trailing) = exit.trailing_stop_percent.filter(|t| *t <= 0.0 || *t >= 100.0) {
+                profile_errors.push(format!("exit trailing stop must be between 0 and 100 percent (got {})", trailing));
+            }
+            errors.extend(profile_errors.into_iter().map(|error| format!("{}{}", prefix, error)));
+        }
+        
+        for route in &self.profile_routes {
+            let Some(profile) = self.profiles.iter().find(|profile| profile.name == route.profile) else {
+                errors.push(format!("profile routes: unknown profile {:?}", route.profile));
+                continue;
+            };
+            if let Some(Err(e)) = route.rule.as_ref().map(|rule| rules::check_expression_for(&profile.filter_settings, rule)) {
+                errors.push(format!("profile routes: route to {:?}: {}", route.profile, e));
+            }
+        }
+        
+        if let Some(active) = &self.active_profile {
+            if !names.contains(active.as_str()) {
+                errors.push(format!("active profile {:?} does not exist", active));
+            }
+        }
+        
+        errors
+    }
+}
+
+fn trade_settings_errors(trade: &TradeSettings) -> Vec<String> {
+    let mut errors = Vec::new();
+    
+    if trade.min_buy_amount <= 0.0 {
+        errors.push(format!("trading.minBuyAmount must be greater than 0 (got {})", trade.min_buy_amount));
+    }
+    if trade.min_buy_amount > trade.max_buy_amount {
+        errors.push(format!(
+            "trading.minBuyAmount ({}) must not exceed trading.maxBuyAmount ({})",
+            trade.min_buy_amount, trade.max_buy_amount
+        ));
+    }
+    if trade.default_buy_amount < trade.min_buy_amount || trade.default_buy_amount > trade.max_buy_amount {
+        errors.push(format!(
+            "default buy amount {} must be between trading.minBuyAmount ({}) and trading.maxBuyAmount ({})",
+            trade.default_buy_amount, trade.min_buy_amount, trade.max_buy_amount
+        ));
+    }
+    if trade.default_slippage <= 0.0 || trade.default_slippage > 50.0 {
+        errors.push(format!("trading.maxSlippage must be between 0 and 50 percent (got {})", trade.default_slippage));
+    }
+    if trade.default_take_profit <= 0.0 {
+        errors.push(format!("trading.autoSell.profitTarget must be greater than 0 (got {})", trade.default_take_profit));
+    }
+    if trade.default_stop_loss <= 0.0 || trade.default_stop_loss >= 100.0 {
+        errors.push(format!("trading.autoSell.stopLoss must be between 0 and 100 percent (got {})", trade.default_stop_loss));
+    }
+    
+    errors
+}
+
+fn filter_settings_errors(filters: &FilterSettings) -> Vec<String> {
+    let mut errors = Vec::new();
+    
+    if filters.min_liquidity < 0.0 {
+        errors.push(format!("scanner.minLiquidity must not be negative (got {})", filters.min_liquidity));
+    }
+    if filters.max_buy_tax > 100 || filters.max_sell_tax > 100 {
+        errors.push("filters: tax limits must be between 0 and 100 percent".to_string());
+    }
+    if filters.min_score > 100 {
+        errors.push(format!("filters: min score must be between 0 and 100 (got {})", filters.min_score));
+    }
+    if let (Some(min), Some(max)) = (filters.min_total_supply, filters.max_total_supply) {
+        if min > max {
+            errors.push(format!("filters.minTotalSupply ({}) must not exceed filters.maxTotalSupply ({})", min, max));
+        }
+    }
+    if filters.min_curve_progress > filters.max_curve_progress {
+        errors.push(format!(
+            "filters: min curve progress ({}) must not exceed max curve progress ({})",
+            filters.min_curve_progress, filters.max_curve_progress
+        ));
+    }
+    if let Err(e) = rules::check_rules(filters) {
+        errors.push(format!("filters: {}", e));
+    }
+    
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn errors(config: &BotConfig) -> String {
+        config.validate().unwrap_err().to_string()
+    }
+
+    #[test]
+    fn default_config_is_valid() {
+        BotConfig::default().validate().unwrap();
+    }
+
+    #[test]
+    fn rejects_min_buy_above_max() {
+        let mut config = BotConfig::default();
+        config.trade_settings.min_buy_amount = 5.0;
+        config.trade_settings.max_buy_amount = 1.0;
+        assert!(errors(&config).contains("trading.minBuyAmount (5) must not exceed trading.maxBuyAmount (1)"));
+    }
+
+    #[test]
+    fn rejects_slippage_out_of_range() {
+        let mut config = BotConfig::default();
+        config.trade_settings.default_slippage = 80.0;
+        assert!(errors(&config).contains("trading.maxSlippage"));
+    }
+
+    #[test]
+    fn reports_every_problem_at_once() {
+        let mut config = BotConfig::default();
+        config.scanner_settings.max_pairs = 0;
+        config.ui_settings.refresh_rate_ms = 10;
+        config.advanced_settings.max_pending_tx = 0;
+        config.advanced_settings.rpc_endpoints = vec!["not a url".to_string()];
+
+        let errors = errors(&config);
+        assert!(errors.contains("scanner.maxPairs"));
+        assert!(errors.contains("ui.refreshRate"));
+        assert!(errors.contains("advanced.maxPendingTx"));
+        assert!(errors.contains("advanced.rpcEndpoints"));
+    }
+
+    #[test]
+    fn endpoints_with_secrets_are_checked_once_resolved() {
+        let config = BotConfig {
+            rpc_url: "${RPC_URL}".to_string(),
+            ..BotConfig::default()
+        };
+        config.validate().unwrap();
+    }
+
+    /// A scoring model only the API knows about, unlike any the file sets
+    fn api_scoring() -> crate::scoring::ScoringModel {
+        crate::scoring::ScoringModel { base: 42.0, features: Vec::new() }
+    }
+
+    fn temp_config(name: &str, json: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("wolf63x-{}-{}.json", name, std::process::id()));
+        std::fs::write(&path, json).unwrap();
+        path
+    }
+
+    #[test]
+    fn api_set_scoring_model_survives_a_reload() {
+        let path = temp_config("reload", r#"{ "scanner": { "interval": 1000 } }"#);
+        let mut manager = ConfigManager::new();
+        manager.load_file(&path).unwrap();
+
+        let mut config = manager.config();
+        config.filter_settings.scoring = api_scoring();
+        manager.set_config(config).unwrap();
+
+        std::fs::write(&path, r#"{ "scanner": { "interval": 2000 } }"#).unwrap();
+        let reloaded = manager.reload();
+        std::fs::remove_file(&path).unwrap();
+
+        let reloaded = reloaded.unwrap();
+        assert_eq!(reloaded.scanner_settings.interval_ms, 2000);
+        assert_eq!(reloaded.filter_settings.scoring.base, 42.0);
+        assert_eq!(manager.config().filter_settings.scoring.base, 42.0);
+    }
+
+    #[test]
+    fn file_edited_before_a_restart_is_applied_on_top_of_the_saved_version() {
+        let path = temp_config("restart", r#"{ "scanner": { "interval": 1000 } }"#);
+        let dir = std::env::temp_dir().join(format!("wolf63x-restart-history-{}", std::process::id()));
+
+        let mut manager = ConfigManager::new();
+        manager.load_file(&path).unwrap();
+        manager.attach_store(ConfigStore::open(&dir, 10).unwrap()).unwrap();
+        let mut config = manager.config();
+        config.filter_settings.scoring = api_scoring();
+        manager.set_config(config).unwrap();
+
+        std::thread::sleep(Duration::from_millis(20));
+        std::fs::write(&path, r#"{ "scanner": { "interval": 2000 } }"#).unwrap();
+        let mut restarted = ConfigManager::new();
+        restarted.load_file(&path).unwrap();
+        let attached = restarted.attach_store(ConfigStore::open(&dir, 10).unwrap());
+        std::fs::remove_file(&path).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        attached.unwrap();
+        assert_eq!(restarted.config().scanner_settings.interval_ms, 2000);
+        assert_eq!(restarted.config().filter_settings.scoring.base, 42.0);
+    }
+
+    #[test]
+    fn rejects_unknown_active_profile() {
+        let config = BotConfig {
+            active_profile: Some("missing".to_string()),
+            ..BotConfig::default()
+        };
+        assert!(errors(&config).contains("active profile \"missing\" does not exist"));
+    }
+
+    #[test]
+    fn activating_an_unknown_profile_is_not_found() {
+        let err = BotConfig::default().activate_profile("missing").unwrap_err();
+        assert_eq!(err.code(), "not_found");
+    }
 }
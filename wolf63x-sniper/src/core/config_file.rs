@@ -0,0 +1,563 @@
+use serde::Deserialize;
+use anyhow::{Result, anyhow};
+use std::path::Path;
+use crate::config::{ApiToken, BotConfig, ServerSettings, Theme};
+use crate::filters::PatternList;
+use crate::profiles::{ProfileRoute, StrategyProfile};
+use crate::rules::{CustomRule, RuleExpr};
+use crate::scoring::ScoringModel;
+
+/// Copy every key set in a file section onto the settings of the same name
+macro_rules! overlay {
+    ($section:expr => $settings:expr; $($field:ident),* $(,)?) => {
+        $(
+            if let Some(value) = $section.$field {
+                $settings.$field = value;
+            }
+        )*
+    };
+}
+
+/// On-disk configuration, as shipped in `config.json`
+///
+/// Every section and key is optional; anything left out keeps its default.
+/// Unknown keys are rejected so typos do not silently fall back to defaults.
+/// Keys the bot knows but has no use for, such as those carried over from
+/// the EVM version, are ignored with a warning.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ConfigFile {
+    #[serde(default)]
+    pub bot: BotSection,
+    #[serde(default)]
+    pub trading: TradingSection,
+    #[serde(default)]
+    pub scanner: ScannerSection,
+    #[serde(default)]
+    pub filters: FiltersSection,
+    #[serde(default)]
+    pub ui: UiSection,
+    #[serde(default)]
+    pub advanced: AdvancedSection,
+    #[serde(default)]
+    pub server: ServerSection,
+    /// Strategy profiles, written in the same shape as `/api/profiles` returns them
+    pub profiles: Option<Vec<StrategyProfile>>,
+    pub profile_routes: Option<Vec<ProfileRoute>>,
+    /// Profile whose settings replace the `trading` and `filters` sections
+    pub active_profile: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct BotSection {
+    /// Informational only; ignored with a warning, see `apply_to`
+    pub name: Option<String>,
+    pub version: Option<String>,
+    pub auto_start: Option<bool>,
+    pub theme: Option<String>,
+    pub wallet_type: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct TradingSection {
+    pub max_buy_amount: Option<f64>,
+    pub min_buy_amount: Option<f64>,
+    pub default_buy_amount: Option<f64>,
+    pub max_slippage: Option<f64>,
+    /// Carried over from the EVM version of the bot; ignored with a warning
+    pub gas_limit: Option<u32>,
+    pub max_trades_per_day: Option<u32>,
+    pub auto_snipe: Option<bool>,
+    pub auto_sell: Option<AutoSellSection>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct AutoSellSection {
+    pub enabled: Option<bool>,
+    pub profit_target: Option<f64>,
+    pub stop_loss: Option<f64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ScannerSection {
+    pub enabled: Option<bool>,
+    pub interval: Option<u32>,
+    pub max_pairs: Option<usize>,
+    pub min_liquidity: Option<f64>,
+    pub exclude_tokens: Option<Vec<String>>,
+    /// Pump.fun only quotes against SOL, so anything else is ignored with a warning
+    pub include_pairs: Option<Vec<String>>,
+}
+
+/// Every filter setting, by its camelCase name
+///
+/// `minLiquidity` and `excludedTokens` can also be set as `scanner.minLiquidity`
+/// and `scanner.excludeTokens`; these keys win when both are set.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct FiltersSection {
+    pub min_liquidity: Option<f64>,
+    pub excluded_tokens: Option<Vec<String>>,
+    pub min_holders: Option<u32>,
+    pub min_total_supply: Option<f64>,
+    pub max_total_supply: Option<f64>,
+    #[serde(alias = "excludeHoneypots")]
+    pub exclude_honeypot: Option<bool>,
+    /// Solana tokens are "renounced" once their mint authority is revoked
+    pub exclude_renounced: Option<bool>,
+    pub require_lp_lock: Option<bool>,
+    #[serde(rename = "minLPLocked", alias = "minLpLockDays")]
+    pub min_lp_locked: Option<u32>,
+    pub min_token_age_minutes: Option<u32>,
+    pub min_score: Option<u8>,
+    pub max_buy_tax: Option<u8>,
+    pub max_sell_tax: Option<u8>,
+    pub blacklisted_creators: Option<Vec<String>>,
+    pub max_similar_tokens: Option<u8>,
+    pub similarity_window_minutes: Option<u32>,
+    pub reject_mint_authority: Option<bool>,
+    pub reject_freeze_authority: Option<bool>,
+    pub reject_risky_extensions: Option<bool>,
+    pub max_top10_holder_percent: Option<f64>,
+    pub max_creator_holding_percent: Option<f64>,
+    pub max_same_funder_wallets: Option<u32>,
+    pub max_bundle_percent: Option<f64>,
+    pub max_round_trip_loss_percent: Option<f64>,
+    /// Rule expression, e.g. `{ "all": [{ "rule": "liquidity" }, { "any": [...] }] }`
+    pub rules: Option<RuleExpr>,
+    pub custom_rules: Option<Vec<CustomRule>>,
+    pub scoring: Option<ScoringModel>,
+    pub name_allow: Option<PatternList>,
+    pub name_deny: Option<PatternList>,
+    pub symbol_allow: Option<PatternList>,
+    pub symbol_deny: Option<PatternList>,
+    pub description_allow_keywords: Option<Vec<String>>,
+    pub description_deny_keywords: Option<Vec<String>>,
+    pub require_website: Option<bool>,
+    pub require_twitter: Option<bool>,
+    pub require_telegram: Option<bool>,
+    pub min_curve_progress: Option<f64>,
+    pub max_curve_progress: Option<f64>,
+    pub min_sol_inflow_per_minute: Option<f64>,
+    pub min_buy_sell_ratio: Option<f64>,
+    pub min_unique_buyers: Option<u32>,
+    pub momentum_window_minutes: Option<u32>,
+    pub early_buyer_minutes: Option<u32>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct UiSection {
+    pub refresh_rate: Option<u32>,
+    /// Neither dashboard draws charts or converts prices; both are ignored with a warning
+    pub chart_timeframe: Option<String>,
+    pub default_currency: Option<String>,
+    pub notifications: Option<NotificationsSection>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct NotificationsSection {
+    pub enabled: Option<bool>,
+    pub sound: Option<bool>,
+    pub desktop: Option<bool>,
+    pub webhook_url: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct AdvancedSection {
+    pub rpc_endpoints: Option<Vec<String>>,
+    pub jito_endpoints: Option<Vec<String>>,
+    /// Carried over from the EVM version of the bot; Solana has no gas price
+    pub max_gwei: Option<f64>,
+    pub max_pending_tx: Option<u32>,
+    pub retry_attempts: Option<u32>,
+    pub retry_delay: Option<u32>,
+    pub kept_config_versions: Option<usize>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ServerSection {
+    pub bind_address: Option<String>,
+    pub cors_origins: Option<Vec<String>>,
+    pub api_tokens: Option<Vec<ApiToken>>,
+    pub mutating_requests_per_minute: Option<u32>,
+}
+
+impl ServerSection {
+    /// Overlay the section onto the default server settings
+    pub fn settings(self) -> ServerSettings {
+        let defaults = ServerSettings::default();
+        ServerSettings {
+            bind_address: self.bind_address.unwrap_or(defaults.bind_address),
+            cors_origins: self.cors_origins.unwrap_or(defaults.cors_origins),
+            api_tokens: self.api_tokens.unwrap_or(defaults.api_tokens),
+            mutating_requests_per_minute: self.mutating_requests_per_minute
+                .unwrap_or(defaults.mutating_requests_per_minute),
+        }
+    }
+}
+
+impl ConfigFile {
+    /// Parse a config file, picking the format from its extension
+    ///
+    /// `.toml` files are read as TOML, anything else as JSON with `//` and
+    /// `/* */` comments allowed.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| anyhow!("Cannot read config file {}: {}", path.display(), e))?;
+
+        let is_toml = path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("toml"));
+        if is_toml {
+            toml::from_str(&text).map_err(|e| anyhow!("Invalid TOML in {}: {}", path.display(), e))
+        } else {
+            let stripped = json_comments::StripComments::new(text.as_bytes());
+            serde_json::from_reader(stripped)
+                .map_err(|e| anyhow!("Invalid JSON in {}: {}", path.display(), e))
+        }
+    }
+
+    /// Overlay the file onto `config`, leaving unset keys untouched
+    pub fn apply_to(self, config: &mut BotConfig) -> Result<()> {
+        let bot = self.bot;
+        if bot.name.is_some() || bot.version.is_some() {
+            log::warn!("bot.name and bot.version are informational and ignored");
+        }
+        if let Some(auto_start) = bot.auto_start {
+            config.auto_start = auto_start;
+        }
+        if let Some(theme) = bot.theme {
+            config.theme = match theme.to_ascii_lowercase().as_str() {
+                "violet" => Theme::Violet,
+                "green" => Theme::Green,
+                "dark" => Theme::Dark,
+                _ => return Err(anyhow!("bot.theme must be one of violet, green, dark (got {:?})", theme)),
+            };
+        }
+        if let Some(wallet_type) = bot.wallet_type {
+            config.wallet_type = wallet_type;
+        }
+
+        let trading = self.trading;
+        let trade = &mut config.trade_settings;
+        if let Some(max_buy_amount) = trading.max_buy_amount {
+            trade.max_buy_amount = max_buy_amount;
+        }
+        if let Some(min_buy_amount) = trading.min_buy_amount {
+            trade.min_buy_amount = min_buy_amount;
+        }
+        // Without an explicit default, buy the minimum rather than an arbitrary amount
+        trade.default_buy_amount = trading.default_buy_amount
+            .unwrap_or_else(|| trade.default_buy_amount.clamp(trade.min_buy_amount, trade.max_buy_amount.max(trade.min_buy_amount)));
+        if let Some(max_slippage) = trading.max_slippage {
+            trade.default_slippage = max_slippage;
+        }
+        if trading.gas_limit.is_some() {
+            log::warn!("trading.gasLimit has no effect on Solana and is ignored");
+        }
+        if let Some(max_trades_per_day) = trading.max_trades_per_day {
+            trade.max_trades_per_day = max_trades_per_day;
+        }
+        if let Some(auto_snipe) = trading.auto_snipe {
+            config.auto_snipe = auto_snipe;
+        }
+        if let Some(auto_sell) = trading.auto_sell {
+            if let Some(enabled) = auto_sell.enabled {
+                config.auto_sell = enabled;
+            }
+            if let Some(profit_target) = auto_sell.profit_target {
+                config.trade_settings.default_take_profit = profit_target;
+            }
+            if let Some(stop_loss) = auto_sell.stop_loss {
+                config.trade_settings.default_stop_loss = stop_loss;
+            }
+        }
+
+        let scanner = self.scanner;
+        let filters = &mut config.filter_settings;
+        if let Some(enabled) = scanner.enabled {
+            config.scanner_settings.enabled = enabled;
+        }
+        if let Some(interval) = scanner.interval {
+            config.scanner_settings.interval_ms = interval;
+        }
+        if let Some(max_pairs) = scanner.max_pairs {
+            config.scanner_settings.max_pairs = max_pairs;
+        }
+        for pair in scanner.include_pairs.iter().flatten().filter(|pair| !pair.eq_ignore_ascii_case("SOL")) {
+            log::warn!("scanner.includePairs: Pump.fun does not quote against {:?}, ignored", pair);
+        }
+        if let Some(min_liquidity) = scanner.min_liquidity {
+            filters.min_liquidity = min_liquidity;
+        }
+        if let Some(exclude_tokens) = scanner.exclude_tokens {
+            filters.excluded_tokens = exclude_tokens;
+        }
+
+        let file_filters = self.filters;
+        overlay!(file_filters => filters;
+            min_liquidity, excluded_tokens, min_holders, require_lp_lock, min_token_age_minutes,
+            min_score, max_buy_tax, max_sell_tax, blacklisted_creators, max_similar_tokens,
+            similarity_window_minutes, reject_mint_authority, reject_freeze_authority,
+            reject_risky_extensions, max_top10_holder_percent, max_creator_holding_percent,
+            max_same_funder_wallets, max_bundle_percent, max_round_trip_loss_percent,
+            custom_rules, scoring, name_allow, name_deny, symbol_allow, symbol_deny,
+            description_allow_keywords, description_deny_keywords, require_website,
+            require_twitter, require_telegram, min_curve_progress, max_curve_progress,
+            min_sol_inflow_per_minute, min_buy_sell_ratio, min_unique_buyers,
+            momentum_window_minutes, early_buyer_minutes,
+        );
+        if file_filters.rules.is_some() {
+            filters.rules = file_filters.rules;
+        }
+        if file_filters.min_total_supply.is_some() {
+            filters.min_total_supply = file_filters.min_total_supply;
+        }
+        if file_filters.max_total_supply.is_some() {
+            filters.max_total_supply = file_filters.max_total_supply;
+        }
+        if let Some(exclude_honeypot) = file_filters.exclude_honeypot {
+            filters.exclude_honeypots = exclude_honeypot;
+        }
+        if file_filters.exclude_renounced == Some(true) {
+            log::warn!("filters.excludeRenounced is ignored: rejecting revoked mint authorities would reject every safe token");
+        }
+        if let Some(min_lp_locked) = file_filters.min_lp_locked {
+            filters.min_lp_lock_days = min_lp_locked;
+        }
+
+        let ui = self.ui;
+        if let Some(refresh_rate) = ui.refresh_rate {
+            config.ui_settings.refresh_rate_ms = refresh_rate;
+        }
+        if ui.chart_timeframe.is_some() {
+            log::warn!("ui.chartTimeframe is ignored: the dashboards draw no charts");
+        }
+        if ui.default_currency.is_some() {
+            log::warn!("ui.defaultCurrency is ignored: amounts are shown in SOL");
+        }
+        if let Some(notifications) = ui.notifications {
+            let enabled = notifications.enabled.unwrap_or(true);
+            let notify = &mut config.notification_settings;
+            if let Some(sound) = notifications.sound {
+                notify.enable_sound = sound;
+            }
+            if let Some(desktop) = notifications.desktop {
+                notify.enable_desktop = desktop;
+            }
+            if notifications.webhook_url.is_some() {
+                notify.webhook_url = notifications.webhook_url;
+            }
+            if !enabled {
+                notify.enable_sound = false;
+                notify.enable_desktop = false;
+                notify.enable_email = false;
+                notify.webhook_url = None;
+            }
+        }
+
+        let advanced = self.advanced;
+        if let Some(endpoints) = advanced.rpc_endpoints {
+            let mut endpoints = endpoints.into_iter();
+            match endpoints.next() {
+                Some(primary) => config.rpc_url = primary,
+                None => return Err(anyhow!("advanced.rpcEndpoints must list at least one endpoint")),
+            }
+            config.advanced_settings.rpc_endpoints = endpoints.collect();
+        }
+        if let Some(jito_endpoints) = advanced.jito_endpoints {
+            config.advanced_settings.jito_endpoints = jito_endpoints;
+        }
+        if advanced.max_gwei.is_some() {
+            log::warn!("advanced.maxGwei has no effect on Solana and is ignored");
+        }
+        if let Some(max_pending_tx) = advanced.max_pending_tx {
+            config.advanced_settings.max_pending_tx = max_pending_tx;
+        }
+        if let Some(retry_attempts) = advanced.retry_attempts {
+            config.advanced_settings.retry_attempts = retry_attempts;
+        }
+        if let Some(retry_delay) = advanced.retry_delay {
+            config.advanced_settings.retry_delay_ms = retry_delay;
+        }
+        if let Some(kept_config_versions) = advanced.kept_config_versions {
+            config.advanced_settings.kept_config_versions = kept_config_versions;
+        }
+
+        if let Some(profiles) = self.profiles {
+            config.profiles = profiles;
+        }
+        if let Some(profile_routes) = self.profile_routes {
+            config.profile_routes = profile_routes;
+        }
+        if let Some(active_profile) = self.active_profile {
+            config.activate_profile(&active_profile)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Load, map and validate a config file on top of the defaults
+pub fn load_config(path: impl AsRef<Path>) -> Result<BotConfig> {
+    load_settings(path).map(|(config, _)| config)
+}
+
+/// Like `load_config`, also returning the server section
+pub fn load_settings(path: impl AsRef<Path>) -> Result<(BotConfig, ServerSettings)> {
+    load_settings_onto(path, BotConfig::default())
+}
+
+/// Like `load_settings`, overlaying the file onto `config` instead of the defaults
+///
+/// Used for reloads, so settings the file does not set, e.g. ones changed
+/// through the API, are kept.
+pub fn load_settings_onto(path: impl AsRef<Path>, mut config: BotConfig) -> Result<(BotConfig, ServerSettings)> {
+    let mut file = ConfigFile::load(&path)?;
+    let server = std::mem::take(&mut file.server).settings();
+    server.validate()
+        .map_err(|e| anyhow!("{} ({})", e, path.as_ref().display()))?;
+
+    file.apply_to(&mut config)?;
+    config.validate()
+        .map_err(|e| anyhow!("{} ({})", e, path.as_ref().display()))?;
+    Ok((config, server))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn apply(json: &str) -> Result<BotConfig> {
+        let file: ConfigFile = serde_json::from_str(json)?;
+        let mut config = BotConfig::default();
+        file.apply_to(&mut config)?;
+        Ok(config)
+    }
+
+    #[test]
+    fn maps_sections_onto_the_config() {
+        let config = apply(r#"{
+            "trading": { "maxBuyAmount": 5, "minBuyAmount": 0.2, "maxSlippage": 2, "autoSell": { "enabled": true, "stopLoss": 15 } },
+            "scanner": { "enabled": false, "interval": 2000, "maxPairs": 50, "minLiquidity": 3, "excludeTokens": ["abc"] },
+            "filters": { "minHolders": 25, "minLPLocked": 7 },
+            "ui": { "refreshRate": 250 },
+            "advanced": { "maxPendingTx": 2, "retryAttempts": 4, "retryDelay": 500 }
+        }"#).unwrap();
+
+        assert_eq!(config.trade_settings.max_buy_amount, 5.0);
+        assert_eq!(config.trade_settings.min_buy_amount, 0.2);
+        assert_eq!(config.trade_settings.default_slippage, 2.0);
+        assert!(config.auto_sell);
+        assert_eq!(config.trade_settings.default_stop_loss, 15.0);
+        assert!(!config.scanner_settings.enabled);
+        assert_eq!(config.scanner_settings.interval_ms, 2000);
+        assert_eq!(config.scanner_settings.max_pairs, 50);
+        assert_eq!(config.filter_settings.min_liquidity, 3.0);
+        assert_eq!(config.filter_settings.excluded_tokens, vec!["abc".to_string()]);
+        assert_eq!(config.filter_settings.min_holders, 25);
+        assert_eq!(config.filter_settings.min_lp_lock_days, 7);
+        assert_eq!(config.ui_settings.refresh_rate_ms, 250);
+        assert_eq!(config.advanced_settings.max_pending_tx, 2);
+        assert_eq!(config.advanced_settings.retry_attempts, 4);
+        assert_eq!(config.advanced_settings.retry_delay_ms, 500);
+    }
+
+    #[test]
+    fn maps_every_filter_setting() {
+        let config = apply(r#"{
+            "filters": {
+                "excludeHoneypots": false,
+                "maxSimilarTokens": 2,
+                "maxBundlePercent": 15,
+                "minCurveProgress": 5,
+                "nameDeny": ["rug"],
+                "customRules": [{ "name": "big", "field": "market_cap", "op": "gte", "value": 10000 }],
+                "rules": { "all": [{ "rule": "liquidity" }, { "rule": "big" }] },
+                "scoring": { "base": 10, "features": [{ "name": "curve", "signal": "bonding_curve_progress", "bands": [{ "points": 5 }] }] }
+            }
+        }"#).unwrap();
+
+        let filters = &config.filter_settings;
+        assert!(!filters.exclude_honeypots);
+        assert_eq!(filters.max_similar_tokens, 2);
+        assert_eq!(filters.max_bundle_percent, 15.0);
+        assert_eq!(filters.min_curve_progress, 5.0);
+        assert_eq!(filters.name_deny.find_match("RUG pull"), Some("rug"));
+        assert_eq!(filters.custom_rules[0].name, "big");
+        assert!(matches!(&filters.rules, Some(RuleExpr::All(children)) if children.len() == 2));
+        assert_eq!(filters.scoring.base, 10.0);
+        assert_eq!(filters.scoring.features[0].name, "curve");
+    }
+
+    #[test]
+    fn leaves_unset_keys_untouched() {
+        let defaults = BotConfig::default();
+        let config = apply(r#"{ "scanner": { "interval": 3000 } }"#).unwrap();
+
+        assert_eq!(config.scanner_settings.interval_ms, 3000);
+        assert_eq!(config.scanner_settings.max_pairs, defaults.scanner_settings.max_pairs);
+        assert_eq!(config.trade_settings.max_buy_amount, defaults.trade_settings.max_buy_amount);
+        assert_eq!(config.rpc_url, defaults.rpc_url);
+    }
+
+    #[test]
+    fn default_buy_amount_falls_back_to_the_minimum() {
+        let config = apply(r#"{ "trading": { "minBuyAmount": 1, "maxBuyAmount": 2 } }"#).unwrap();
+        assert_eq!(config.trade_settings.default_buy_amount, 1.0);
+    }
+
+    #[test]
+    fn first_rpc_endpoint_is_primary_and_the_rest_are_fallbacks() {
+        let config = apply(r#"{ "advanced": { "rpcEndpoints": ["https://a.example", "https://b.example"] } }"#).unwrap();
+        assert_eq!(config.rpc_url, "https://a.example");
+        assert_eq!(config.advanced_settings.rpc_endpoints, vec!["https://b.example".to_string()]);
+
+        assert!(apply(r#"{ "advanced": { "rpcEndpoints": [] } }"#).is_err());
+    }
+
+    #[test]
+    fn ignores_known_but_unsupported_keys() {
+        let defaults = serde_json::to_value(BotConfig::default()).unwrap();
+        let config = apply(r#"{
+            "bot": { "name": "Wolf63x Sniper", "version": "0.2.0" },
+            "trading": { "gasLimit": 300000 },
+            "scanner": { "includePairs": ["SOL", "USDC", "USDT"] },
+            "filters": { "excludeRenounced": true },
+            "ui": { "chartTimeframe": "1h", "defaultCurrency": "USD" },
+            "advanced": { "maxGwei": 100 }
+        }"#).unwrap();
+
+        assert_eq!(serde_json::to_value(config).unwrap(), defaults);
+        assert!(apply(r#"{ "bot": { "theme": "blue" } }"#).is_err());
+    }
+
+    #[test]
+    fn rejects_unknown_keys() {
+        assert!(serde_json::from_str::<ConfigFile>(r#"{ "trading": { "maxBuyAmmount": 1 } }"#).is_err());
+    }
+
+    #[test]
+    fn shipped_config_loads() {
+        let (config, server) = load_settings(concat!(env!("CARGO_MANIFEST_DIR"), "/config.json")).unwrap();
+        assert_eq!(config.trade_settings.max_buy_amount, 10.0);
+        assert_eq!(server.bind_address, "127.0.0.1:3000");
+    }
+
+    #[test]
+    fn loads_toml_by_extension() {
+        let path = std::env::temp_dir().join(format!("wolf63x-config-{}.toml", std::process::id()));
+        std::fs::write(&path, "[trading]\nmaxBuyAmount = 3.0\n").unwrap();
+        let config = load_config(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(config.unwrap().trade_settings.max_buy_amount, 3.0);
+    }
+}
@@ -0,0 +1,260 @@
+use serde::{Serialize, Deserialize};
+use anyhow::{Result, anyhow};
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use crate::config::BotConfig;
+use crate::error::SniperError;
+
+/// Saved versions kept for rollback unless configured otherwise
+pub const DEFAULT_KEPT_VERSIONS: usize = 20;
+
+const FILE_PREFIX: &str = "config-";
+const FILE_SUFFIX: &str = ".json";
+
+/// A saved configuration version
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigVersion {
+    pub version: u64,
+    pub saved_at: DateTime<Utc>,
+}
+
+/// On-disk layout of a saved version
+#[derive(Serialize, Deserialize)]
+struct StoredConfig {
+    #[serde(flatten)]
+    version: ConfigVersion,
+    config: BotConfig,
+}
+
+/// A setting that differs between two versions, by dotted path
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigChange {
+    pub path: String,
+    /// `None` when the setting did not exist in the older version
+    pub old: Option<Value>,
+    /// `None` when the setting was removed
+    pub new: Option<Value>,
+}
+
+/// Versioned configuration history on disk, one JSON file per version
+#[derive(Debug, Clone)]
+pub struct ConfigStore {
+    dir: PathBuf,
+    keep: usize,
+}
+
+impl ConfigStore {
+    /// Open (and create if needed) a history directory keeping the last `keep` versions
+    pub fn open(dir: impl AsRef<Path>, keep: usize) -> Result<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        std::fs::create_dir_all(&dir)?;
+        Ok(Self { dir, keep: keep.max(1) })
+    }
+
+    /// Keep the last `keep` versions from the next save on
+    pub fn set_keep(&mut self, keep: usize) {
+        self.keep = keep.max(1);
+    }
+
+    fn path(&self, version: u64) -> PathBuf {
+        self.dir.join(format!("{}{:06}{}", FILE_PREFIX, version, FILE_SUFFIX))
+    }
+
+    fn version_numbers(&self) -> Result<Vec<u64>> {
+        let mut numbers = Vec::new();
+        for entry in std::fs::read_dir(&self.dir)? {
+            let name = entry?.file_name();
+            let number = name.to_str()
+                .and_then(|name| name.strip_prefix(FILE_PREFIX))
+                .and_then(|name| name.strip_suffix(FILE_SUFFIX))
+                .and_then(|number| number.parse::<u64>().ok());
+            numbers.extend(number);
+        }
+        numbers.sort_unstable();
+        Ok(numbers)
+    }
+
+    fn read(&self, version: u64) -> Result<StoredConfig> {
+        let path = self.path(version);
+        let json = std::fs::read_to_string(&path).map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => SniperError::NotFound(format!("No config version {}", version)).into(),
+            _ => anyhow!("Cannot read config version {}: {}", version, e),
+        })?;
+        serde_json::from_str(&json)
+            .map_err(|e| anyhow!("Corrupt config version {} in {}: {}", version, path.display(), e))
+    }
+
+    /// Saved versions, oldest first
+    pub fn versions(&self) -> Result<Vec<ConfigVersion>> {
+        self.version_numbers()?
+            .into_iter()
+            .map(|number| self.read(number).map(|stored| stored.version))
+            .collect()
+    }
+
+    /// Most recently saved version
+    pub fn latest(&self) -> Result<Option<(ConfigVersion, BotConfig)>> {
+        match self.version_numbers()?.last() {
+            Some(&number) => {
+                let stored = self.read(number)?;
+                Ok(Some((stored.version, stored.config)))
+            }
+            None => Ok(None),
+        }
+    }
+
+    pub fn load(&self, version: u64) -> Result<BotConfig> {
+        Ok(self.read(version)?.config)
+    }
+
+    /// Save a new version atomically and prune the oldest beyond the limit
+    ///
+    /// A config equal to the latest version is not saved again; that version
+    /// is returned instead.
+    pub fn save(&self, config: &BotConfig) -> Result<ConfigVersion> {
+        let numbers = self.version_numbers()?;
+        if let Some(latest) = numbers.last().and_then(|&last| self.read(last).ok()) {
+            if serde_json::to_value(&latest.config)? == serde_json::to_value(config)? {
+                return Ok(latest.version);
+            }
+        }
+        let version = ConfigVersion {
+            version: numbers.last().map_or(1, |last| last + 1),
+            saved_at: Utc::now(),
+        };
+        let stored = StoredConfig {
+            version: version.clone(),
+            config: config.clone(),
+        };
+
+        // Write then rename, so a crash never leaves a half-written version behind
+        let path = self.path(version.version);
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_string_pretty(&stored)?)?;
+        std::fs::rename(&tmp, &path)?;
+
+        let excess = (numbers.len() + 1).saturating_sub(self.keep);
+        for &old in numbers.iter().take(excess) {
+            if let Err(e) = std::fs::remove_file(self.path(old)) {
+                log::warn!("Failed to prune config version {}: {}", old, e);
+            }
+        }
+
+        Ok(version)
+    }
+
+    /// Settings that changed from one version to another
+    pub fn diff(&self, from: u64, to: u64) -> Result<Vec<ConfigChange>> {
+        let old = serde_json::to_value(self.load(from)?)?;
+        let new = serde_json::to_value(self.load(to)?)?;
+
+        let mut changes = Vec::new();
+        diff_values(String::new(), Some(&old), Some(&new), &mut changes);
+        Ok(changes)
+    }
+}
+
+/// Walk both values, recursing into objects; arrays and scalars are compared whole
+fn diff_values(path: String, old: Option<&Value>, new: Option<&Value>, changes: &mut Vec<ConfigChange>) {
+    match (old, new) {
+        (Some(Value::Object(old)), Some(Value::Object(new))) => {
+            let mut keys: Vec<&String> = old.keys().chain(new.keys()).collect();
+            keys.sort();
+            keys.dedup();
+
+            for key in keys {
+                let child = if path.is_empty() { key.clone() } else { format!("{}.{}", path, key) };
+                diff_values(child, old.get(key), new.get(key), changes);
+            }
+        }
+        (old, new) if old != new => changes.push(ConfigChange {
+            path,
+            old: old.cloned(),
+            new: new.cloned(),
+        }),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Fresh history directory per test
+    fn store(name: &str, keep: usize) -> ConfigStore {
+        let dir = std::env::temp_dir().join(format!("wolf63x-history-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        ConfigStore::open(dir, keep).unwrap()
+    }
+
+    fn config_with_slippage(slippage: f64) -> BotConfig {
+        let mut config = BotConfig::default();
+        config.trade_settings.default_slippage = slippage;
+        config
+    }
+
+    #[test]
+    fn diff_reports_changed_settings_by_path() {
+        let store = store("diff", 10);
+        let old = store.save(&config_with_slippage(1.0)).unwrap();
+        let mut config = config_with_slippage(2.0);
+        config.active_profile = Some("fresh".to_string());
+        let new = store.save(&config).unwrap();
+
+        let changes = store.diff(old.version, new.version).unwrap();
+        let paths: Vec<&str> = changes.iter().map(|change| change.path.as_str()).collect();
+        assert_eq!(paths, vec!["active_profile", "trade_settings.default_slippage"]);
+        assert_eq!(changes[1].old, Some(serde_json::json!(1.0)));
+        assert_eq!(changes[1].new, Some(serde_json::json!(2.0)));
+        assert!(store.diff(new.version, new.version).unwrap().is_empty());
+    }
+
+    #[test]
+    fn diff_values_reports_added_and_removed_keys() {
+        let old = serde_json::json!({ "a": 1, "b": { "c": 2 } });
+        let new = serde_json::json!({ "b": { "c": 2, "d": [3] } });
+        let mut changes = Vec::new();
+        diff_values(String::new(), Some(&old), Some(&new), &mut changes);
+
+        assert_eq!(changes.len(), 2);
+        assert_eq!((changes[0].path.as_str(), changes[0].new.is_none()), ("a", true));
+        assert_eq!((changes[1].path.as_str(), changes[1].old.is_none()), ("b.d", true));
+    }
+
+    #[test]
+    fn prunes_the_oldest_versions_beyond_the_limit() {
+        let store = store("prune", 3);
+        for slippage in 1..=5 {
+            store.save(&config_with_slippage(slippage as f64)).unwrap();
+        }
+
+        let versions: Vec<u64> = store.versions().unwrap().iter().map(|v| v.version).collect();
+        assert_eq!(versions, vec![3, 4, 5]);
+        assert_eq!(store.latest().unwrap().unwrap().1.trade_settings.default_slippage, 5.0);
+        assert!(store.load(1).is_err());
+    }
+
+    #[test]
+    fn lowering_the_limit_prunes_on_the_next_save() {
+        let mut store = store("keep", 5);
+        for slippage in 1..=4 {
+            store.save(&config_with_slippage(slippage as f64)).unwrap();
+        }
+        store.set_keep(2);
+        store.save(&config_with_slippage(9.0)).unwrap();
+
+        let versions: Vec<u64> = store.versions().unwrap().iter().map(|v| v.version).collect();
+        assert_eq!(versions, vec![4, 5]);
+    }
+
+    #[test]
+    fn saving_an_unchanged_config_keeps_the_latest_version() {
+        let store = store("unchanged", 10);
+        let first = store.save(&config_with_slippage(1.0)).unwrap();
+        let again = store.save(&config_with_slippage(1.0)).unwrap();
+
+        assert_eq!(again.version, first.version);
+        assert_eq!(store.versions().unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,543 @@
+use serde::{Serialize, Deserialize};
+use anyhow::{Result, anyhow};
+use borsh::BorshDeserialize;
+use chrono::{DateTime, Duration, Utc};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{account::Account, pubkey::Pubkey};
+use spl_token_2022::extension::{
+    BaseStateWithExtensions, StateWithExtensions,
+    permanent_delegate::PermanentDelegate,
+    transfer_fee::TransferFeeConfig,
+    transfer_hook::TransferHook,
+};
+use spl_token_2022::state::Mint;
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex, RwLock};
+use tokio::sync::Semaphore;
+use crate::bundles::{BundleDetector, BundleReport};
+use crate::honeypot::{HoneypotSimulator, RoundTrip};
+use crate::holders::{HolderAnalysis, HolderAnalyzer};
+use crate::pump_fun::{self, BondingCurveState};
+use crate::scanner::TokenOpportunity;
+
+/// Metaplex token metadata program
+const TOKEN_METADATA_PROGRAM_ID: &str = "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s";
+
+/// Default number of mints enriched at the same time
+const DEFAULT_MAX_CONCURRENT: usize = 4;
+
+/// Images larger than this are not downloaded for hashing
+const MAX_IMAGE_BYTES: u64 = 2 * 1024 * 1024;
+
+/// Metadata and image URIs are chosen by the token creator, so a request
+/// must not hold an enrichment slot for long
+const HTTP_CONNECT_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(3);
+const HTTP_REQUEST_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// Off-chain metadata resolved from the token's metadata URI
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TokenMetadata {
+    pub uri: String,
+    pub description: Option<String>,
+    pub image: Option<String>,
+    /// SHA-256 of the image bytes, shared by copycats that reupload the same art
+    #[serde(default)]
+    pub image_hash: Option<String>,
+    pub website: Option<String>,
+    pub twitter: Option<String>,
+    pub telegram: Option<String>,
+}
+
+/// One of the largest token accounts for a mint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenHolder {
+    pub address: String,
+    pub amount: u64,
+    pub percent: f64,
+}
+
+/// When each group of enriched fields was last fetched
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FieldFreshness {
+    pub mint: Option<DateTime<Utc>>,
+    pub metadata: Option<DateTime<Utc>>,
+    pub holders: Option<DateTime<Utc>>,
+    pub holder_analysis: Option<DateTime<Utc>>,
+    pub bundles: Option<DateTime<Utc>>,
+    pub bonding_curve: Option<DateTime<Utc>>,
+    pub honeypot: Option<DateTime<Utc>>,
+}
+
+/// How long each group of enriched fields stays valid in the cache
+#[derive(Debug, Clone)]
+pub struct EnrichmentTtl {
+    pub mint: Duration,
+    pub metadata: Duration,
+    pub holders: Duration,
+    pub bonding_curve: Duration,
+    pub honeypot: Duration,
+}
+
+impl Default for EnrichmentTtl {
+    fn default() -> Self {
+        Self {
+            mint: Duration::minutes(10),
+            metadata: Duration::hours(1),
+            holders: Duration::seconds(30),
+            bonding_curve: Duration::seconds(5),
+            honeypot: Duration::minutes(1),
+        }
+    }
+}
+
+/// Decoded SPL mint account
+#[derive(Debug, Clone, Default)]
+pub struct MintInfo {
+    pub supply: u64,
+    pub decimals: u8,
+    pub mint_authority: Option<String>,
+    pub freeze_authority: Option<String>,
+    pub token_2022: bool,
+    /// Fee of the newer epoch, which takes over once that epoch starts
+    pub transfer_fee_bps: u16,
+    /// Can raise the transfer fee at any time, up to the maximum
+    pub transfer_fee_authority: Option<String>,
+    pub permanent_delegate: Option<String>,
+    pub transfer_hook_program: Option<String>,
+}
+
+impl MintInfo {
+    /// Token-2022 extensions that let the issuer tax, seize or block transfers
+    pub fn risky_extensions(&self) -> Vec<String> {
+        let mut extensions = Vec::new();
+        if self.transfer_fee_bps > 0 || self.transfer_fee_authority.is_some() {
+            extensions.push("transfer_fee".to_string());
+        }
+        if self.permanent_delegate.is_some() {
+            extensions.push("permanent_delegate".to_string());
+        }
+        if self.transfer_hook_program.is_some() {
+            extensions.push("transfer_hook".to_string());
+        }
+        extensions
+    }
+
+    /// Transfer fee as a whole percentage, rounded up
+    pub fn transfer_fee_percent(&self) -> u8 {
+        (self.transfer_fee_bps as u32).div_ceil(100).min(100) as u8
+    }
+}
+
+/// Everything known about a mint beyond what the launch event carries
+#[derive(Debug, Clone, Default)]
+pub struct TokenEnrichment {
+    pub mint: Option<MintInfo>,
+    pub name: Option<String>,
+    pub symbol: Option<String>,
+    pub metadata: Option<TokenMetadata>,
+    pub top_holders: Vec<TokenHolder>,
+    pub holder_analysis: Option<HolderAnalysis>,
+    pub bundles: Option<BundleReport>,
+    pub bonding_curve: Option<BondingCurveState>,
+    pub round_trip: Option<RoundTrip>,
+    /// Why the last round trip simulation failed, `None` once one succeeds
+    pub round_trip_error: Option<String>,
+    pub freshness: FieldFreshness,
+}
+
+impl TokenEnrichment {
+    /// Copy the enriched fields onto an opportunity
+    pub fn apply_to(&self, token: &mut TokenOpportunity) {
+        if let Some(mint) = &self.mint {
+            token.supply = mint.supply;
+            token.decimals = mint.decimals;
+            token.mint_authority = mint.mint_authority.clone();
+            token.freeze_authority = mint.freeze_authority.clone();
+            token.token_2022 = mint.token_2022;
+            token.transfer_fee_bps = mint.transfer_fee_bps;
+            token.risky_extensions = mint.risky_extensions();
+            // A transfer fee is charged on both sides of a swap
+            token.buy_tax = mint.transfer_fee_percent();
+            token.sell_tax = mint.transfer_fee_percent();
+        }
+
+        if token.name.is_empty() {
+            if let Some(name) = &self.name {
+                token.name = name.clone();
+            }
+        }
+
+        if token.symbol.is_empty() {
+            if let Some(symbol) = &self.symbol {
+                token.symbol = symbol.clone();
+            }
+        }
+
+        if self.metadata.is_some() {
+            token.metadata = self.metadata.clone();
+        }
+
+        if self.freshness.holders.is_some() {
+            token.top_holders = self.top_holders.clone();
+        }
+
+        if let Some(analysis) = &self.holder_analysis {
+            token.top10_holder_percent = analysis.top10_percent;
+            token.creator_holding_percent = analysis.creator_percent;
+            token.same_funder_wallets = analysis.same_funder_wallets;
+        }
+
+        if let Some(bundles) = &self.bundles {
+            token.bundle_percent = bundles.bundle_percent;
+            token.bundled_wallets = bundles.bundled_wallets.len() as u32;
+        }
+
+        if let Some(curve) = &self.bonding_curve {
+            token.bonding_curve_progress = curve.progress();
+            token.bonding_curve_complete = curve.complete;
+            token.liquidity = curve.liquidity_sol();
+        }
+
+        if let Some(round_trip) = &self.round_trip {
+            token.honeypot = round_trip.sell_failed;
+            token.round_trip_loss_percent = round_trip.loss_percent;
+            // Beyond the curve's own fees and price impact, the loss is transfer fees or a hidden sell penalty
+            token.sell_tax = token.sell_tax.max(round_trip.excess_loss_percent().round() as u8);
+        }
+        token.round_trip_error = self.round_trip_error.clone();
+
+        token.freshness = self.freshness.clone();
+    }
+}
+
+/// Prefix of the Metaplex metadata account we care about
+#[derive(BorshDeserialize)]
+struct MetadataAccount {
+    _key: u8,
+    _update_authority: [u8; 32],
+    _mint: [u8; 32],
+    name: String,
+    symbol: String,
+    uri: String,
+}
+
+/// JSON document behind the metadata URI
+#[derive(Debug, Default, Deserialize)]
+struct UriMetadata {
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    image: Option<String>,
+    #[serde(default)]
+    website: Option<String>,
+    #[serde(default)]
+    twitter: Option<String>,
+    #[serde(default)]
+    telegram: Option<String>,
+}
+
+/// Fetches on-chain and off-chain data for new mints
+///
+/// Calls are bounded by a semaphore and each field group is cached per mint
+/// for its own TTL, so repeated enrichment only refetches what went stale.
+pub struct Enricher {
+    rpc: Arc<RpcClient>,
+    http: reqwest::Client,
+    holders: Arc<HolderAnalyzer>,
+    bundles: BundleDetector,
+    /// Set once a wallet is connected; shared so running scans pick it up
+    simulation_payer: RwLock<Option<Pubkey>>,
+    limiter: Semaphore,
+    ttl: EnrichmentTtl,
+    cache: Mutex<HashMap<String, TokenEnrichment>>,
+}
+
+impl Enricher {
+    pub fn new(rpc_url: String) -> Self {
+        Self::with_limits(rpc_url, DEFAULT_MAX_CONCURRENT, EnrichmentTtl::default())
+    }
+
+    pub fn with_limits(rpc_url: String, max_concurrent: usize, ttl: EnrichmentTtl) -> Self {
+        let rpc = Arc::new(crate::telemetry::rpc_client(rpc_url));
+        let holders = Arc::new(HolderAnalyzer::new(rpc.clone()));
+        Self {
+            bundles: BundleDetector::new(rpc.clone(), holders.clone()),
+            holders,
+            simulation_payer: RwLock::new(None),
+            rpc,
+            http: http_client(),
+            limiter: Semaphore::new(max_concurrent.max(1)),
+            ttl,
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Simulate round trips from `payer` to detect honeypots
+    ///
+    /// Without a payer the honeypot check is skipped and its fields stay unknown.
+    pub fn set_simulation_payer(&self, payer: Option<Pubkey>) {
+        *self.simulation_payer.write().unwrap() = payer;
+    }
+
+    pub fn simulation_payer(&self) -> Option<Pubkey> {
+        *self.simulation_payer.read().unwrap()
+    }
+
+    /// Enrich a mint, refetching only the field groups whose TTL expired
+    pub async fn enrich(&self, mint: &str, creator: Option<&str>) -> Result<TokenEnrichment> {
+        let _permit = self.limiter.acquire().await?;
+        let mint_key = Pubkey::from_str(mint)?;
+        let now = Utc::now();
+
+        let mut enrichment = self.cache.lock().unwrap().get(mint).cloned().unwrap_or_default();
+
+        let is_stale = |fetched: Option<DateTime<Utc>>, ttl: Duration| {
+            fetched.is_none_or(|at| now - at > ttl)
+        };
+        let mint_stale = is_stale(enrichment.freshness.mint, self.ttl.mint);
+        let metadata_stale = is_stale(enrichment.freshness.metadata, self.ttl.metadata);
+        let holders_stale = is_stale(enrichment.freshness.holders, self.ttl.holders);
+        let curve_stale = is_stale(enrichment.freshness.bonding_curve, self.ttl.bonding_curve);
+        let honeypot_stale = is_stale(enrichment.freshness.honeypot, self.ttl.honeypot);
+
+        // Batch every stale account into a single RPC round trip
+        let metadata_address = metadata_address(&mint_key)?;
+        let curve_address = pump_fun::bonding_curve_address(&mint_key)?;
+        let mut wanted = Vec::new();
+        if mint_stale {
+            wanted.push(mint_key);
+        }
+        if metadata_stale {
+            wanted.push(metadata_address);
+        }
+        if curve_stale {
+            wanted.push(curve_address);
+        }
+
+        let accounts: HashMap<Pubkey, Option<Account>> = if wanted.is_empty() {
+            HashMap::new()
+        } else {
+            let fetched = self.rpc.get_multiple_accounts(&wanted).await?;
+            wanted.into_iter().zip(fetched).collect()
+        };
+
+        if let Some(Some(account)) = accounts.get(&mint_key) {
+            match decode_mint(account) {
+                Ok(info) => {
+                    enrichment.mint = Some(info);
+                    enrichment.freshness.mint = Some(now);
+                }
+                Err(e) => log::debug!("Failed to decode mint {}: {}", mint, e),
+            }
+        }
+
+        if let Some(Some(account)) = accounts.get(&metadata_address) {
+            match self.fetch_metadata(&account.data).await {
+                Ok((name, symbol, metadata)) => {
+                    enrichment.name = Some(name);
+                    enrichment.symbol = Some(symbol);
+                    enrichment.metadata = Some(metadata);
+                    enrichment.freshness.metadata = Some(now);
+                }
+                Err(e) => log::debug!("Failed to resolve metadata for {}: {}", mint, e),
+            }
+        }
+
+        if let Some(Some(account)) = accounts.get(&curve_address) {
+            match BondingCurveState::decode(&account.data) {
+                Ok(curve) => {
+                    enrichment.bonding_curve = Some(curve);
+                    enrichment.freshness.bonding_curve = Some(now);
+                }
+                Err(e) => log::debug!("Failed to decode bonding curve for {}: {}", mint, e),
+            }
+        }
+
+        if holders_stale {
+            let supply = enrichment.mint.as_ref().map(|m| m.supply).unwrap_or(0);
+            match self.fetch_top_holders(&mint_key, supply).await {
+                Ok(holders) => {
+                    enrichment.top_holders = holders;
+                    enrichment.freshness.holders = Some(now);
+                }
+                Err(e) => log::debug!("Failed to fetch holders for {}: {}", mint, e),
+            }
+
+            // Only Pump.fun launches have a curve whose first trades can be inspected
+            if enrichment.bonding_curve.is_some() {
+                let supply = enrichment.mint.as_ref().map(|m| m.supply).unwrap_or(0);
+                match self.bundles.detect(mint, creator, supply).await {
+                    Ok(Some(report)) => {
+                        enrichment.bundles = Some(report);
+                        enrichment.freshness.bundles = Some(now);
+                    }
+                    Ok(None) => {}
+                    Err(e) => log::debug!("Failed to detect bundles for {}: {}", mint, e),
+                }
+            }
+
+            let launch_buyers = enrichment.bundles.as_ref().map(|report| report.launch_buyers.as_slice()).unwrap_or_default();
+            match self.holders.analyze(&enrichment.top_holders, creator, launch_buyers).await {
+                Ok(analysis) => {
+                    enrichment.holder_analysis = Some(analysis);
+                    enrichment.freshness.holder_analysis = Some(now);
+                }
+                Err(e) => log::debug!("Failed to analyze holders for {}: {}", mint, e),
+            }
+        }
+
+        // Once migrated the curve no longer trades, so there is nothing to simulate
+        if let (Some(payer), Some(curve)) = (self.simulation_payer(), &enrichment.bonding_curve) {
+            if honeypot_stale && !curve.complete {
+                match HoneypotSimulator::new(self.rpc.clone(), payer).simulate(mint, curve).await {
+                    Ok(round_trip) => {
+                        if round_trip.sell_failed {
+                            log::warn!("Honeypot detected for {}: sell simulation failed", mint);
+                        }
+                        enrichment.round_trip = Some(round_trip);
+                        enrichment.round_trip_error = None;
+                        enrichment.freshness.honeypot = Some(now);
+                    }
+                    Err(e) => {
+                        log::debug!("Failed to simulate round trip for {}: {}", mint, e);
+                        enrichment.round_trip_error = Some(e.to_string());
+                    }
+                }
+            }
+        }
+
+        self.cache.lock().unwrap().insert(mint.to_string(), enrichment.clone());
+
+        Ok(enrichment)
+    }
+
+    /// Drop cached data for mints that are no longer tracked
+    pub fn retain(&self, keep: impl Fn(&str) -> bool) {
+        self.cache.lock().unwrap().retain(|mint, _| keep(mint));
+    }
+
+    /// Decode the metadata account and resolve the JSON behind its URI
+    async fn fetch_metadata(&self, data: &[u8]) -> Result<(String, String, TokenMetadata)> {
+        let account = MetadataAccount::deserialize(&mut &data[..])?;
+        let name = account.name.trim_end_matches('\0').trim().to_string();
+        let symbol = account.symbol.trim_end_matches('\0').trim().to_string();
+        let uri = account.uri.trim_end_matches('\0').trim().to_string();
+
+        let mut metadata = TokenMetadata {
+            uri: uri.clone(),
+            ..TokenMetadata::default()
+        };
+
+        if !uri.is_empty() {
+            let json: UriMetadata = self.http.get(&uri).send().await?.json().await?;
+            metadata.description = json.description;
+            metadata.image = json.image;
+            metadata.website = json.website;
+            metadata.twitter = json.twitter;
+            metadata.telegram = json.telegram;
+        }
+
+        if let Some(image) = &metadata.image {
+            match self.hash_image(image).await {
+                Ok(hash) => metadata.image_hash = hash,
+                Err(e) => log::debug!("Failed to hash image {}: {}", image, e),
+            }
+        }
+
+        Ok((name, symbol, metadata))
+    }
+
+    /// Hash the image behind a metadata URL, skipping oversized files
+    async fn hash_image(&self, url: &str) -> Result<Option<String>> {
+        let mut response = self.http.get(url).send().await?.error_for_status()?;
+        if response.content_length().is_some_and(|len| len > MAX_IMAGE_BYTES) {
+            return Ok(None);
+        }
+
+        // Hosts can omit or understate the length, so stop reading once past the limit
+        let mut hasher = solana_sdk::hash::Hasher::default();
+        let mut read = 0;
+        while let Some(chunk) = response.chunk().await? {
+            read += chunk.len() as u64;
+            if read > MAX_IMAGE_BYTES {
+                return Ok(None);
+            }
+            hasher.hash(&chunk);
+        }
+
+        Ok(Some(hasher.result().to_string()))
+    }
+
+    /// Fetch the largest token accounts with their share of the supply
+    async fn fetch_top_holders(&self, mint: &Pubkey, supply: u64) -> Result<Vec<TokenHolder>> {
+        let accounts = self.rpc.get_token_largest_accounts(mint).await?;
+
+        Ok(accounts.into_iter().map(|account| {
+            let amount = account.amount.amount.parse::<u64>().unwrap_or(0);
+            let percent = if supply > 0 {
+                amount as f64 / supply as f64 * 100.0
+            } else {
+                0.0
+            };
+
+            TokenHolder {
+                address: account.address,
+                amount,
+                percent,
+            }
+        }).collect())
+    }
+}
+
+/// HTTP client that gives up on slow hosts
+///
+/// Fails only where `reqwest::Client::new` would, when TLS cannot be initialized.
+pub(crate) fn http_client() -> reqwest::Client {
+    reqwest::Client::builder()
+        .connect_timeout(HTTP_CONNECT_TIMEOUT)
+        .timeout(HTTP_REQUEST_TIMEOUT)
+        .build()
+        .expect("TLS backend cannot be initialized")
+}
+
+/// Derive the Metaplex metadata account of a mint
+fn metadata_address(mint: &Pubkey) -> Result<Pubkey> {
+    let program = Pubkey::from_str(TOKEN_METADATA_PROGRAM_ID)?;
+    let (address, _) = Pubkey::find_program_address(
+        &[b"metadata", program.as_ref(), mint.as_ref()],
+        &program,
+    );
+    Ok(address)
+}
+
+/// Decode a legacy SPL or Token-2022 mint, including the extensions we screen for
+fn decode_mint(account: &Account) -> Result<MintInfo> {
+    let state = StateWithExtensions::<Mint>::unpack(&account.data)
+        .map_err(|e| anyhow!("Invalid mint account: {}", e))?;
+    let mint = state.base;
+
+    let mut info = MintInfo {
+        supply: mint.supply,
+        decimals: mint.decimals,
+        mint_authority: Option::<Pubkey>::from(mint.mint_authority).map(|key| key.to_string()),
+        freeze_authority: Option::<Pubkey>::from(mint.freeze_authority).map(|key| key.to_string()),
+        token_2022: account.owner == spl_token_2022::id(),
+        ..MintInfo::default()
+    };
+
+    if let Ok(fee_config) = state.get_extension::<TransferFeeConfig>() {
+        info.transfer_fee_bps = u16::from(fee_config.newer_transfer_fee.transfer_fee_basis_points);
+        info.transfer_fee_authority = Option::<Pubkey>::from(fee_config.transfer_fee_config_authority).map(|key| key.to_string());
+    }
+
+    if let Ok(delegate) = state.get_extension::<PermanentDelegate>() {
+        info.permanent_delegate = Option::<Pubkey>::from(delegate.delegate).map(|key| key.to_string());
+    }
+
+    if let Ok(hook) = state.get_extension::<TransferHook>() {
+        info.transfer_hook_program = Option::<Pubkey>::from(hook.program_id).map(|key| key.to_string());
+    }
+
+    Ok(info)
+}
@@ -0,0 +1,118 @@
+use solana_client::client_error::{ClientError, ClientErrorKind};
+use solana_sdk::instruction::InstructionError;
+use solana_sdk::transaction::TransactionError;
+use thiserror::Error;
+use wasm_bindgen::JsValue;
+use crate::pump_fun::SLIPPAGE_ERROR_CODES;
+
+/// Errors a caller can act on, e.g. by topping up, retrying or changing the request
+#[derive(Debug, Error)]
+pub enum SniperError {
+    #[error("Insufficient balance: {required} SOL required, {available} SOL available")]
+    InsufficientBalance { required: f64, available: f64 },
+
+    #[error("Price moved beyond the slippage limit")]
+    SlippageExceeded,
+
+    #[error("Wallet is locked or not connected")]
+    WalletLocked,
+
+    /// The endpoint could not be reached or did not answer
+    #[error("RPC unavailable: {0}")]
+    RpcUnavailable(String),
+
+    /// The transaction was rejected in simulation or failed on chain
+    #[error("Transaction failed: {0}")]
+    TransactionFailed(String),
+
+    #[error("Blockhash expired before the transaction landed")]
+    BlockhashExpired,
+
+    #[error("Token rejected by filters: {}", .0.join(", "))]
+    FilterRejected(Vec<String>),
+
+    #[error("Limit reached: {0}")]
+    LimitReached(String),
+
+    #[error("Trading is halted by the kill switch")]
+    TradingHalted,
+
+    #[error("{0}")]
+    NotFound(String),
+
+    #[error("{0}")]
+    InvalidRequest(String),
+
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+impl SniperError {
+    /// Stable, machine-readable name of the error
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::InsufficientBalance { .. } => "insufficient_balance",
+            Self::SlippageExceeded => "slippage_exceeded",
+            Self::WalletLocked => "wallet_locked",
+            Self::RpcUnavailable(_) => "rpc_unavailable",
+            Self::TransactionFailed(_) => "transaction_failed",
+            Self::BlockhashExpired => "blockhash_expired",
+            Self::FilterRejected(_) => "filter_rejected",
+            Self::LimitReached(_) => "limit_reached",
+            Self::TradingHalted => "trading_halted",
+            Self::NotFound(_) => "not_found",
+            Self::InvalidRequest(_) => "invalid_request",
+            Self::Other(_) => "internal",
+        }
+    }
+}
+
+/// Transaction failures by what the program or runtime reported; transport failures as the RPC being unavailable
+impl From<ClientError> for SniperError {
+    fn from(error: ClientError) -> Self {
+        match error.get_transaction_error() {
+            // The client's message carries the simulation logs
+            Some(transaction_error) => match Self::from(transaction_error) {
+                Self::TransactionFailed(_) => Self::TransactionFailed(error.to_string()),
+                typed => typed,
+            },
+            None => match error.kind() {
+                ClientErrorKind::Io(_) | ClientErrorKind::Reqwest(_) => Self::RpcUnavailable(error.to_string()),
+                _ => Self::Other(error.into()),
+            },
+        }
+    }
+}
+
+/// A transaction that landed or was simulated and failed, by what the program or runtime reported
+impl From<TransactionError> for SniperError {
+    fn from(error: TransactionError) -> Self {
+        match error {
+            TransactionError::BlockhashNotFound => Self::BlockhashExpired,
+            TransactionError::InstructionError(_, InstructionError::Custom(code))
+                if SLIPPAGE_ERROR_CODES.contains(&code) => Self::SlippageExceeded,
+            _ => Self::TransactionFailed(error.to_string()),
+        }
+    }
+}
+
+impl From<SniperError> for JsValue {
+    fn from(error: SniperError) -> Self {
+        JsValue::from_str(&error.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn landed_transaction_errors_are_typed() {
+        let slippage = TransactionError::InstructionError(1, InstructionError::Custom(SLIPPAGE_ERROR_CODES[0]));
+        assert!(matches!(SniperError::from(slippage), SniperError::SlippageExceeded));
+        assert!(matches!(SniperError::from(TransactionError::BlockhashNotFound), SniperError::BlockhashExpired));
+
+        let failed = SniperError::from(TransactionError::InstructionError(1, InstructionError::Custom(6001)));
+        assert_eq!(failed.code(), "transaction_failed");
+    }
+}
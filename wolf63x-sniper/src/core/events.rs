@@ -0,0 +1,383 @@
+use serde::{Serialize, Deserialize};
+use anyhow::{Result, anyhow};
+use chrono::{DateTime, Utc};
+use std::collections::{HashSet, VecDeque};
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use tokio::sync::broadcast;
+use crate::logger::TradeType;
+use crate::rules::FilterVerdict;
+use crate::scanner::TokenOpportunity;
+
+/// Events kept for clients resuming from a sequence number
+pub const DEFAULT_EVENT_HISTORY: usize = 1000;
+
+/// Sequence numbers of a run start at its boot time shifted by this many bits,
+/// above every number an earlier run handed out while staying exact in JavaScript
+const EPOCH_SHIFT: u32 = 20;
+
+/// Group of events a client can subscribe to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Topic {
+    Opportunities,
+    Verdicts,
+    Orders,
+    Positions,
+    Wallet,
+}
+
+impl Topic {
+    pub const ALL: [Topic; 5] = [Topic::Opportunities, Topic::Verdicts, Topic::Orders, Topic::Positions, Topic::Wallet];
+}
+
+impl FromStr for Topic {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "opportunities" => Ok(Topic::Opportunities),
+            "verdicts" => Ok(Topic::Verdicts),
+            "orders" => Ok(Topic::Orders),
+            "positions" => Ok(Topic::Positions),
+            "wallet" => Ok(Topic::Wallet),
+            other => Err(anyhow!("Unknown event topic: {}", other)),
+        }
+    }
+}
+
+/// Exit rule that fired for a position
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExitTrigger {
+    TakeProfit,
+    StopLoss,
+    /// The price fell the trailing distance below its high since entry
+    TrailingStop,
+    /// The position was held for the plan's maximum time
+    MaxHold,
+}
+
+/// Something clients are pushed instead of polling for
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", content = "data", rename_all = "snake_case")]
+pub enum Event {
+    /// A token seen for the first time, already scored
+    NewOpportunity(Box<TokenOpportunity>),
+    /// A tracked token started or stopped passing the filters
+    FilterVerdict {
+        token_address: String,
+        verdict: FilterVerdict,
+    },
+    OrderSubmitted {
+        token_address: String,
+        side: TradeType,
+        amount: f64,
+    },
+    OrderConfirmed {
+        token_address: String,
+        side: TradeType,
+        signature: String,
+    },
+    OrderFailed {
+        token_address: String,
+        side: TradeType,
+        error: String,
+    },
+    PositionPrice {
+        token_address: String,
+        price: f64,
+        /// Change since entry
+        change_percent: f64,
+    },
+    ExitTriggered {
+        token_address: String,
+        trigger: ExitTrigger,
+        price: f64,
+        change_percent: f64,
+    },
+    WalletBalance {
+        address: String,
+        balance: f64,
+    },
+}
+
+impl Event {
+    pub fn topic(&self) -> Topic {
+        match self {
+            Event::NewOpportunity(_) => Topic::Opportunities,
+            Event::FilterVerdict { .. } => Topic::Verdicts,
+            Event::OrderSubmitted { .. } | Event::OrderConfirmed { .. } | Event::OrderFailed { .. } => Topic::Orders,
+            Event::PositionPrice { .. } | Event::ExitTriggered { .. } => Topic::Positions,
+            Event::WalletBalance { .. } => Topic::Wallet,
+        }
+    }
+
+    /// Name of the event type, as in the serialized `type` field
+    pub fn name(&self) -> &'static str {
+        match self {
+            Event::NewOpportunity(_) => "new_opportunity",
+            Event::FilterVerdict { .. } => "filter_verdict",
+            Event::OrderSubmitted { .. } => "order_submitted",
+            Event::OrderConfirmed { .. } => "order_confirmed",
+            Event::OrderFailed { .. } => "order_failed",
+            Event::PositionPrice { .. } => "position_price",
+            Event::ExitTriggered { .. } => "exit_triggered",
+            Event::WalletBalance { .. } => "wallet_balance",
+        }
+    }
+}
+
+/// An event numbered in publish order
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SequencedEvent {
+    pub seq: u64,
+    pub at: DateTime<Utc>,
+    #[serde(flatten)]
+    pub event: Event,
+}
+
+struct History {
+    /// First sequence number of this run
+    first_seq: u64,
+    next_seq: u64,
+    events: VecDeque<SequencedEvent>,
+}
+
+/// Fan-out of events to every subscriber, keeping recent history for replay
+pub struct EventBus {
+    history: Mutex<History>,
+    capacity: usize,
+    sender: broadcast::Sender<SequencedEvent>,
+}
+
+impl EventBus {
+    /// A bus remembering the last `capacity` events
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        let (sender, _) = broadcast::channel(capacity);
+        let first_seq = ((Utc::now().timestamp().max(0) as u64) << EPOCH_SHIFT) + 1;
+        Self {
+            history: Mutex::new(History {
+                first_seq,
+                next_seq: first_seq,
+                events: VecDeque::with_capacity(capacity),
+            }),
+            capacity,
+            sender,
+        }
+    }
+
+    /// Number and send an event, returning its sequence number
+    pub fn publish(&self, event: Event) -> u64 {
+        // Numbered and sent under the history lock, so subscribers see events in sequence order
+        let mut history = self.history.lock().unwrap();
+        let event = SequencedEvent {
+            seq: history.next_seq,
+            at: Utc::now(),
+            event,
+        };
+        history.next_seq += 1;
+
+        if history.events.len() == self.capacity {
+            history.events.pop_front();
+        }
+        history.events.push_back(event.clone());
+
+        // No receivers is not an error, nobody is listening yet
+        let _ = self.sender.send(event.clone());
+        event.seq
+    }
+
+    /// Events after `since` that are still in history, plus a receiver for everything newer
+    fn resume(&self, since: u64) -> (VecDeque<SequencedEvent>, broadcast::Receiver<SequencedEvent>) {
+        let history = self.history.lock().unwrap();
+        let missed = history.events.iter().filter(|e| e.seq > since).cloned().collect();
+        (missed, self.sender.subscribe())
+    }
+
+    /// Subscribe to some topics, or all when `topics` is empty
+    ///
+    /// With `since`, events after that sequence number are replayed first, as
+    /// far back as history goes; `Some(0)` replays all of it. A number this run
+    /// never handed out, e.g. one from before a restart, cannot be resumed from:
+    /// the subscription starts with new events and is marked stale.
+    pub fn subscribe(self: &Arc<Self>, topics: HashSet<Topic>, since: Option<u64>) -> Subscription {
+        let (first_seq, next_seq) = {
+            let history = self.history.lock().unwrap();
+            (history.first_seq, history.next_seq)
+        };
+        let stale = since.is_some_and(|since| since != 0 && !(first_seq - 1..next_seq).contains(&since));
+        let last_seq = match since {
+            Some(since) if !stale => since,
+            _ => next_seq - 1,
+        };
+        let (backlog, receiver) = self.resume(last_seq);
+        Subscription {
+            bus: self.clone(),
+            topics: if topics.is_empty() { Topic::ALL.into_iter().collect() } else { topics },
+            backlog,
+            receiver,
+            last_seq,
+            stale,
+        }
+    }
+}
+
+/// One client's view of the bus
+pub struct Subscription {
+    bus: Arc<EventBus>,
+    topics: HashSet<Topic>,
+    backlog: VecDeque<SequencedEvent>,
+    receiver: broadcast::Receiver<SequencedEvent>,
+    last_seq: u64,
+    stale: bool,
+}
+
+impl Subscription {
+    /// The client resumed from an event of another run, so whatever it built
+    /// from earlier events is out of date and should be fetched again
+    pub fn is_stale(&self) -> bool {
+        self.stale
+    }
+
+    pub fn subscribe(&mut self, topics: impl IntoIterator<Item = Topic>) {
+        self.topics.extend(topics);
+    }
+
+    pub fn unsubscribe(&mut self, topics: impl IntoIterator<Item = Topic>) {
+        for topic in topics {
+            self.topics.remove(&topic);
+        }
+    }
+
+    pub fn topics(&self) -> &HashSet<Topic> {
+        &self.topics
+    }
+
+    /// Next event on a subscribed topic, `None` once the bus is gone
+    ///
+    /// Cancel safe. A subscriber that falls behind catches up from history.
+    pub async fn next(&mut self) -> Option<SequencedEvent> {
+        loop {
+            let event = match self.backlog.pop_front() {
+                Some(event) => event,
+                None => match self.receiver.recv().await {
+                    Ok(event) => event,
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        log::debug!("Event subscriber lagged by {} events, replaying from history", skipped);
+                        (self.backlog, self.receiver) = self.bus.resume(self.last_seq);
+                        continue;
+                    }
+                    Err(broadcast::error::RecvError::Closed) => return None,
+                },
+            };
+
+            // Replayed history can overlap what the receiver already delivered
+            if event.seq <= self.last_seq {
+                continue;
+            }
+            self.last_seq = event.seq;
+
+            if self.topics.contains(&event.event.topic()) {
+                return Some(event);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn balance(balance: f64) -> Event {
+        Event::WalletBalance { address: "wallet".to_string(), balance }
+    }
+
+    fn order_failed() -> Event {
+        Event::OrderFailed { token_address: "mint".to_string(), side: TradeType::Buy, error: "failed".to_string() }
+    }
+
+    async fn next_seq(subscription: &mut Subscription) -> u64 {
+        subscription.next().await.unwrap().seq
+    }
+
+    #[tokio::test]
+    async fn resumes_after_a_sequence_number() {
+        let bus = Arc::new(EventBus::new(10));
+        let first = bus.publish(balance(0.0));
+        for i in 1..3 {
+            bus.publish(balance(i as f64));
+        }
+
+        let mut subscription = bus.subscribe(HashSet::new(), Some(first));
+        assert!(!subscription.is_stale());
+        assert_eq!(next_seq(&mut subscription).await, first + 1);
+        assert_eq!(next_seq(&mut subscription).await, first + 2);
+
+        bus.publish(balance(3.0));
+        assert_eq!(next_seq(&mut subscription).await, first + 3);
+    }
+
+    #[tokio::test]
+    async fn new_subscribers_start_after_history() {
+        let bus = Arc::new(EventBus::new(10));
+        let first = bus.publish(balance(0.0));
+
+        let mut subscription = bus.subscribe(HashSet::new(), None);
+        bus.publish(balance(1.0));
+        assert_eq!(next_seq(&mut subscription).await, first + 1);
+    }
+
+    #[tokio::test]
+    async fn sequence_numbers_of_an_earlier_run_are_not_resumed_from() {
+        let bus = Arc::new(EventBus::new(10));
+        let first = bus.publish(balance(0.0));
+        bus.publish(balance(1.0));
+
+        // A client that saw a few events before the server restarted
+        let mut subscription = bus.subscribe(HashSet::new(), Some(5));
+        assert!(subscription.is_stale());
+        let next = bus.publish(balance(2.0));
+        assert_eq!(next_seq(&mut subscription).await, next);
+
+        // Nor one this run has not handed out yet
+        assert!(bus.subscribe(HashSet::new(), Some(next + 1)).is_stale());
+
+        let mut everything = bus.subscribe(HashSet::new(), Some(0));
+        assert!(!everything.is_stale());
+        assert_eq!(next_seq(&mut everything).await, first);
+    }
+
+    #[tokio::test]
+    async fn lagging_subscriber_replays_from_history() {
+        let bus = Arc::new(EventBus::new(4));
+        let mut subscription = bus.subscribe(HashSet::new(), None);
+        let first = bus.publish(balance(0.0));
+        for i in 1..10 {
+            bus.publish(balance(i as f64));
+        }
+
+        // The receiver lagged; what history still holds comes back in order, once
+        for seq in first + 6..=first + 9 {
+            assert_eq!(next_seq(&mut subscription).await, seq);
+        }
+        bus.publish(balance(10.0));
+        assert_eq!(next_seq(&mut subscription).await, first + 10);
+    }
+
+    #[tokio::test]
+    async fn only_subscribed_topics_are_delivered() {
+        let bus = Arc::new(EventBus::new(10));
+        let mut subscription = bus.subscribe(HashSet::from([Topic::Wallet]), None);
+        bus.publish(order_failed());
+        let wallet = bus.publish(balance(1.0));
+        assert_eq!(next_seq(&mut subscription).await, wallet);
+
+        subscription.subscribe([Topic::Orders]);
+        subscription.unsubscribe([Topic::Wallet]);
+        bus.publish(balance(2.0));
+        let order = bus.publish(order_failed());
+        assert_eq!(next_seq(&mut subscription).await, order);
+    }
+}
@@ -1,7 +1,16 @@
 use wasm_bindgen::prelude::*;
 use serde::{Serialize, Deserialize};
+use regex::{Regex, RegexBuilder};
+use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
 use crate::console_log;
+use crate::profiles::{ProfileSet, SharedProfiles};
+use crate::rules::{self, CustomRule, FilterVerdict, RuleExpr};
 use crate::scanner::TokenOpportunity;
+use crate::scoring::{ScoreBreakdown, ScoringModel};
+use crate::similarity::SimilarityIndex;
+
+/// Filter settings shared by the config manager, the filter and the scanner
+pub type SharedFilterSettings = Arc<RwLock<FilterSettings>>;
 
 /// Filter settings for token validation
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -14,8 +23,159 @@ pub struct FilterSettings {
     pub min_lp_lock_days: u32,
     pub min_token_age_minutes: u32,
     pub max_similar_tokens: u8,
+    #[serde(default = "default_similarity_window_minutes")]
+    pub similarity_window_minutes: u32,
     pub blacklisted_creators: Vec<String>,
+    /// Mints that are never accepted
+    #[serde(default)]
+    pub excluded_tokens: Vec<String>,
+    /// Total supply bounds in whole tokens
+    #[serde(default)]
+    pub min_total_supply: Option<f64>,
+    #[serde(default)]
+    pub max_total_supply: Option<f64>,
     pub min_score: u8,
+    #[serde(default = "default_true")]
+    pub reject_mint_authority: bool,
+    #[serde(default = "default_true")]
+    pub reject_freeze_authority: bool,
+    #[serde(default = "default_true")]
+    pub reject_risky_extensions: bool,
+    #[serde(default = "default_max_top10_holder_percent")]
+    pub max_top10_holder_percent: f64,
+    #[serde(default = "default_max_creator_holding_percent")]
+    pub max_creator_holding_percent: f64,
+    #[serde(default = "default_max_same_funder_wallets")]
+    pub max_same_funder_wallets: u32,
+    #[serde(default = "default_max_bundle_percent")]
+    pub max_bundle_percent: f64,
+    #[serde(default = "default_true")]
+    pub exclude_honeypots: bool,
+    #[serde(default = "default_max_round_trip_loss_percent")]
+    pub max_round_trip_loss_percent: f64,
+    /// Rule expression to apply; all rules must pass when unset
+    #[serde(default)]
+    pub rules: Option<RuleExpr>,
+    #[serde(default)]
+    pub custom_rules: Vec<CustomRule>,
+    #[serde(default)]
+    pub scoring: ScoringModel,
+    /// Names must match one of these patterns when any are set
+    #[serde(default)]
+    pub name_allow: PatternList,
+    #[serde(default)]
+    pub name_deny: PatternList,
+    #[serde(default)]
+    pub symbol_allow: PatternList,
+    #[serde(default)]
+    pub symbol_deny: PatternList,
+    /// The metadata description must mention one of these when any are set
+    #[serde(default)]
+    pub description_allow_keywords: Vec<String>,
+    #[serde(default)]
+    pub description_deny_keywords: Vec<String>,
+    #[serde(default)]
+    pub require_website: bool,
+    #[serde(default)]
+    pub require_twitter: bool,
+    #[serde(default)]
+    pub require_telegram: bool,
+    /// Bonding curve completion range (%) worth sniping
+    #[serde(default)]
+    pub min_curve_progress: f64,
+    #[serde(default = "default_max_curve_progress")]
+    pub max_curve_progress: f64,
+    #[serde(default)]
+    pub min_sol_inflow_per_minute: f64,
+    #[serde(default)]
+    pub min_buy_sell_ratio: f64,
+    #[serde(default)]
+    pub min_unique_buyers: u32,
+    #[serde(default = "default_momentum_window_minutes")]
+    pub momentum_window_minutes: u32,
+    /// Period after creation in which unique buyers are counted
+    #[serde(default = "default_early_buyer_minutes")]
+    pub early_buyer_minutes: u32,
+}
+
+/// Case-insensitive regex list, (de)serialized as plain pattern strings
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(try_from = "Vec<String>", into = "Vec<String>")]
+pub struct PatternList {
+    patterns: Vec<String>,
+    regexes: Vec<Regex>,
+}
+
+impl PatternList {
+    pub fn is_empty(&self) -> bool {
+        self.regexes.is_empty()
+    }
+
+    /// First pattern matching `text`, if any
+    pub fn find_match(&self, text: &str) -> Option<&str> {
+        self.regexes.iter()
+            .zip(&self.patterns)
+            .find(|(regex, _)| regex.is_match(text))
+            .map(|(_, pattern)| pattern.as_str())
+    }
+}
+
+impl TryFrom<Vec<String>> for PatternList {
+    type Error = regex::Error;
+
+    fn try_from(patterns: Vec<String>) -> Result<Self, Self::Error> {
+        let regexes = patterns.iter()
+            .map(|pattern| RegexBuilder::new(pattern).case_insensitive(true).build())
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self { patterns, regexes })
+    }
+}
+
+impl From<PatternList> for Vec<String> {
+    fn from(list: PatternList) -> Self {
+        list.patterns
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_similarity_window_minutes() -> u32 {
+    60
+}
+
+fn default_max_curve_progress() -> f64 {
+    100.0
+}
+
+fn default_momentum_window_minutes() -> u32 {
+    5
+}
+
+fn default_early_buyer_minutes() -> u32 {
+    5
+}
+
+fn default_max_top10_holder_percent() -> f64 {
+    30.0
+}
+
+fn default_max_creator_holding_percent() -> f64 {
+    10.0
+}
+
+fn default_max_same_funder_wallets() -> u32 {
+    3
+}
+
+fn default_max_bundle_percent() -> f64 {
+    20.0
+}
+
+fn default_max_round_trip_loss_percent() -> f64 {
+    10.0
 }
 
 impl Default for FilterSettings {
@@ -29,8 +189,40 @@ impl Default for FilterSettings {
             min_lp_lock_days: 30,
             min_token_age_minutes: 3,
             max_similar_tokens: 3,
+            similarity_window_minutes: default_similarity_window_minutes(),
             blacklisted_creators: vec![],
+            excluded_tokens: vec![],
+            min_total_supply: None,
+            max_total_supply: None,
             min_score: 75,
+            reject_mint_authority: true,
+            reject_freeze_authority: true,
+            reject_risky_extensions: true,
+            max_top10_holder_percent: default_max_top10_holder_percent(),
+            max_creator_holding_percent: default_max_creator_holding_percent(),
+            max_same_funder_wallets: default_max_same_funder_wallets(),
+            max_bundle_percent: default_max_bundle_percent(),
+            exclude_honeypots: true,
+            max_round_trip_loss_percent: default_max_round_trip_loss_percent(),
+            rules: None,
+            custom_rules: vec![],
+            scoring: ScoringModel::default(),
+            name_allow: PatternList::default(),
+            name_deny: PatternList::default(),
+            symbol_allow: PatternList::default(),
+            symbol_deny: PatternList::default(),
+            description_allow_keywords: vec![],
+            description_deny_keywords: vec![],
+            require_website: false,
+            require_twitter: false,
+            require_telegram: false,
+            min_curve_progress: 0.0,
+            max_curve_progress: default_max_curve_progress(),
+            min_sol_inflow_per_minute: 0.0,
+            min_buy_sell_ratio: 0.0,
+            min_unique_buyers: 0,
+            momentum_window_minutes: default_momentum_window_minutes(),
+            early_buyer_minutes: default_early_buyer_minutes(),
         }
     }
 }
@@ -38,144 +230,113 @@ impl Default for FilterSettings {
 /// Token filter for validating opportunities
 #[wasm_bindgen]
 pub struct TokenFilter {
-    settings: FilterSettings,
+    settings: SharedFilterSettings,
+    profiles: SharedProfiles,
+    similarity: SimilarityIndex,
 }
 
 #[wasm_bindgen]
 impl TokenFilter {
     #[wasm_bindgen(constructor)]
     pub fn new() -> Self {
-        Self {
-            settings: FilterSettings::default(),
-        }
+        Self::with_settings(Arc::new(RwLock::new(FilterSettings::default())))
     }
 
     /// Update filter settings
     pub fn update_settings(&mut self, settings: JsValue) -> Result<(), JsValue> {
         let new_settings: FilterSettings = serde_wasm_bindgen::from_value(settings)?;
-        self.settings = new_settings;
+        rules::check_rules(&new_settings).map_err(|e| JsValue::from_str(&e.to_string()))?;
+        *self.settings_mut() = new_settings;
         console_log!("Filter settings updated");
         Ok(())
     }
     
     /// Get current filter settings
     pub fn get_settings(&self) -> Result<JsValue, JsValue> {
-        serde_wasm_bindgen::to_value(&self.settings).map_err(|e| JsValue::from_str(&e.to_string()))
+        serde_wasm_bindgen::to_value(&*self.settings()).map_err(|e| JsValue::from_str(&e.to_string()))
     }
     
-    /// Validate a token opportunity
-    pub fn validate(&self, opportunity: JsValue) -> Result<bool, JsValue> {
+    /// Validate a token opportunity, returning the result of every rule
+    pub fn validate(&self, opportunity: JsValue) -> Result<JsValue, JsValue> {
         let token: TokenOpportunity = serde_wasm_bindgen::from_value(opportunity)?;
-        
-        // Apply all filters
-        let valid = self.check_liquidity(&token) &&
-                   self.check_holders(&token) &&
-                   self.check_taxes(&token) &&
-                   self.check_lp_lock(&token) &&
-                   self.check_token_age(&token) &&
-                   self.check_blacklist(&token) &&
-                   self.check_score(&token);
-        
-        Ok(valid)
+        let verdict = self.evaluate(&token);
+        serde_wasm_bindgen::to_value(&verdict).map_err(|e| JsValue::from_str(&e.to_string()))
     }
     
     /// Calculate a safety score for a token (0-100)
     pub fn calculate_score(&self, opportunity: JsValue) -> Result<u8, JsValue> {
         let token: TokenOpportunity = serde_wasm_bindgen::from_value(opportunity)?;
-        
-        // Start with a perfect score and deduct points for risk factors
-        let mut score = 100;
-        
-        // Liquidity check (0-20 points)
-        if token.liquidity < self.settings.min_liquidity {
-            score -= 20;
-        } else if token.liquidity < self.settings.min_liquidity * 2.0 {
-            score -= 10;
-        }
-        
-        // Holders check (0-15 points)
-        if token.holders < self.settings.min_holders {
-            score -= 15;
-        } else if token.holders < self.settings.min_holders * 2 {
-            score -= 7;
-        }
-        
-        // Tax check (0-25 points)
-        let tax_deduction = ((token.buy_tax + token.sell_tax) as f64 / 2.0) * 2.5;
-        score -= tax_deduction.min(25.0) as u8;
-        
-        // LP lock check (0-30 points)
-        if !token.lp_locked {
-            score -= 30;
-        } else if let Some(lock_end) = token.lp_lock_end {
-            let now = chrono::Utc::now();
-            let days_locked = (lock_end - now).num_days();
-            
-            if days_locked < 30 {
-                score -= 25;
-            } else if days_locked < 90 {
-                score -= 15;
-            } else if days_locked < 180 {
-                score -= 5;
-            }
-        }
-        
-        // Token age check (0-10 points)
-        let token_age_minutes = (chrono::Utc::now() - token.created_at).num_minutes();
-        if token_age_minutes < self.settings.min_token_age_minutes as i64 {
-            score -= 10;
-        }
-        
-        // Ensure score is between 0 and 100
-        score = score.max(0).min(100);
-        
-        Ok(score)
+        Ok(self.score(&token))
     }
 }
 
-// Private methods
+// Native Rust implementation (not exposed to WASM)
 impl TokenFilter {
-    fn check_liquidity(&self, token: &TokenOpportunity) -> bool {
-        token.liquidity >= self.settings.min_liquidity
+    /// Filter reading settings that are shared with other components
+    pub fn with_settings(settings: SharedFilterSettings) -> Self {
+        Self {
+            settings,
+            profiles: Arc::new(RwLock::new(ProfileSet::default())),
+            similarity: SimilarityIndex::new(),
+        }
     }
     
-    fn check_holders(&self, token: &TokenOpportunity) -> bool {
-        token.holders >= self.settings.min_holders
+    /// Handle to the profiles tokens are routed to
+    pub fn shared_profiles(&self) -> SharedProfiles {
+        self.profiles.clone()
     }
     
-    fn check_taxes(&self, token: &TokenOpportunity) -> bool {
-        token.buy_tax <= self.settings.max_buy_tax && 
-        token.sell_tax <= self.settings.max_sell_tax
+    /// Assign a token to the profile its first matching route points at
+    pub fn route(&self, token: &mut TokenOpportunity) {
+        token.profile = self.profiles.read().unwrap().route(token).map(|profile| profile.name.clone());
     }
     
-    fn check_lp_lock(&self, token: &TokenOpportunity) -> bool {
-        if !self.settings.require_lp_lock {
-            return true;
-        }
-        
-        if !token.lp_locked {
-            return false;
+    /// Run a token through the configured rules, or those of its profile
+    pub fn evaluate(&self, token: &TokenOpportunity) -> FilterVerdict {
+        let profiles = self.profiles.read().unwrap();
+        match token.profile.as_deref().and_then(|name| profiles.get(name)) {
+            Some(profile) => rules::evaluate(&profile.filter_settings, token),
+            None => rules::evaluate(&self.settings(), token),
         }
-        
-        if let Some(lock_end) = token.lp_lock_end {
-            let now = chrono::Utc::now();
-            let days_locked = (lock_end - now).num_days();
-            return days_locked >= self.settings.min_lp_lock_days as i64;
-        }
-        
-        false
     }
     
-    fn check_token_age(&self, token: &TokenOpportunity) -> bool {
-        let token_age_minutes = (chrono::Utc::now() - token.created_at).num_minutes();
-        token_age_minutes >= self.settings.min_token_age_minutes as i64
+    /// Record a launch in the similarity index and update its near-duplicate count
+    pub fn track_similarity(&mut self, token: &mut TokenOpportunity) {
+        let window = chrono::Duration::minutes(self.settings().similarity_window_minutes as i64);
+        token.similar_tokens = self.similarity.observe(token, window);
     }
     
-    fn check_blacklist(&self, token: &TokenOpportunity) -> bool {
-        !self.settings.blacklisted_creators.contains(&token.address)
+    pub fn settings(&self) -> RwLockReadGuard<'_, FilterSettings> {
+        self.settings.read().unwrap()
     }
     
-    fn check_score(&self, token: &TokenOpportunity) -> bool {
-        token.score >= self.settings.min_score
+    pub fn settings_mut(&self) -> RwLockWriteGuard<'_, FilterSettings> {
+        self.settings.write().unwrap()
+    }
+    
+    /// Handle to the live settings, for components that must stay in sync with this filter
+    pub fn shared_settings(&self) -> SharedFilterSettings {
+        self.settings.clone()
+    }
+    
+    /// Score a token opportunity (0-100)
+    pub fn score(&self, token: &TokenOpportunity) -> u8 {
+        self.score_breakdown(token).score
+    }
+    
+    /// Score a token with the contribution of every scoring feature
+    pub fn score_breakdown(&self, token: &TokenOpportunity) -> ScoreBreakdown {
+        let profiles = self.profiles.read().unwrap();
+        let mut breakdown = match token.profile.as_deref().and_then(|name| profiles.get(name)) {
+            Some(profile) => profile.filter_settings.scoring.score(token),
+            None => self.settings().scoring.score(token),
+        };
+        
+        // A token that cannot be sold is worthless whatever else it has going for it
+        if token.honeypot {
+            breakdown.score = 0;
+        }
+        
+        breakdown
     }
 }
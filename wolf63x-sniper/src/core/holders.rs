@@ -0,0 +1,263 @@
+use serde::{Serialize, Deserialize};
+use anyhow::Result;
+use futures::stream::{self, StreamExt};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_client::GetConfirmedSignaturesForAddress2Config;
+use solana_client::rpc_config::RpcTransactionConfig;
+use solana_client::rpc_response::RpcConfirmedTransactionStatusWithSignature;
+use solana_sdk::{pubkey::Pubkey, signature::Signature};
+use solana_transaction_status::UiTransactionEncoding;
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use crate::enrichment::TokenHolder;
+
+/// Well-known program-controlled wallets that hold supply on behalf of a pool
+const KNOWN_PROGRAM_ACCOUNTS: &[&str] = &[
+    // Raydium AMM v4 authority
+    "5Q544fKrFoe6tsEbD7S8EmxGTJYAKtTVhAW5Q5pge4j1",
+    // Pump.fun migration account
+    "39azUYFWPz3VHgKCf3VChUwbpURdCHRxjWVowf5jUJjg",
+];
+
+/// Number of holders counted towards the concentration figure
+const TOP_HOLDER_COUNT: usize = 10;
+
+/// Signature pages walked back when looking for a wallet's first transaction
+const MAX_SIGNATURE_PAGES: usize = 3;
+
+/// Signatures fetched per page
+const SIGNATURE_PAGE_SIZE: usize = 1000;
+
+/// Wallets whose funding is traced in parallel
+const TRACE_CONCURRENCY: usize = 4;
+
+/// Distribution of a token's supply across its largest holders
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HolderAnalysis {
+    /// Share of supply held by the top 10 wallets, excluding the curve and pools
+    pub top10_percent: f64,
+    /// Share of supply currently held by the creator
+    pub creator_percent: f64,
+    /// Size of the largest group of launch-slot buyers and top holders funded by the same wallet
+    pub same_funder_wallets: u32,
+    /// Wallet that funded that group, if any
+    pub common_funder: Option<String>,
+}
+
+/// Resolves token account owners and traces where holder wallets got their SOL
+pub struct HolderAnalyzer {
+    rpc: Arc<RpcClient>,
+    funding_cache: Mutex<HashMap<String, Option<String>>>,
+}
+
+impl HolderAnalyzer {
+    pub fn new(rpc: Arc<RpcClient>) -> Self {
+        Self {
+            rpc,
+            funding_cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Analyze the largest token accounts of a mint
+    ///
+    /// Funding is traced for the wallets that bought in the launch slot, where
+    /// insiders buy, as well as the top holders.
+    pub async fn analyze(&self, holders: &[TokenHolder], creator: Option<&str>, launch_buyers: &[String]) -> Result<HolderAnalysis> {
+        let owners = self.resolve_owners(holders).await?;
+
+        // Pair each holder with its owning wallet, dropping curve and pool accounts
+        let wallets: Vec<(&TokenHolder, String)> = holders.iter()
+            .zip(owners)
+            .filter_map(|(holder, owner)| owner.map(|owner| (holder, owner)))
+            .filter(|(_, owner)| is_wallet(owner))
+            .collect();
+
+        let top10_percent = wallets.iter()
+            .take(TOP_HOLDER_COUNT)
+            .map(|(holder, _)| holder.percent)
+            .sum::<f64>();
+
+        let creator_percent = match creator {
+            Some(creator) => wallets.iter()
+                .filter(|(_, owner)| owner == creator)
+                .map(|(holder, _)| holder.percent)
+                .sum::<f64>(),
+            None => 0.0,
+        };
+
+        let mut traced = launch_buyers.to_vec();
+        for (_, owner) in &wallets {
+            if !traced.contains(owner) {
+                traced.push(owner.clone());
+            }
+        }
+
+        let mut by_funder: HashMap<String, u32> = HashMap::new();
+        for (_, funder) in self.funding_sources(traced).await {
+            if let Some(funder) = funder {
+                *by_funder.entry(funder).or_insert(0) += 1;
+            }
+        }
+
+        let (common_funder, same_funder_wallets) = by_funder.into_iter()
+            .filter(|(_, count)| *count > 1)
+            .max_by_key(|(_, count)| *count)
+            .map_or((None, 0), |(funder, count)| (Some(funder), count));
+
+        Ok(HolderAnalysis {
+            top10_percent,
+            creator_percent,
+            same_funder_wallets,
+            common_funder,
+        })
+    }
+
+    /// Find the wallet that sent the first SOL into `wallet`
+    ///
+    /// Results are cached since a wallet's funding source never changes.
+    pub async fn funding_source(&self, wallet: &str) -> Result<Option<String>> {
+        if let Some(cached) = self.funding_cache.lock().unwrap().get(wallet) {
+            return Ok(cached.clone());
+        }
+
+        let address = Pubkey::from_str(wallet)?;
+        let funder = match self.oldest_signature(&address).await? {
+            Some(signature) => self.first_transfer_source(&signature, wallet).await?,
+            None => None,
+        };
+
+        self.funding_cache.lock().unwrap().insert(wallet.to_string(), funder.clone());
+
+        Ok(funder)
+    }
+
+    /// Trace the funding of several wallets in parallel; failed traces count as unknown
+    pub async fn funding_sources(&self, wallets: Vec<String>) -> Vec<(String, Option<String>)> {
+        stream::iter(wallets)
+            .map(|wallet| async move {
+                let funder = match self.funding_source(&wallet).await {
+                    Ok(funder) => funder,
+                    Err(e) => {
+                        log::debug!("Failed to trace funding of {}: {}", wallet, e);
+                        None
+                    }
+                };
+                (wallet, funder)
+            })
+            .buffer_unordered(TRACE_CONCURRENCY)
+            .collect()
+            .await
+    }
+
+    /// Owners of the given token accounts, `None` where the account is gone
+    async fn resolve_owners(&self, holders: &[TokenHolder]) -> Result<Vec<Option<String>>> {
+        let addresses = holders.iter()
+            .map(|holder| Pubkey::from_str(&holder.address))
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        let accounts = self.rpc.get_multiple_accounts(&addresses).await?;
+
+        // Token account layout: mint (32 bytes) followed by owner (32 bytes)
+        Ok(accounts.into_iter().map(|account| {
+            account.filter(|a| a.data.len() >= 64).map(|a| {
+                let mut owner = [0u8; 32];
+                owner.copy_from_slice(&a.data[32..64]);
+                Pubkey::new_from_array(owner).to_string()
+            })
+        }).collect())
+    }
+
+    /// Walk back through an address's history to its first signature
+    async fn oldest_signature(&self, address: &Pubkey) -> Result<Option<Signature>> {
+        match oldest_signatures(&self.rpc, address, 1, MAX_SIGNATURE_PAGES).await? {
+            Some(history) => match history.first() {
+                Some(first) => Ok(Some(Signature::from_str(&first.signature)?)),
+                None => Ok(None),
+            },
+            None => Ok(None),
+        }
+    }
+
+    /// Source of the system transfer into `wallet`, or the fee payer as a fallback
+    async fn first_transfer_source(&self, signature: &Signature, wallet: &str) -> Result<Option<String>> {
+        let transaction = self.rpc.get_transaction_with_config(
+            signature,
+            RpcTransactionConfig {
+                encoding: Some(UiTransactionEncoding::JsonParsed),
+                max_supported_transaction_version: Some(0),
+                ..RpcTransactionConfig::default()
+            },
+        ).await?;
+
+        let json = serde_json::to_value(&transaction.transaction.transaction)?;
+        let message = &json["message"];
+
+        let transfer_source = message["instructions"].as_array()
+            .into_iter()
+            .flatten()
+            .filter(|ix| ix["program"] == "system" && ix["parsed"]["type"] == "transfer")
+            .find(|ix| ix["parsed"]["info"]["destination"] == wallet)
+            .and_then(|ix| ix["parsed"]["info"]["source"].as_str())
+            .map(str::to_string);
+
+        let fee_payer = message["accountKeys"][0]["pubkey"].as_str()
+            .filter(|payer| *payer != wallet)
+            .map(str::to_string);
+
+        Ok(transfer_source.or(fee_payer))
+    }
+}
+
+/// The `count` oldest signatures of an address, oldest first
+///
+/// Returns `None` when the history is longer than `max_pages` pages, since the
+/// start of it cannot be reached without walking the whole thing.
+pub async fn oldest_signatures(
+    rpc: &RpcClient,
+    address: &Pubkey,
+    count: usize,
+    max_pages: usize,
+) -> Result<Option<Vec<RpcConfirmedTransactionStatusWithSignature>>> {
+    let mut before = None;
+    let mut window: Vec<RpcConfirmedTransactionStatusWithSignature> = Vec::new();
+
+    for _ in 0..max_pages {
+        let page = rpc.get_signatures_for_address_with_config(
+            address,
+            GetConfirmedSignaturesForAddress2Config {
+                before,
+                limit: Some(SIGNATURE_PAGE_SIZE),
+                ..GetConfirmedSignaturesForAddress2Config::default()
+            },
+        ).await?;
+
+        let reached_start = page.len() < SIGNATURE_PAGE_SIZE;
+        if let Some(last) = page.last() {
+            before = Some(Signature::from_str(&last.signature)?);
+        }
+
+        // Pages come newest first; keep only the tail we might need
+        window.extend(page);
+        if window.len() > count {
+            window.drain(..window.len() - count);
+        }
+
+        if reached_start {
+            window.reverse();
+            return Ok(Some(window));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Whether an owner is a user wallet rather than a curve, pool or other program account
+fn is_wallet(owner: &str) -> bool {
+    if KNOWN_PROGRAM_ACCOUNTS.contains(&owner) {
+        return false;
+    }
+
+    // Program-derived addresses are off the ed25519 curve
+    Pubkey::from_str(owner).is_ok_and(|key| key.is_on_curve())
+}
@@ -0,0 +1,172 @@
+use serde::{Serialize, Deserialize};
+use anyhow::{Result, anyhow};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_config::{RpcSimulateTransactionAccountsConfig, RpcSimulateTransactionConfig};
+use solana_client::rpc_response::RpcSimulateTransactionResult;
+use solana_account_decoder::UiAccountEncoding;
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    pubkey::Pubkey,
+    transaction::{Transaction, TransactionError},
+};
+use std::str::FromStr;
+use std::sync::Arc;
+use crate::pump_fun::{self, BondingCurveState};
+
+/// SOL spent on the simulated buy (0.01 SOL)
+const DEFAULT_TEST_LAMPORTS: u64 = 10_000_000;
+
+/// Base fee of a single-signature transaction
+const SIGNATURE_FEE_LAMPORTS: u64 = 5_000;
+
+/// Position of the sell and close instructions in the simulated transaction
+const SELL_INSTRUCTION_INDEX: u8 = 2;
+const CLOSE_INSTRUCTION_INDEX: u8 = 3;
+
+/// Tries at simulating against the slot the payer balance was read at
+const SIMULATION_ATTEMPTS: usize = 3;
+
+/// Outcome of a simulated buy followed by a sell of everything received
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RoundTrip {
+    pub lamports_in: u64,
+    pub tokens: u64,
+    /// Share of the buy amount lost over the round trip, curve fees included
+    pub loss_percent: f64,
+    /// Share the curve's fees and price impact alone account for
+    #[serde(default)]
+    pub expected_loss_percent: f64,
+    /// The buy went through but the sell could not
+    pub sell_failed: bool,
+    pub error: Option<String>,
+}
+
+impl RoundTrip {
+    /// Loss beyond what the curve explains, e.g. transfer fees or a hidden sell penalty
+    pub fn excess_loss_percent(&self) -> f64 {
+        (self.loss_percent - self.expected_loss_percent).max(0.0)
+    }
+}
+
+/// Simulates small round trips against current chain state to catch honeypots
+pub struct HoneypotSimulator {
+    rpc: Arc<RpcClient>,
+    payer: Pubkey,
+    test_lamports: u64,
+}
+
+impl HoneypotSimulator {
+    /// `payer` must hold enough SOL for the test buy; nothing is ever signed or sent
+    pub fn new(rpc: Arc<RpcClient>, payer: Pubkey) -> Self {
+        Self {
+            rpc,
+            payer,
+            test_lamports: DEFAULT_TEST_LAMPORTS,
+        }
+    }
+
+    /// Simulate buy → sell → close in one transaction against the Pump.fun curve
+    ///
+    /// The payer balance the loss is measured from is read at the slot the
+    /// simulation runs against, so trades landing in between do not skew it.
+    pub async fn simulate(&self, mint: &str, curve: &BondingCurveState) -> Result<RoundTrip> {
+        let mint = Pubkey::from_str(mint)?;
+        let tokens = curve.buy_quote(self.test_lamports);
+        if tokens == 0 {
+            return Err(anyhow!("Curve has no tokens left to buy"));
+        }
+        let user_ata = pump_fun::associated_token_address(&self.payer, &mint)?;
+
+        for _ in 0..SIMULATION_ATTEMPTS {
+            let before = self.rpc.get_multiple_accounts_with_commitment(
+                &[self.payer, user_ata],
+                CommitmentConfig::processed(),
+            ).await?;
+            let balance_before = before.value[0].as_ref().map_or(0, |account| account.lamports);
+            // Tokens already held keep an existing account open, so only a fresh one is closed
+            let close = before.value[1].is_none();
+
+            let transaction = self.round_trip_transaction(&mint, &user_ata, tokens, close)?;
+            let simulated = self.rpc.simulate_transaction_with_config(
+                &transaction,
+                RpcSimulateTransactionConfig {
+                    sig_verify: false,
+                    replace_recent_blockhash: true,
+                    commitment: Some(CommitmentConfig::processed()),
+                    accounts: Some(RpcSimulateTransactionAccountsConfig {
+                        encoding: Some(UiAccountEncoding::Base64),
+                        addresses: vec![self.payer.to_string()],
+                    }),
+                    min_context_slot: Some(before.context.slot),
+                    ..RpcSimulateTransactionConfig::default()
+                },
+            ).await?;
+
+            if simulated.context.slot == before.context.slot {
+                return self.round_trip(curve, tokens, balance_before, simulated.value);
+            }
+        }
+        Err(anyhow!("Chain moved on between reading the payer balance and simulating"))
+    }
+
+    fn round_trip_transaction(&self, mint: &Pubkey, user_ata: &Pubkey, tokens: u64, close: bool) -> Result<Transaction> {
+        let mut instructions = vec![
+            pump_fun::create_ata_idempotent_instruction(&self.payer, mint)?,
+            pump_fun::buy_instruction(mint, &self.payer, tokens, self.test_lamports * 110 / 100)?,
+            pump_fun::sell_instruction(mint, &self.payer, tokens, 0)?,
+        ];
+        if close {
+            let token_program = Pubkey::from_str(pump_fun::TOKEN_PROGRAM_ID)?;
+            instructions.push(spl_token_2022::instruction::close_account(&token_program, user_ata, &self.payer, &self.payer, &[])?);
+        }
+        Ok(Transaction::new_with_payer(&instructions, Some(&self.payer)))
+    }
+
+    fn round_trip(
+        &self,
+        curve: &BondingCurveState,
+        tokens: u64,
+        balance_before: u64,
+        result: RpcSimulateTransactionResult,
+    ) -> Result<RoundTrip> {
+        let expected_back = curve.after_buy(self.test_lamports).sell_quote(tokens);
+        let expected_loss_percent = self.test_lamports.saturating_sub(expected_back) as f64 / self.test_lamports as f64 * 100.0;
+
+        if let Some(err) = result.err {
+            return match err {
+                // A fresh token account left holding tokens means the sell kept some back
+                TransactionError::InstructionError(index, ref reason)
+                    if index == SELL_INSTRUCTION_INDEX || index == CLOSE_INSTRUCTION_INDEX =>
+                {
+                    Ok(RoundTrip {
+                        lamports_in: self.test_lamports,
+                        tokens,
+                        loss_percent: 100.0,
+                        expected_loss_percent,
+                        sell_failed: true,
+                        error: Some(format!("{:?}", reason)),
+                    })
+                }
+                // A failing buy says nothing about the token, e.g. the payer is empty
+                _ => Err(anyhow!("Buy simulation failed: {}", err)),
+            };
+        }
+
+        let balance_after = result.accounts
+            .and_then(|accounts| accounts.into_iter().next().flatten())
+            .map(|account| account.lamports)
+            .ok_or_else(|| anyhow!("Simulation did not return the payer account"))?;
+
+        let spent = balance_before.saturating_sub(balance_after).saturating_sub(SIGNATURE_FEE_LAMPORTS);
+        let loss_percent = (spent as f64 / self.test_lamports as f64 * 100.0).clamp(0.0, 100.0);
+
+        Ok(RoundTrip {
+            lamports_in: self.test_lamports,
+            tokens,
+            loss_percent,
+            expected_loss_percent,
+            sell_failed: false,
+            error: None,
+        })
+    }
+}
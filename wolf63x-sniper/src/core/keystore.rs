@@ -0,0 +1,169 @@
+use serde::{Serialize, Deserialize};
+use anyhow::{Result, anyhow};
+use aes_gcm::{aead::{Aead, KeyInit}, Aes256Gcm, Nonce};
+use argon2::Argon2;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use rand::RngCore;
+use solana_sdk::signature::Keypair;
+use solana_sdk::signer::Signer;
+use std::path::Path;
+
+/// Keystore used when `--keystore` is not given
+pub const DEFAULT_KEYSTORE_PATH: &str = "wallet.keystore.json";
+
+/// Secret holding the keystore password, from the environment or the secrets file
+pub const KEYSTORE_PASSWORD_SECRET: &str = "KEYSTORE_PASSWORD";
+
+const KEYSTORE_VERSION: u32 = 1;
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+
+/// A keypair encrypted with a password-derived key (Argon2id, AES-256-GCM)
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Keystore {
+    pub version: u32,
+    /// Public address, readable without the password
+    pub address: String,
+    salt: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+impl Keystore {
+    pub fn encrypt(keypair: &Keypair, password: &str) -> Result<Self> {
+        let mut salt = [0u8; SALT_LEN];
+        let mut nonce = [0u8; NONCE_LEN];
+        rand::thread_rng().fill_bytes(&mut salt);
+        rand::thread_rng().fill_bytes(&mut nonce);
+
+        let cipher = cipher(password, &salt)?;
+        let ciphertext = cipher.encrypt(&Nonce::from(nonce), keypair.to_bytes().as_ref())
+            .map_err(|_| anyhow!("Failed to encrypt keypair"))?;
+
+        Ok(Self {
+            version: KEYSTORE_VERSION,
+            address: keypair.pubkey().to_string(),
+            salt: STANDARD.encode(salt),
+            nonce: STANDARD.encode(nonce),
+            ciphertext: STANDARD.encode(ciphertext),
+        })
+    }
+
+    pub fn decrypt(&self, password: &str) -> Result<Keypair> {
+        if self.version != KEYSTORE_VERSION {
+            return Err(anyhow!("Unsupported keystore version {}", self.version));
+        }
+
+        let salt = STANDARD.decode(&self.salt)?;
+        let nonce: [u8; NONCE_LEN] = STANDARD.decode(&self.nonce)?
+            .try_into()
+            .map_err(|_| anyhow!("Corrupt keystore nonce"))?;
+        let ciphertext = STANDARD.decode(&self.ciphertext)?;
+
+        let bytes = cipher(password, &salt)?
+            .decrypt(&Nonce::from(nonce), ciphertext.as_ref())
+            .map_err(|_| anyhow!("Wrong keystore password or corrupt keystore"))?;
+        Keypair::from_bytes(&bytes).map_err(|e| anyhow!("Corrupt keypair in keystore: {}", e))
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let json = std::fs::read_to_string(path)
+            .map_err(|e| anyhow!("Cannot read keystore {}: {}", path.display(), e))?;
+        serde_json::from_str(&json).map_err(|e| anyhow!("Invalid keystore {}: {}", path.display(), e))
+    }
+
+    /// Write the keystore readable by the owner only
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let tmp = path.with_extension("json.tmp");
+        write_private(&tmp, serde_json::to_string_pretty(self)?.as_bytes())?;
+        std::fs::rename(&tmp, path)?;
+        Ok(())
+    }
+}
+
+fn cipher(password: &str, salt: &[u8]) -> Result<Aes256Gcm> {
+    if password.is_empty() {
+        return Err(anyhow!("Keystore password is empty"));
+    }
+
+    let mut key = [0u8; 32];
+    Argon2::default()
+        .hash_password_into(password.as_bytes(), salt, &mut key)
+        .map_err(|e| anyhow!("Failed to derive keystore key: {}", e))?;
+    Ok(Aes256Gcm::new_from_slice(&key)?)
+}
+
+/// Parse a keypair as written by `solana-keygen` (a JSON byte array) or as a base58 secret key
+pub fn parse_keypair(text: &str) -> Result<Keypair> {
+    let text = text.trim();
+    let bytes = if text.starts_with('[') {
+        serde_json::from_str::<Vec<u8>>(text).map_err(|e| anyhow!("Invalid keypair JSON: {}", e))?
+    } else {
+        bs58::decode(text).into_vec().map_err(|e| anyhow!("Invalid base58 keypair: {}", e))?
+    };
+    Keypair::from_bytes(&bytes).map_err(|e| anyhow!("Invalid keypair: {}", e))
+}
+
+#[cfg(unix)]
+fn write_private(path: &Path, contents: &[u8]) -> Result<()> {
+    use std::io::Write;
+    use std::os::unix::fs::OpenOptionsExt;
+
+    let mut file = std::fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(0o600)
+        .open(path)?;
+    file.write_all(contents)?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn write_private(path: &Path, contents: &[u8]) -> Result<()> {
+    Ok(std::fs::write(path, contents)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decrypts_what_it_encrypted() {
+        let keypair = Keypair::new();
+        let keystore = Keystore::encrypt(&keypair, "correct horse").unwrap();
+
+        assert_eq!(keystore.address, keypair.pubkey().to_string());
+        assert_eq!(keystore.decrypt("correct horse").unwrap().to_bytes(), keypair.to_bytes());
+        assert!(keystore.decrypt("wrong horse").is_err());
+        assert!(Keystore::encrypt(&keypair, "").is_err());
+    }
+
+    #[test]
+    fn saved_keystore_loads_and_is_private() {
+        let keypair = Keypair::new();
+        let path = std::env::temp_dir().join(format!("wolf63x-keystore-{}.json", std::process::id()));
+        Keystore::encrypt(&keypair, "password").unwrap().save(&path).unwrap();
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            assert_eq!(std::fs::metadata(&path).unwrap().permissions().mode() & 0o777, 0o600);
+        }
+        let loaded = Keystore::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(loaded.decrypt("password").unwrap().pubkey(), keypair.pubkey());
+    }
+
+    #[test]
+    fn parses_keygen_and_base58_keypairs() {
+        let keypair = Keypair::new();
+        let json = serde_json::to_string(&keypair.to_bytes().to_vec()).unwrap();
+
+        assert_eq!(parse_keypair(&json).unwrap().pubkey(), keypair.pubkey());
+        assert_eq!(parse_keypair(&format!(" {}\n", keypair.to_base58_string())).unwrap().pubkey(), keypair.pubkey());
+        assert!(parse_keypair("[1, 2, 3]").is_err());
+    }
+}
@@ -20,9 +20,12 @@ pub struct TradeLog {
     pub profit_loss: Option<f64>,
     pub profit_loss_percent: Option<f64>,
     pub time_held: Option<String>,
+    /// Strategy profile that opened the position
+    #[serde(default)]
+    pub profile: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum TradeType {
     Buy,
     Sell,
@@ -59,42 +62,7 @@ impl Logger {
     /// Log a new trade
     pub fn log_trade(&mut self, trade: JsValue) -> Result<(), JsValue> {
         let trade_log: TradeLog = serde_wasm_bindgen::from_value(trade)?;
-        
-        // Update daily stats
-        let date = trade_log.timestamp.format("%Y-%m-%d").to_string();
-        let mut stats = self.daily_stats.entry(date.clone()).or_insert_with(|| DailyStats {
-            date: date.clone(),
-            total_spent: 0.0,
-            total_earned: 0.0,
-            profit_loss: 0.0,
-            trade_count: 0,
-            win_count: 0,
-            loss_count: 0,
-        });
-        
-        stats.trade_count += 1;
-        
-        match trade_log.trade_type {
-            TradeType::Buy => {
-                stats.total_spent += trade_log.amount_in;
-            },
-            TradeType::Sell => {
-                stats.total_earned += trade_log.amount_out;
-                
-                if let Some(profit) = trade_log.profit_loss {
-                    stats.profit_loss += profit;
-                    
-                    if profit > 0.0 {
-                        stats.win_count += 1;
-                    } else {
-                        stats.loss_count += 1;
-                    }
-                }
-            },
-        }
-        
-        // Add to logs
-        self.trade_logs.push(trade_log);
+        self.record(trade_log);
         
         console_log!("Trade logged successfully");
         
@@ -218,3 +186,55 @@ struct PerformanceMetrics {
     best_trade: f64,
     worst_trade: f64,
 }
+
+// Native Rust implementation (not exposed to WASM)
+impl Logger {
+    /// All trade logs, oldest first
+    pub fn trade_logs(&self) -> &[TradeLog] {
+        &self.trade_logs
+    }
+    
+    /// Profit and loss of every sell, in SOL
+    pub fn realized_pnl(&self) -> f64 {
+        self.trade_logs.iter().filter_map(|trade| trade.profit_loss).sum()
+    }
+    
+    /// Record a trade and update the daily stats
+    pub fn record(&mut self, trade_log: TradeLog) {
+        // Update daily stats
+        let date = trade_log.timestamp.format("%Y-%m-%d").to_string();
+        let stats = self.daily_stats.entry(date.clone()).or_insert_with(|| DailyStats {
+            date: date.clone(),
+            total_spent: 0.0,
+            total_earned: 0.0,
+            profit_loss: 0.0,
+            trade_count: 0,
+            win_count: 0,
+            loss_count: 0,
+        });
+        
+        stats.trade_count += 1;
+        
+        match trade_log.trade_type {
+            TradeType::Buy => {
+                stats.total_spent += trade_log.amount_in;
+            },
+            TradeType::Sell => {
+                stats.total_earned += trade_log.amount_out;
+                
+                if let Some(profit) = trade_log.profit_loss {
+                    stats.profit_loss += profit;
+                    
+                    if profit > 0.0 {
+                        stats.win_count += 1;
+                    } else {
+                        stats.loss_count += 1;
+                    }
+                }
+            },
+        }
+        
+        // Add to logs
+        self.trade_logs.push(trade_log);
+    }
+}
@@ -5,6 +5,26 @@ pub mod scanner;
 pub mod filters;
 pub mod logger;
 pub mod config;
+pub mod config_file;
+pub mod config_store;
+pub mod profiles;
+pub mod secrets;
+pub mod error;
+pub mod events;
+pub mod telemetry;
+pub mod pump_fun;
+pub mod pump_ws;
+pub mod enrichment;
+pub mod holders;
+pub mod bundles;
+pub mod reputation;
+pub mod honeypot;
+pub mod rules;
+pub mod scoring;
+pub mod similarity;
+pub mod momentum;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod keystore;
 
 // Re-export key components
 pub use wallet::*;
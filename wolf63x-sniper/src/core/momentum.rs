@@ -0,0 +1,111 @@
+use serde::{Serialize, Deserialize};
+use chrono::{DateTime, Duration, Utc};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// Trades kept per mint; older ones fall outside any useful window anyway
+const MAX_TRADES_PER_MINT: usize = 2_000;
+
+/// Side of a curve trade
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TradeSide {
+    Buy,
+    Sell,
+}
+
+/// A single curve trade as seen on the trade stream
+#[derive(Debug, Clone)]
+pub struct Trade {
+    pub mint: String,
+    pub trader: String,
+    pub side: TradeSide,
+    pub sol_amount: f64,
+    pub at: DateTime<Utc>,
+}
+
+/// Trading activity of a mint derived from its recent trades
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Momentum {
+    /// Net SOL flowing into the curve per minute over the window
+    pub sol_inflow_per_minute: f64,
+    /// Buys per sell over the window
+    pub buy_sell_ratio: f64,
+    /// Distinct wallets buying within the early-buyer period after creation
+    pub unique_buyers: u32,
+    pub buys: u32,
+    pub sells: u32,
+    pub last_trade: Option<DateTime<Utc>>,
+}
+
+/// Rolling per-mint trade history fed by the PumpPortal trade stream
+#[derive(Debug, Default)]
+pub struct MomentumTracker {
+    trades: HashMap<String, VecDeque<Trade>>,
+}
+
+impl MomentumTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&mut self, trade: Trade) {
+        let trades = self.trades.entry(trade.mint.clone()).or_default();
+        trades.push_back(trade);
+        if trades.len() > MAX_TRADES_PER_MINT {
+            trades.pop_front();
+        }
+    }
+
+    /// Momentum of a mint, `None` until at least one trade has been seen
+    ///
+    /// Inflow and buy/sell ratio cover the last `window`; unique buyers are
+    /// counted over the first `early_period` after `created_at`.
+    pub fn momentum(
+        &self,
+        mint: &str,
+        created_at: DateTime<Utc>,
+        window: Duration,
+        early_period: Duration,
+    ) -> Option<Momentum> {
+        let trades = self.trades.get(mint).filter(|trades| !trades.is_empty())?;
+        let now = Utc::now();
+
+        // A young token has not been trading for the whole window yet
+        let start = (now - window).max(created_at);
+        let minutes = ((now - start).num_seconds() as f64 / 60.0).max(1.0);
+
+        let mut momentum = Momentum {
+            last_trade: trades.back().map(|trade| trade.at),
+            ..Momentum::default()
+        };
+
+        let mut inflow = 0.0;
+        for trade in trades.iter().filter(|trade| trade.at >= start) {
+            match trade.side {
+                TradeSide::Buy => {
+                    momentum.buys += 1;
+                    inflow += trade.sol_amount;
+                }
+                TradeSide::Sell => {
+                    momentum.sells += 1;
+                    inflow -= trade.sol_amount;
+                }
+            }
+        }
+
+        momentum.sol_inflow_per_minute = inflow / minutes;
+        momentum.buy_sell_ratio = momentum.buys as f64 / momentum.sells.max(1) as f64;
+        momentum.unique_buyers = trades.iter()
+            .filter(|trade| trade.side == TradeSide::Buy && trade.at <= created_at + early_period)
+            .map(|trade| trade.trader.as_str())
+            .collect::<HashSet<_>>()
+            .len() as u32;
+
+        Some(momentum)
+    }
+
+    /// Drop trade history for mints that are no longer tracked
+    pub fn retain(&mut self, keep: impl Fn(&str) -> bool) {
+        self.trades.retain(|mint, _| keep(mint));
+    }
+}
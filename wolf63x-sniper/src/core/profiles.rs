@@ -0,0 +1,88 @@
+use serde::{Serialize, Deserialize};
+use std::sync::{Arc, RwLock};
+use crate::config::TradeSettings;
+use crate::filters::FilterSettings;
+use crate::rules::{self, RuleExpr};
+use crate::scanner::TokenOpportunity;
+
+/// Profiles and routes shared between the config manager and the token filter
+pub type SharedProfiles = Arc<RwLock<ProfileSet>>;
+
+/// How positions opened by a profile are closed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExitPlan {
+    pub take_profit_percent: f64,
+    pub stop_loss_percent: f64,
+    /// Sell once the price falls this far below its high since entry
+    #[serde(default)]
+    pub trailing_stop_percent: Option<f64>,
+    /// Sell whatever the price once a position is this old
+    #[serde(default)]
+    pub max_hold_minutes: Option<u32>,
+}
+
+impl Default for ExitPlan {
+    fn default() -> Self {
+        let trade = TradeSettings::default();
+        Self {
+            take_profit_percent: trade.default_take_profit,
+            stop_loss_percent: trade.default_stop_loss,
+            trailing_stop_percent: None,
+            max_hold_minutes: None,
+        }
+    }
+}
+
+/// A named strategy, e.g. fresh launches, migrations or copy trades
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StrategyProfile {
+    pub name: String,
+    #[serde(default)]
+    pub trade_settings: TradeSettings,
+    #[serde(default)]
+    pub filter_settings: FilterSettings,
+    #[serde(default)]
+    pub exit_plan: ExitPlan,
+}
+
+/// Sends tokens from a scanner source and/or matching a rule to a profile
+///
+/// Routes are tried in order and the first match wins; a route without a
+/// source or rule matches everything.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfileRoute {
+    pub profile: String,
+    /// Scanner source, e.g. `pump.fun`
+    #[serde(default)]
+    pub source: Option<String>,
+    /// Rule expression, evaluated with the profile's filter settings
+    #[serde(default)]
+    pub rule: Option<RuleExpr>,
+}
+
+/// Profiles that run side by side and the routes that feed them
+#[derive(Debug, Clone, Default)]
+pub struct ProfileSet {
+    pub profiles: Vec<StrategyProfile>,
+    pub routes: Vec<ProfileRoute>,
+}
+
+impl ProfileSet {
+    pub fn get(&self, name: &str) -> Option<&StrategyProfile> {
+        self.profiles.iter().find(|profile| profile.name == name)
+    }
+
+    /// Profile the first matching route sends a token to
+    pub fn route(&self, token: &TokenOpportunity) -> Option<&StrategyProfile> {
+        self.routes.iter().find_map(|route| {
+            let profile = self.get(&route.profile)?;
+
+            let source_matches = route.source.as_ref().is_none_or(|source| source == &token.source);
+            let rule_matches = || route.rule.as_ref().is_none_or(|rule| {
+                rules::evaluate_expression(&profile.filter_settings, rule, token).passed
+            });
+
+            (source_matches && rule_matches()).then_some(profile)
+        })
+    }
+}
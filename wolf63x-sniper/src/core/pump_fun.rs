@@ -0,0 +1,423 @@
+use serde::{Serialize, Deserialize};
+use anyhow::{Result, anyhow};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    system_program,
+    sysvar,
+};
+use std::str::FromStr;
+
+/// Base URL of the Pump.fun frontend API
+pub const PUMP_FUN_API: &str = "https://frontend-api.pump.fun";
+
+/// Pump.fun bonding curve program
+pub const PUMP_FUN_PROGRAM_ID: &str = "6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P";
+
+/// Pump.fun global state account
+pub const GLOBAL_ACCOUNT: &str = "4wTV1YmiEkRvAtNtsSGPtUrqRYQMe5SKy2uB4Jjaxnjf";
+
+/// Account receiving the Pump.fun trading fee
+pub const FEE_RECIPIENT: &str = "CebN5WGQ4jvEPvsVU4EoHEpgzq1VV7AbicfhtW4xC9iM";
+
+/// Pump.fun event authority
+pub const EVENT_AUTHORITY: &str = "Ce6TQqeHC9p8KetsN6JsjHK7UTZk7nasjjnr7XxXp9F1";
+
+/// SPL Token program used by Pump.fun mints
+pub const TOKEN_PROGRAM_ID: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+
+/// Associated token account program
+pub const ASSOCIATED_TOKEN_PROGRAM_ID: &str = "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL";
+
+/// Custom program errors for a buy costing or a sell returning more than its slippage allows
+pub const SLIPPAGE_ERROR_CODES: [u32; 2] = [6002, 6003];
+
+/// Trading fee charged by the curve on both buys and sells, in basis points
+pub const TRADE_FEE_BPS: u64 = 100;
+
+/// Anchor discriminators of the curve instructions
+const BUY_DISCRIMINATOR: [u8; 8] = [102, 6, 61, 18, 1, 218, 235, 234];
+const SELL_DISCRIMINATOR: [u8; 8] = [51, 230, 133, 164, 1, 127, 131, 173];
+
+/// Pump.fun tokens are minted with 6 decimals
+pub const TOKEN_DECIMALS: u8 = 6;
+
+/// Real token reserves a fresh bonding curve starts with (raw units)
+pub const INITIAL_REAL_TOKEN_RESERVES: u64 = 793_100_000_000_000;
+
+/// Difference between virtual and real token reserves on every curve (raw units)
+pub const VIRTUAL_TOKEN_OFFSET: u64 = 279_900_000_000_000;
+
+const LAMPORTS_PER_SOL: f64 = 1_000_000_000.0;
+
+/// Accounts `getMultipleAccounts` returns per call
+const MAX_ACCOUNTS_PER_REQUEST: usize = 100;
+
+/// Coin snapshot as returned by the Pump.fun frontend API
+#[derive(Debug, Clone, Deserialize)]
+pub struct PumpCoin {
+    pub mint: String,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub symbol: String,
+    #[serde(default)]
+    pub creator: String,
+    #[serde(default)]
+    pub created_timestamp: i64,
+    #[serde(default)]
+    pub virtual_sol_reserves: u64,
+    #[serde(default)]
+    pub virtual_token_reserves: u64,
+    #[serde(default)]
+    pub total_supply: u64,
+    #[serde(default)]
+    pub usd_market_cap: f64,
+    #[serde(default)]
+    pub complete: bool,
+}
+
+impl PumpCoin {
+    /// Token price in USD derived from the reported market cap
+    pub fn price_usd(&self) -> f64 {
+        let supply = self.total_supply as f64 / 10f64.powi(TOKEN_DECIMALS as i32);
+        if supply <= 0.0 {
+            return 0.0;
+        }
+        self.usd_market_cap / supply
+    }
+
+    /// SOL sitting in the bonding curve, used as the token's liquidity
+    pub fn liquidity_sol(&self) -> f64 {
+        self.virtual_sol_reserves as f64 / LAMPORTS_PER_SOL
+    }
+
+    /// Bonding curve completion (0-100)
+    pub fn curve_progress(&self) -> f64 {
+        if self.complete {
+            return 100.0;
+        }
+        curve_progress(self.virtual_token_reserves)
+    }
+}
+
+/// Curve completion percentage computed from the virtual token reserves
+pub fn curve_progress(virtual_token_reserves: u64) -> f64 {
+    let real = virtual_token_reserves.saturating_sub(VIRTUAL_TOKEN_OFFSET);
+    let sold = INITIAL_REAL_TOKEN_RESERVES.saturating_sub(real);
+    (sold as f64 / INITIAL_REAL_TOKEN_RESERVES as f64 * 100.0).clamp(0.0, 100.0)
+}
+
+/// On-chain state of a Pump.fun bonding curve account
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BondingCurveState {
+    pub virtual_token_reserves: u64,
+    pub virtual_sol_reserves: u64,
+    pub real_token_reserves: u64,
+    pub real_sol_reserves: u64,
+    pub token_total_supply: u64,
+    pub complete: bool,
+}
+
+impl BondingCurveState {
+    /// Decode a bonding curve account (8-byte discriminator followed by the reserves)
+    pub fn decode(data: &[u8]) -> Result<Self> {
+        if data.len() < 49 {
+            return Err(anyhow!("Bonding curve account too short: {} bytes", data.len()));
+        }
+
+        let read_u64 = |offset: usize| {
+            let mut bytes = [0u8; 8];
+            bytes.copy_from_slice(&data[offset..offset + 8]);
+            u64::from_le_bytes(bytes)
+        };
+
+        Ok(Self {
+            virtual_token_reserves: read_u64(8),
+            virtual_sol_reserves: read_u64(16),
+            real_token_reserves: read_u64(24),
+            real_sol_reserves: read_u64(32),
+            token_total_supply: read_u64(40),
+            complete: data[48] != 0,
+        })
+    }
+
+    /// Bonding curve completion (0-100)
+    pub fn progress(&self) -> f64 {
+        if self.complete {
+            return 100.0;
+        }
+        let sold = INITIAL_REAL_TOKEN_RESERVES.saturating_sub(self.real_token_reserves);
+        (sold as f64 / INITIAL_REAL_TOKEN_RESERVES as f64 * 100.0).clamp(0.0, 100.0)
+    }
+
+    /// SOL held by the curve
+    pub fn liquidity_sol(&self) -> f64 {
+        self.virtual_sol_reserves as f64 / LAMPORTS_PER_SOL
+    }
+
+    /// Spot price of one token in SOL, before the fee
+    pub fn price_sol(&self) -> f64 {
+        let tokens = self.virtual_token_reserves as f64 / 10f64.powi(TOKEN_DECIMALS as i32);
+        if tokens <= 0.0 {
+            return 0.0;
+        }
+        self.liquidity_sol() / tokens
+    }
+
+    /// Tokens received for spending `lamports` (fee included)
+    pub fn buy_quote(&self, lamports: u64) -> u64 {
+        let sol_in = lamports as u128 * 10_000 / (10_000 + TRADE_FEE_BPS as u128);
+        let k = self.virtual_sol_reserves as u128 * self.virtual_token_reserves as u128;
+        let new_sol = self.virtual_sol_reserves as u128 + sol_in;
+        if new_sol == 0 {
+            return 0;
+        }
+        let tokens_out = self.virtual_token_reserves as u128 - k / new_sol;
+        tokens_out.min(self.real_token_reserves as u128) as u64
+    }
+
+    /// The curve once `lamports` (fee included) have been spent buying from it
+    pub fn after_buy(&self, lamports: u64) -> Self {
+        let tokens = self.buy_quote(lamports);
+        let sol_in = (lamports as u128 * 10_000 / (10_000 + TRADE_FEE_BPS as u128)) as u64;
+        Self {
+            virtual_token_reserves: self.virtual_token_reserves - tokens,
+            virtual_sol_reserves: self.virtual_sol_reserves + sol_in,
+            real_token_reserves: self.real_token_reserves - tokens,
+            real_sol_reserves: self.real_sol_reserves + sol_in,
+            ..*self
+        }
+    }
+
+    /// Lamports received for selling `tokens`, after the fee
+    pub fn sell_quote(&self, tokens: u64) -> u64 {
+        let k = self.virtual_sol_reserves as u128 * self.virtual_token_reserves as u128;
+        let new_tokens = self.virtual_token_reserves as u128 + tokens as u128;
+        if new_tokens == 0 {
+            return 0;
+        }
+        let sol_out = self.virtual_sol_reserves as u128 - k / new_tokens;
+        (sol_out * (10_000 - TRADE_FEE_BPS as u128) / 10_000) as u64
+    }
+}
+
+/// Derive the bonding curve account of a Pump.fun mint
+pub fn bonding_curve_address(mint: &Pubkey) -> Result<Pubkey> {
+    let program = Pubkey::from_str(PUMP_FUN_PROGRAM_ID)?;
+    let (address, _) = Pubkey::find_program_address(&[b"bonding-curve", mint.as_ref()], &program);
+    Ok(address)
+}
+
+/// Derive the associated token account of `owner` for `mint`
+pub fn associated_token_address(owner: &Pubkey, mint: &Pubkey) -> Result<Pubkey> {
+    let token_program = Pubkey::from_str(TOKEN_PROGRAM_ID)?;
+    let ata_program = Pubkey::from_str(ASSOCIATED_TOKEN_PROGRAM_ID)?;
+    let (address, _) = Pubkey::find_program_address(
+        &[owner.as_ref(), token_program.as_ref(), mint.as_ref()],
+        &ata_program,
+    );
+    Ok(address)
+}
+
+/// Create the user's token account for `mint` if it does not exist yet
+pub fn create_ata_idempotent_instruction(payer: &Pubkey, mint: &Pubkey) -> Result<Instruction> {
+    Ok(Instruction {
+        program_id: Pubkey::from_str(ASSOCIATED_TOKEN_PROGRAM_ID)?,
+        accounts: vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new(associated_token_address(payer, mint)?, false),
+            AccountMeta::new_readonly(*payer, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(Pubkey::from_str(TOKEN_PROGRAM_ID)?, false),
+        ],
+        data: vec![1],
+    })
+}
+
+/// Buy `token_amount` tokens from the curve, spending at most `max_sol_cost` lamports
+pub fn buy_instruction(mint: &Pubkey, user: &Pubkey, token_amount: u64, max_sol_cost: u64) -> Result<Instruction> {
+    let curve = bonding_curve_address(mint)?;
+    let mut data = BUY_DISCRIMINATOR.to_vec();
+    data.extend_from_slice(&token_amount.to_le_bytes());
+    data.extend_from_slice(&max_sol_cost.to_le_bytes());
+
+    Ok(Instruction {
+        program_id: Pubkey::from_str(PUMP_FUN_PROGRAM_ID)?,
+        accounts: vec![
+            AccountMeta::new_readonly(Pubkey::from_str(GLOBAL_ACCOUNT)?, false),
+            AccountMeta::new(Pubkey::from_str(FEE_RECIPIENT)?, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new(curve, false),
+            AccountMeta::new(associated_token_address(&curve, mint)?, false),
+            AccountMeta::new(associated_token_address(user, mint)?, false),
+            AccountMeta::new(*user, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(Pubkey::from_str(TOKEN_PROGRAM_ID)?, false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(Pubkey::from_str(EVENT_AUTHORITY)?, false),
+            AccountMeta::new_readonly(Pubkey::from_str(PUMP_FUN_PROGRAM_ID)?, false),
+        ],
+        data,
+    })
+}
+
+/// Sell `token_amount` tokens to the curve for at least `min_sol_output` lamports
+pub fn sell_instruction(mint: &Pubkey, user: &Pubkey, token_amount: u64, min_sol_output: u64) -> Result<Instruction> {
+    let curve = bonding_curve_address(mint)?;
+    let mut data = SELL_DISCRIMINATOR.to_vec();
+    data.extend_from_slice(&token_amount.to_le_bytes());
+    data.extend_from_slice(&min_sol_output.to_le_bytes());
+
+    Ok(Instruction {
+        program_id: Pubkey::from_str(PUMP_FUN_PROGRAM_ID)?,
+        accounts: vec![
+            AccountMeta::new_readonly(Pubkey::from_str(GLOBAL_ACCOUNT)?, false),
+            AccountMeta::new(Pubkey::from_str(FEE_RECIPIENT)?, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new(curve, false),
+            AccountMeta::new(associated_token_address(&curve, mint)?, false),
+            AccountMeta::new(associated_token_address(user, mint)?, false),
+            AccountMeta::new(*user, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(Pubkey::from_str(ASSOCIATED_TOKEN_PROGRAM_ID)?, false),
+            AccountMeta::new_readonly(Pubkey::from_str(TOKEN_PROGRAM_ID)?, false),
+            AccountMeta::new_readonly(Pubkey::from_str(EVENT_AUTHORITY)?, false),
+            AccountMeta::new_readonly(Pubkey::from_str(PUMP_FUN_PROGRAM_ID)?, false),
+        ],
+        data,
+    })
+}
+
+/// Fetch the current state of a single coin
+pub async fn fetch_coin(client: &reqwest::Client, mint: &str) -> Result<PumpCoin> {
+    let url = format!("{}/coins/{}", PUMP_FUN_API, mint);
+    let response = client.get(&url).send().await?;
+
+    if !response.status().is_success() {
+        return Err(anyhow!("Pump.fun API returned {} for {}", response.status(), mint));
+    }
+
+    Ok(response.json::<PumpCoin>().await?)
+}
+
+/// Fetch the most recently created coins
+pub async fn fetch_latest_coins(client: &reqwest::Client, limit: u32) -> Result<Vec<PumpCoin>> {
+    let url = format!(
+        "{}/coins?offset=0&limit={}&sort=created_timestamp&order=DESC&includeNsfw=false",
+        PUMP_FUN_API, limit
+    );
+    let response = client.get(&url).send().await?;
+
+    if !response.status().is_success() {
+        return Err(anyhow!("Pump.fun API returned {}", response.status()));
+    }
+
+    Ok(response.json::<Vec<PumpCoin>>().await?)
+}
+
+/// Fetch and decode the bonding curve of a mint
+pub async fn fetch_bonding_curve(rpc: &RpcClient, mint: &Pubkey) -> Result<BondingCurveState> {
+    let address = bonding_curve_address(mint)?;
+    let data = rpc.get_account_data(&address).await
+        .map_err(|e| anyhow!("No bonding curve for {}: {}", mint, e))?;
+    BondingCurveState::decode(&data)
+}
+
+/// Fetch and decode the bonding curves of several mints, `None` for those without one
+pub async fn fetch_bonding_curves(rpc: &RpcClient, mints: &[Pubkey]) -> Result<Vec<Option<BondingCurveState>>> {
+    let addresses = mints.iter().map(bonding_curve_address).collect::<Result<Vec<_>>>()?;
+
+    let mut curves = Vec::with_capacity(addresses.len());
+    for chunk in addresses.chunks(MAX_ACCOUNTS_PER_REQUEST) {
+        let accounts = rpc.get_multiple_accounts(chunk).await?;
+        curves.extend(accounts.into_iter().map(|account| account.and_then(|account| BondingCurveState::decode(&account.data).ok())));
+    }
+    Ok(curves)
+}
+
+/// Expected result of buying on a bonding curve
+#[derive(Debug, Clone, Serialize)]
+pub struct BuyQuote {
+    pub mint: String,
+    pub sol_in: f64,
+    pub tokens_out: f64,
+    /// Average price paid per token, fee included
+    pub price_sol: f64,
+    pub curve_progress: f64,
+}
+
+impl BuyQuote {
+    /// Quote a buy of `sol_in` SOL against the current curve
+    pub fn new(mint: &Pubkey, curve: &BondingCurveState, sol_in: f64) -> Result<Self> {
+        if curve.complete {
+            return Err(anyhow!("{} has migrated off the bonding curve", mint));
+        }
+
+        let raw_tokens = curve.buy_quote((sol_in * LAMPORTS_PER_SOL) as u64);
+        let tokens_out = raw_tokens as f64 / 10f64.powi(TOKEN_DECIMALS as i32);
+        Ok(Self {
+            mint: mint.to_string(),
+            sol_in,
+            tokens_out,
+            price_sol: if tokens_out > 0.0 { sol_in / tokens_out } else { 0.0 },
+            curve_progress: curve.progress(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOL: u64 = 1_000_000_000;
+
+    /// A curve as Pump.fun creates it, before any buy
+    fn fresh_curve() -> BondingCurveState {
+        BondingCurveState {
+            virtual_token_reserves: INITIAL_REAL_TOKEN_RESERVES + VIRTUAL_TOKEN_OFFSET,
+            virtual_sol_reserves: 30 * SOL,
+            real_token_reserves: INITIAL_REAL_TOKEN_RESERVES,
+            real_sol_reserves: 0,
+            token_total_supply: 1_000_000_000_000_000,
+            complete: false,
+        }
+    }
+
+    #[test]
+    fn round_trips_lose_the_fee_both_ways() {
+        let curve = fresh_curve();
+        let tokens = curve.buy_quote(SOL);
+        assert!(tokens > 0);
+        assert!(curve.sell_quote(tokens) < SOL);
+
+        // Selling into the curve the buy left behind returns the SOL less two 1% fees
+        let back = curve.after_buy(SOL).sell_quote(tokens);
+        assert!((980_000_000..=980_200_000).contains(&back), "got {} lamports back", back);
+        assert_eq!(curve.sell_quote(0), 0);
+    }
+
+    #[test]
+    fn buys_are_capped_by_the_tokens_left() {
+        let curve = fresh_curve();
+        assert_eq!(curve.buy_quote(1_000 * SOL), INITIAL_REAL_TOKEN_RESERVES);
+        assert_eq!(BondingCurveState::default().buy_quote(SOL), 0);
+    }
+
+    #[test]
+    fn progress_follows_the_tokens_sold() {
+        let mut curve = fresh_curve();
+        assert_eq!(curve.progress(), 0.0);
+        assert_eq!(curve_progress(curve.virtual_token_reserves), 0.0);
+
+        curve.real_token_reserves = INITIAL_REAL_TOKEN_RESERVES / 2;
+        curve.virtual_token_reserves = curve.real_token_reserves + VIRTUAL_TOKEN_OFFSET;
+        assert!((curve.progress() - 50.0).abs() < 1e-9);
+        assert!((curve_progress(curve.virtual_token_reserves) - 50.0).abs() < 1e-9);
+
+        curve.complete = true;
+        assert_eq!(curve.progress(), 100.0);
+    }
+}
@@ -1,63 +1,138 @@
-use futures_util::{SinkExt, StreamExt};
-use serde_json::Value;
-use tokio_tungstenite::connect_async;
-use url::Url;
-
-use crate::scanner::TokenOpportunity;
+use std::collections::VecDeque;
 
+use anyhow::{Result, anyhow};
 use futures::{StreamExt, SinkExt};
+use metrics::gauge;
 use serde::Deserialize;
-use tokio_tungstenite::connect_async;
+use tokio::net::TcpStream;
+use tokio_tungstenite::{connect_async, MaybeTlsStream, WebSocketStream};
 use tokio_tungstenite::tungstenite::Message;
+use crate::telemetry::WEBSOCKET_CONNECTED;
+
+/// Mints whose trades are streamed at once; the oldest are unsubscribed first
+const MAX_TRADE_SUBSCRIPTIONS: usize = 500;
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct NewTokenEvent {
     pub mint: String,
     pub name: String,
     pub symbol: String,
-    pub priceNative: String,
-    pub priceUsd: String,
+    #[serde(default, rename = "priceNative")]
+    pub price_native: String,
+    #[serde(default, rename = "priceUsd")]
+    pub price_usd: String,
+    #[serde(default)]
     pub liquidity: String,
-    pub createdAt: String,
+    #[serde(default, rename = "createdAt")]
+    pub created_at: String,
+    #[serde(default, rename = "traderPublicKey")]
+    pub trader_public_key: String,
+    /// SOL spent by the creator's initial buy
+    #[serde(default, rename = "solAmount")]
+    pub sol_amount: f64,
+}
+
+/// Buy or sell on a bonding curve streamed by PumpPortal
+#[derive(Debug, Deserialize, Clone)]
+pub struct TradeEvent {
+    pub mint: String,
+    #[serde(rename = "traderPublicKey")]
+    pub trader: String,
+    /// "buy" or "sell"
+    #[serde(rename = "txType")]
+    pub tx_type: String,
+    #[serde(default, rename = "solAmount")]
+    pub sol_amount: f64,
 }
 
-pub async fn listen_new_pump_fun_pairs<F>(mut on_token: F)
+/// Mints whose trades are streamed, kept across reconnects so they are followed again
+#[derive(Debug, Default)]
+pub struct TradeSubscriptions(VecDeque<String>);
+
+/// Stream new pairs and their trades until the connection drops
+///
+/// The trades of every mint in `trade_subscriptions` are streamed too, and
+/// each new pair is added to it. Fails if connecting or subscribing fails, or
+/// the stream errors; returns `Ok` when the server closes the stream.
+pub async fn listen_new_pump_fun_pairs<F, T>(
+    on_token: &mut F,
+    on_trade: &mut T,
+    trade_subscriptions: &mut TradeSubscriptions,
+) -> Result<()>
 where
-    F: FnMut(NewTokenEvent) + Send + 'static,
+    F: FnMut(NewTokenEvent),
+    T: FnMut(TradeEvent),
 {
     let url = "wss://pumpportal.fun/api/data";
-    let (ws_stream, _) = match connect_async(url).await {
-        Ok(res) => res,
-        Err(e) => {
-            eprintln!("Failed to connect to Pump.fun WS: {}", e);
-            return;
-        }
-    };
-    println!("WebSocket connected to {}", url);
+    let (ws_stream, _) = connect_async(url).await
+        .map_err(|e| anyhow!("Failed to connect to Pump.fun WS: {}", e))?;
+    log::info!("WebSocket connected to {}", url);
+
+    gauge!(WEBSOCKET_CONNECTED).set(1.0);
+    let streamed = stream(ws_stream, on_token, on_trade, &mut trade_subscriptions.0).await;
+    gauge!(WEBSOCKET_CONNECTED).set(0.0);
+    streamed
+}
+
+async fn stream<F, T>(
+    ws_stream: WebSocketStream<MaybeTlsStream<TcpStream>>,
+    on_token: &mut F,
+    on_trade: &mut T,
+    trade_subscriptions: &mut VecDeque<String>,
+) -> Result<()>
+where
+    F: FnMut(NewTokenEvent),
+    T: FnMut(TradeEvent),
+{
     let (mut write, mut read) = ws_stream.split();
     // Subscribe to new-token events
     let subscribe = serde_json::json!({"method": "subscribeNewToken"});
-    if let Err(e) = write.send(Message::Text(subscribe.to_string())).await {
-        eprintln!("Failed to subscribe: {}", e);
-        return;
+    write.send(Message::Text(subscribe.to_string())).await
+        .map_err(|e| anyhow!("Failed to subscribe: {}", e))?;
+    // Subscriptions do not outlive the connection, so follow the tracked mints' trades again
+    if !trade_subscriptions.is_empty() {
+        let subscribe = serde_json::json!({"method": "subscribeTokenTrade", "keys": trade_subscriptions});
+        write.send(Message::Text(subscribe.to_string())).await
+            .map_err(|e| anyhow!("Failed to subscribe to trades: {}", e))?;
     }
     // Read incoming messages
     while let Some(msg) = read.next().await {
-        let msg = match msg {
-            Ok(m) => m,
-            Err(e) => {
-                eprintln!("WebSocket error: {}", e);
-                break;
-            }
-        };
+        let msg = msg.map_err(|e| anyhow!("WebSocket error: {}", e))?;
         if let Message::Text(txt) = msg {
-            if let Ok(evt) = serde_json::from_str::<NewTokenEvent>(&txt) {
-                on_token(evt.clone());
-                println!(
-                    "[{}] New pair: {} ({}) — price: {} USD, liquidity: {}",
-                    evt.createdAt, evt.name, evt.symbol, evt.priceUsd, evt.liquidity
-                );
+            let Ok(value) = serde_json::from_str::<serde_json::Value>(&txt) else {
+                continue;
+            };
+            match value["txType"].as_str() {
+                Some("buy") | Some("sell") => {
+                    if let Ok(trade) = serde_json::from_value::<TradeEvent>(value) {
+                        on_trade(trade);
+                    }
+                }
+                // Launch events carry no txType on older feeds, so anything else is treated as one
+                _ => {
+                    let Ok(evt) = serde_json::from_value::<NewTokenEvent>(value) else {
+                        continue;
+                    };
+                    on_token(evt.clone());
+                    log::info!(
+                        "[{}] New pair: {} ({}) — price: {} USD, liquidity: {}",
+                        evt.created_at, evt.name, evt.symbol, evt.price_usd, evt.liquidity
+                    );
+
+                    // Follow the new mint's trades to measure its momentum
+                    let subscribe = serde_json::json!({"method": "subscribeTokenTrade", "keys": [evt.mint]});
+                    write.send(Message::Text(subscribe.to_string())).await
+                        .map_err(|e| anyhow!("Failed to subscribe to trades: {}", e))?;
+                    trade_subscriptions.push_back(evt.mint);
+                    if trade_subscriptions.len() > MAX_TRADE_SUBSCRIPTIONS {
+                        if let Some(oldest) = trade_subscriptions.pop_front() {
+                            let unsubscribe = serde_json::json!({"method": "unsubscribeTokenTrade", "keys": [oldest]});
+                            let _ = write.send(Message::Text(unsubscribe.to_string())).await;
+                        }
+                    }
+                }
             }
         }
     }
+    Ok(())
 }
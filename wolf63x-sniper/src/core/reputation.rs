@@ -0,0 +1,344 @@
+use serde::{Serialize, Deserialize};
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Rugged launches after which a creator is blacklisted automatically
+const DEFAULT_AUTO_BLACKLIST_RUGS: u32 = 2;
+
+/// Days after their last launch that creators with nothing noteworthy are forgotten
+const STALE_CREATOR_DAYS: i64 = 30;
+
+/// How a creator's launch ended up
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LaunchOutcome {
+    Pending,
+    Migrated,
+    Rugged,
+    Abandoned,
+}
+
+/// A single token launched by a creator
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LaunchRecord {
+    pub mint: String,
+    pub symbol: String,
+    pub launched_at: DateTime<Utc>,
+    pub outcome: LaunchOutcome,
+}
+
+/// Everything known about a creator wallet
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CreatorRecord {
+    pub address: String,
+    pub launches: Vec<LaunchRecord>,
+    pub blacklisted: bool,
+    /// Set when the blacklist flag was edited by hand, so it is never overridden
+    pub manual: bool,
+    pub note: Option<String>,
+}
+
+/// Summary of a creator's history attached to each opportunity
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CreatorReputation {
+    pub launches: u32,
+    pub migrated: u32,
+    pub rugged: u32,
+    pub abandoned: u32,
+    pub blacklisted: bool,
+}
+
+impl CreatorRecord {
+    fn count(&self, outcome: LaunchOutcome) -> u32 {
+        self.launches.iter().filter(|launch| launch.outcome == outcome).count() as u32
+    }
+
+    pub fn summary(&self) -> CreatorReputation {
+        CreatorReputation {
+            launches: self.launches.len() as u32,
+            migrated: self.count(LaunchOutcome::Migrated),
+            rugged: self.count(LaunchOutcome::Rugged),
+            abandoned: self.count(LaunchOutcome::Abandoned),
+            blacklisted: self.blacklisted,
+        }
+    }
+}
+
+/// Creator reputation store, persisted as JSON on native targets
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ReputationStore {
+    creators: HashMap<String, CreatorRecord>,
+    #[serde(skip)]
+    path: Option<PathBuf>,
+    #[serde(skip)]
+    auto_blacklist_rugs: u32,
+    /// Bumped on every change, so writes can run without holding the store
+    #[serde(skip)]
+    revision: u64,
+    #[serde(skip)]
+    saved_revision: u64,
+}
+
+/// The store serialized at one revision, ready to be written off the hot path
+pub struct ReputationSnapshot {
+    path: PathBuf,
+    json: String,
+    revision: u64,
+}
+
+impl ReputationSnapshot {
+    /// Write the snapshot, replacing the previous file atomically
+    pub fn write(&self) -> Result<()> {
+        let tmp = self.path.with_extension("json.tmp");
+        std::fs::write(&tmp, &self.json)?;
+        std::fs::rename(&tmp, &self.path)?;
+        Ok(())
+    }
+
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+}
+
+impl ReputationStore {
+    /// In-memory store that is never written to disk
+    pub fn new() -> Self {
+        Self {
+            auto_blacklist_rugs: DEFAULT_AUTO_BLACKLIST_RUGS,
+            ..Self::default()
+        }
+    }
+
+    /// Load the store from `path`, starting empty if the file does not exist yet
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+
+        let mut store = if path.exists() {
+            let json = std::fs::read_to_string(&path)?;
+            serde_json::from_str::<ReputationStore>(&json)?
+        } else {
+            Self::default()
+        };
+
+        store.path = Some(path);
+        store.auto_blacklist_rugs = DEFAULT_AUTO_BLACKLIST_RUGS;
+
+        Ok(store)
+    }
+
+    /// Number of rugged launches that blacklists a creator (0 disables it)
+    pub fn set_auto_blacklist_rugs(&mut self, rugs: u32) {
+        self.auto_blacklist_rugs = rugs;
+    }
+
+    /// Write the store to disk now if it has unsaved changes
+    pub fn save(&mut self) -> Result<()> {
+        if let Some(snapshot) = self.snapshot()? {
+            snapshot.write()?;
+            self.mark_saved(snapshot.revision());
+        }
+        Ok(())
+    }
+
+    /// Serialize unsaved changes; `None` when there is nothing to write
+    pub fn snapshot(&self) -> Result<Option<ReputationSnapshot>> {
+        let Some(path) = &self.path else {
+            return Ok(None);
+        };
+        if self.revision == self.saved_revision {
+            return Ok(None);
+        }
+
+        Ok(Some(ReputationSnapshot {
+            path: path.clone(),
+            json: serde_json::to_string_pretty(self)?,
+            revision: self.revision,
+        }))
+    }
+
+    /// Record that a snapshot reached the disk; later changes stay unsaved
+    pub fn mark_saved(&mut self, revision: u64) {
+        self.saved_revision = self.saved_revision.max(revision);
+    }
+
+    /// Forget creators whose last launch is older than 30 days, unless they
+    /// are blacklisted, were edited by hand or rugged a launch
+    ///
+    /// Rugs are kept so that they keep counting towards the auto-blacklist.
+    /// Returns how many creators were removed.
+    pub fn prune_stale(&mut self, now: DateTime<Utc>) -> usize {
+        let cutoff = now - Duration::days(STALE_CREATOR_DAYS);
+        let before = self.creators.len();
+
+        self.creators.retain(|_, record| {
+            record.blacklisted
+                || record.manual
+                || record.count(LaunchOutcome::Rugged) > 0
+                || record.launches.iter().any(|launch| launch.launched_at > cutoff)
+        });
+
+        let removed = before - self.creators.len();
+        if removed > 0 {
+            self.touch();
+        }
+        removed
+    }
+
+    /// Record a new launch by `creator`, ignoring mints already known
+    pub fn record_launch(&mut self, creator: &str, mint: &str, symbol: &str, launched_at: DateTime<Utc>) {
+        let record = self.creators.entry(creator.to_string()).or_insert_with(|| CreatorRecord {
+            address: creator.to_string(),
+            ..CreatorRecord::default()
+        });
+
+        if record.launches.iter().any(|launch| launch.mint == mint) {
+            return;
+        }
+
+        record.launches.push(LaunchRecord {
+            mint: mint.to_string(),
+            symbol: symbol.to_string(),
+            launched_at,
+            outcome: LaunchOutcome::Pending,
+        });
+
+        self.touch();
+    }
+
+    /// Settle a pending launch, blacklisting the creator once they rug too often
+    pub fn record_outcome(&mut self, creator: &str, mint: &str, outcome: LaunchOutcome) {
+        let Some(record) = self.creators.get_mut(creator) else {
+            return;
+        };
+
+        let Some(launch) = record.launches.iter_mut().find(|launch| launch.mint == mint) else {
+            return;
+        };
+
+        if launch.outcome != LaunchOutcome::Pending {
+            return;
+        }
+        launch.outcome = outcome;
+
+        let rugs = record.count(LaunchOutcome::Rugged);
+        if !record.manual && self.auto_blacklist_rugs > 0 && rugs >= self.auto_blacklist_rugs {
+            if !record.blacklisted {
+                log::info!("Auto-blacklisting creator {} after {} rugged launches", creator, rugs);
+            }
+            record.blacklisted = true;
+        }
+
+        self.touch();
+    }
+
+    /// Outcome recorded for a launch, if the launch is known
+    pub fn outcome(&self, creator: &str, mint: &str) -> Option<LaunchOutcome> {
+        self.creators.get(creator)?
+            .launches.iter()
+            .find(|launch| launch.mint == mint)
+            .map(|launch| launch.outcome)
+    }
+
+    pub fn summary(&self, creator: &str) -> Option<CreatorReputation> {
+        self.creators.get(creator).map(CreatorRecord::summary)
+    }
+
+    pub fn get(&self, creator: &str) -> Option<&CreatorRecord> {
+        self.creators.get(creator)
+    }
+
+    pub fn list(&self) -> Vec<&CreatorRecord> {
+        self.creators.values().collect()
+    }
+
+    /// Manually set a creator's blacklist flag; automatic rules leave it alone afterwards
+    pub fn set_blacklisted(&mut self, creator: &str, blacklisted: bool, note: Option<String>) {
+        let record = self.creators.entry(creator.to_string()).or_insert_with(|| CreatorRecord {
+            address: creator.to_string(),
+            ..CreatorRecord::default()
+        });
+
+        record.blacklisted = blacklisted;
+        record.manual = true;
+        if note.is_some() {
+            record.note = note;
+        }
+
+        self.touch();
+    }
+
+    /// Forget everything about a creator
+    pub fn remove(&mut self, creator: &str) -> bool {
+        let removed = self.creators.remove(creator).is_some();
+        if removed {
+            self.touch();
+        }
+        removed
+    }
+
+    fn touch(&mut self) {
+        self.revision += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn launch(store: &mut ReputationStore, creator: &str, mint: &str, launched_at: DateTime<Utc>, outcome: LaunchOutcome) {
+        store.record_launch(creator, mint, "SYM", launched_at);
+        store.record_outcome(creator, mint, outcome);
+    }
+
+    #[test]
+    fn creators_are_blacklisted_once_they_rug_too_often() {
+        let mut store = ReputationStore::new();
+        let now = Utc::now();
+        launch(&mut store, "creator", "a", now, LaunchOutcome::Rugged);
+        launch(&mut store, "creator", "b", now, LaunchOutcome::Migrated);
+        assert!(!store.summary("creator").unwrap().blacklisted);
+
+        launch(&mut store, "creator", "c", now, LaunchOutcome::Rugged);
+        assert!(store.summary("creator").unwrap().blacklisted);
+
+        // A settled launch is not settled again
+        store.record_outcome("creator", "b", LaunchOutcome::Rugged);
+        assert_eq!(store.outcome("creator", "b"), Some(LaunchOutcome::Migrated));
+    }
+
+    #[test]
+    fn manual_blacklist_edits_are_not_overridden() {
+        let mut store = ReputationStore::new();
+        store.set_blacklisted("creator", false, Some("known dev".to_string()));
+        let now = Utc::now();
+        launch(&mut store, "creator", "a", now, LaunchOutcome::Rugged);
+        launch(&mut store, "creator", "b", now, LaunchOutcome::Rugged);
+        assert!(!store.summary("creator").unwrap().blacklisted);
+
+        store.set_auto_blacklist_rugs(0);
+        launch(&mut store, "other", "c", now, LaunchOutcome::Rugged);
+        launch(&mut store, "other", "d", now, LaunchOutcome::Rugged);
+        assert!(!store.summary("other").unwrap().blacklisted);
+    }
+
+    #[test]
+    fn pruning_keeps_creators_whose_history_still_matters() {
+        let mut store = ReputationStore::new();
+        let now = Utc::now();
+        let old = now - Duration::days(STALE_CREATOR_DAYS + 1);
+        launch(&mut store, "stale", "a", old, LaunchOutcome::Abandoned);
+        launch(&mut store, "recent", "b", now, LaunchOutcome::Pending);
+        launch(&mut store, "rugger", "c", old, LaunchOutcome::Rugged);
+        launch(&mut store, "blacklisted", "d", old, LaunchOutcome::Migrated);
+        store.set_blacklisted("blacklisted", true, None);
+
+        assert_eq!(store.prune_stale(now), 1);
+        assert!(store.get("stale").is_none());
+        assert!(store.get("recent").is_some() && store.get("blacklisted").is_some());
+
+        // The rug still counts, so a second one blacklists the creator
+        launch(&mut store, "rugger", "e", now, LaunchOutcome::Rugged);
+        assert!(store.summary("rugger").unwrap().blacklisted);
+    }
+}
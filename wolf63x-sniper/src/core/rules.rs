@@ -0,0 +1,717 @@
+use serde::{Serialize, Deserialize};
+use anyhow::{Result, anyhow};
+use std::collections::HashSet;
+use crate::filters::{FilterSettings, PatternList};
+use crate::scanner::TokenOpportunity;
+
+/// A built-in rule: `Ok(reason)` when it passes, `Err(reason)` when it rejects
+type BuiltinRule = fn(&FilterSettings, &TokenOpportunity) -> Result<String, String>;
+
+/// Built-in rules by name, in the order they are evaluated by default
+const BUILTIN_RULES: &[(&str, BuiltinRule)] = &[
+    ("liquidity", liquidity),
+    ("holders", holders),
+    ("taxes", taxes),
+    ("lp_lock", lp_lock),
+    ("token_age", token_age),
+    ("blacklist", blacklist),
+    ("supply", supply),
+    ("similar_tokens", similar_tokens),
+    ("name_patterns", name_patterns),
+    ("symbol_patterns", symbol_patterns),
+    ("description_keywords", description_keywords),
+    ("social_links", social_links),
+    ("curve_progress", curve_progress),
+    ("momentum", momentum),
+    ("mint_authority", mint_authority),
+    ("freeze_authority", freeze_authority),
+    ("token_extensions", token_extensions),
+    ("holder_concentration", holder_concentration),
+    ("creator_holding", creator_holding),
+    ("funding_clusters", funding_clusters),
+    ("bundles", bundles),
+    ("honeypot", honeypot),
+    ("score", score),
+];
+
+/// Composable rule expression, e.g. `{"all": [{"rule": "liquidity"}, {"not": {"rule": "honeypot"}}]}`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleExpr {
+    /// A built-in or custom rule by name
+    Rule(String),
+    All(Vec<RuleExpr>),
+    Any(Vec<RuleExpr>),
+    Not(Box<RuleExpr>),
+}
+
+/// Comparison applied by a custom rule
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CompareOp {
+    Eq,
+    Ne,
+    Gt,
+    Gte,
+    Lt,
+    Lte,
+    Contains,
+}
+
+/// User-defined rule comparing a token field against a value
+///
+/// `field` is a dotted path into the serialized opportunity, e.g. `market_cap`
+/// or `metadata.twitter`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomRule {
+    pub name: String,
+    pub field: String,
+    pub op: CompareOp,
+    pub value: serde_json::Value,
+}
+
+/// Outcome of a single named rule
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuleResult {
+    pub rule: String,
+    pub passed: bool,
+    pub reason: String,
+}
+
+/// Outcome of running a token through the whole rule expression
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FilterVerdict {
+    pub passed: bool,
+    /// Every rule evaluated, whether or not it decided the outcome
+    pub results: Vec<RuleResult>,
+}
+
+impl FilterVerdict {
+    /// Rules that rejected the token
+    pub fn failures(&self) -> impl Iterator<Item = &RuleResult> {
+        self.results.iter().filter(|result| !result.passed)
+    }
+}
+
+/// Names of all built-in rules
+pub fn builtin_rule_names() -> Vec<&'static str> {
+    BUILTIN_RULES.iter().map(|(name, _)| *name).collect()
+}
+
+/// Expression used when the settings do not define one: every rule must pass
+pub fn default_expression(settings: &FilterSettings) -> RuleExpr {
+    let builtin = BUILTIN_RULES.iter().map(|(name, _)| name.to_string());
+    let custom = settings.custom_rules.iter().map(|rule| rule.name.clone());
+
+    RuleExpr::All(builtin.chain(custom).map(RuleExpr::Rule).collect())
+}
+
+/// Reject rule definitions that reference unknown rules or shadow built-in ones
+pub fn check_rules(settings: &FilterSettings) -> Result<()> {
+    let mut known: HashSet<&str> = BUILTIN_RULES.iter().map(|(name, _)| *name).collect();
+
+    for rule in &settings.custom_rules {
+        if !known.insert(rule.name.as_str()) {
+            return Err(anyhow!("Duplicate rule name: {}", rule.name));
+        }
+    }
+
+    if let Some(expression) = &settings.rules {
+        check_expression(expression, &known)?;
+    }
+
+    Ok(())
+}
+
+/// Reject an expression that references rules unknown to `settings`
+pub fn check_expression_for(settings: &FilterSettings, expression: &RuleExpr) -> Result<()> {
+    let known: HashSet<&str> = BUILTIN_RULES.iter().map(|(name, _)| *name)
+        .chain(settings.custom_rules.iter().map(|rule| rule.name.as_str()))
+        .collect();
+
+    check_expression(expression, &known)
+}
+
+fn check_expression(expression: &RuleExpr, known: &HashSet<&str>) -> Result<()> {
+    match expression {
+        RuleExpr::Rule(name) if !known.contains(name.as_str()) => Err(anyhow!("Unknown rule: {}", name)),
+        RuleExpr::Rule(_) => Ok(()),
+        RuleExpr::All(children) | RuleExpr::Any(children) => {
+            children.iter().try_for_each(|child| check_expression(child, known))
+        }
+        RuleExpr::Not(child) => check_expression(child, known),
+    }
+}
+
+/// Run a token through the configured rule expression
+pub fn evaluate(settings: &FilterSettings, token: &TokenOpportunity) -> FilterVerdict {
+    match &settings.rules {
+        Some(expression) => evaluate_expression(settings, expression, token),
+        None => evaluate_expression(settings, &default_expression(settings), token),
+    }
+}
+
+/// Run a token through an arbitrary expression, with rule thresholds from `settings`
+pub fn evaluate_expression(settings: &FilterSettings, expression: &RuleExpr, token: &TokenOpportunity) -> FilterVerdict {
+    let mut evaluator = Evaluator {
+        settings,
+        token,
+        token_json: None,
+        results: Vec::new(),
+    };
+
+    let passed = evaluator.eval(expression);
+
+    FilterVerdict {
+        passed,
+        results: evaluator.results,
+    }
+}
+
+/// Walks an expression without short-circuiting so every rule gets reported
+struct Evaluator<'a> {
+    settings: &'a FilterSettings,
+    token: &'a TokenOpportunity,
+    token_json: Option<serde_json::Value>,
+    results: Vec<RuleResult>,
+}
+
+impl Evaluator<'_> {
+    fn eval(&mut self, expression: &RuleExpr) -> bool {
+        match expression {
+            RuleExpr::Rule(name) => self.rule(name),
+            RuleExpr::All(children) => self.eval_all(children).into_iter().all(|passed| passed),
+            RuleExpr::Any(children) => self.eval_all(children).into_iter().any(|passed| passed),
+            RuleExpr::Not(child) => !self.eval(child),
+        }
+    }
+
+    /// Evaluate every child before combining, so none is skipped
+    fn eval_all(&mut self, children: &[RuleExpr]) -> Vec<bool> {
+        children.iter().map(|child| self.eval(child)).collect()
+    }
+
+    fn rule(&mut self, name: &str) -> bool {
+        if let Some(result) = self.results.iter().find(|result| result.rule == name) {
+            return result.passed;
+        }
+
+        let outcome = if let Some((_, rule)) = BUILTIN_RULES.iter().find(|(rule, _)| *rule == name) {
+            rule(self.settings, self.token)
+        } else if let Some(rule) = self.settings.custom_rules.iter().find(|rule| rule.name == name) {
+            self.custom(rule)
+        } else {
+            Err(format!("Unknown rule {}", name))
+        };
+
+        let passed = outcome.is_ok();
+        self.results.push(RuleResult {
+            rule: name.to_string(),
+            passed,
+            reason: outcome.unwrap_or_else(|reason| reason),
+        });
+
+        passed
+    }
+
+    fn custom(&mut self, rule: &CustomRule) -> Result<String, String> {
+        let token = self.token;
+        let json = self.token_json.get_or_insert_with(|| {
+            serde_json::to_value(token).unwrap_or(serde_json::Value::Null)
+        });
+
+        let pointer = format!("/{}", rule.field.replace('.', "/"));
+        let actual = json.pointer(&pointer).cloned().unwrap_or(serde_json::Value::Null);
+        let description = format!("{} is {} ({:?} {})", rule.field, actual, rule.op, rule.value);
+
+        if compare(&actual, rule.op, &rule.value) {
+            Ok(description)
+        } else {
+            Err(description)
+        }
+    }
+}
+
+fn compare(actual: &serde_json::Value, op: CompareOp, expected: &serde_json::Value) -> bool {
+    match op {
+        CompareOp::Eq => actual == expected,
+        CompareOp::Ne => actual != expected,
+        CompareOp::Contains => match (actual, expected) {
+            (serde_json::Value::String(actual), serde_json::Value::String(expected)) => {
+                actual.to_lowercase().contains(&expected.to_lowercase())
+            }
+            (serde_json::Value::Array(items), expected) => items.contains(expected),
+            _ => false,
+        },
+        _ => match (actual.as_f64(), expected.as_f64()) {
+            (Some(actual), Some(expected)) => match op {
+                CompareOp::Gt => actual > expected,
+                CompareOp::Gte => actual >= expected,
+                CompareOp::Lt => actual < expected,
+                _ => actual <= expected,
+            },
+            _ => false,
+        },
+    }
+}
+
+fn check(passed: bool, reason: String) -> Result<String, String> {
+    if passed {
+        Ok(reason)
+    } else {
+        Err(reason)
+    }
+}
+
+fn liquidity(settings: &FilterSettings, token: &TokenOpportunity) -> Result<String, String> {
+    check(
+        token.liquidity >= settings.min_liquidity,
+        format!("Liquidity {:.2} SOL (minimum {:.2})", token.liquidity, settings.min_liquidity),
+    )
+}
+
+fn holders(settings: &FilterSettings, token: &TokenOpportunity) -> Result<String, String> {
+    check(
+        token.holders >= settings.min_holders,
+        format!("{} holders (minimum {})", token.holders, settings.min_holders),
+    )
+}
+
+fn taxes(settings: &FilterSettings, token: &TokenOpportunity) -> Result<String, String> {
+    check(
+        token.buy_tax <= settings.max_buy_tax && token.sell_tax <= settings.max_sell_tax,
+        format!(
+            "Buy tax {}% (max {}%), sell tax {}% (max {}%)",
+            token.buy_tax, settings.max_buy_tax, token.sell_tax, settings.max_sell_tax
+        ),
+    )
+}
+
+fn lp_lock(settings: &FilterSettings, token: &TokenOpportunity) -> Result<String, String> {
+    if !settings.require_lp_lock {
+        return Ok("LP lock not required".to_string());
+    }
+
+    if !token.lp_locked {
+        return Err("LP is not locked".to_string());
+    }
+
+    match token.lp_lock_end {
+        Some(lock_end) => {
+            let days_locked = (lock_end - chrono::Utc::now()).num_days();
+            check(
+                days_locked >= settings.min_lp_lock_days as i64,
+                format!("LP locked for {} more days (minimum {})", days_locked, settings.min_lp_lock_days),
+            )
+        }
+        None => Err("LP lock end is unknown".to_string()),
+    }
+}
+
+fn token_age(settings: &FilterSettings, token: &TokenOpportunity) -> Result<String, String> {
+    let age_minutes = (chrono::Utc::now() - token.created_at).num_minutes();
+    check(
+        age_minutes >= settings.min_token_age_minutes as i64,
+        format!("Token is {} minutes old (minimum {})", age_minutes, settings.min_token_age_minutes),
+    )
+}
+
+fn blacklist(settings: &FilterSettings, token: &TokenOpportunity) -> Result<String, String> {
+    if token.creator_reputation.as_ref().is_some_and(|r| r.blacklisted) {
+        return Err(format!("Creator {} is blacklisted by reputation", token.creator));
+    }
+
+    if settings.excluded_tokens.contains(&token.address) {
+        return Err(format!("Token {} is excluded", token.address));
+    }
+
+    if settings.blacklisted_creators.contains(&token.creator) {
+        return Err(format!("Creator {} is blacklisted", token.creator));
+    }
+
+    Ok("Creator is not blacklisted".to_string())
+}
+
+fn supply(settings: &FilterSettings, token: &TokenOpportunity) -> Result<String, String> {
+    if settings.min_total_supply.is_none() && settings.max_total_supply.is_none() {
+        return Ok("No supply bounds configured".to_string());
+    }
+
+    // Unknown until the mint account has been decoded
+    if token.freshness.mint.is_none() {
+        return Err("Mint account not decoded yet".to_string());
+    }
+
+    let supply = token.supply as f64 / 10f64.powi(token.decimals as i32);
+    let min = settings.min_total_supply.unwrap_or(0.0);
+    let max = settings.max_total_supply.unwrap_or(f64::INFINITY);
+
+    check(
+        supply >= min && supply <= max,
+        format!("Total supply {:.0} (range {:.0}-{:.0})", supply, min, max),
+    )
+}
+
+fn similar_tokens(settings: &FilterSettings, token: &TokenOpportunity) -> Result<String, String> {
+    check(
+        token.similar_tokens <= settings.max_similar_tokens as u32,
+        format!(
+            "{} similar launches in the last {} minutes (max {})",
+            token.similar_tokens, settings.similarity_window_minutes, settings.max_similar_tokens
+        ),
+    )
+}
+
+fn name_patterns(settings: &FilterSettings, token: &TokenOpportunity) -> Result<String, String> {
+    match_patterns("Name", &token.name, &settings.name_allow, &settings.name_deny)
+}
+
+fn symbol_patterns(settings: &FilterSettings, token: &TokenOpportunity) -> Result<String, String> {
+    match_patterns("Symbol", &token.symbol, &settings.symbol_allow, &settings.symbol_deny)
+}
+
+fn match_patterns(label: &str, text: &str, allow: &PatternList, deny: &PatternList) -> Result<String, String> {
+    if let Some(pattern) = deny.find_match(text) {
+        return Err(format!("{} {:?} matches denied pattern {}", label, text, pattern));
+    }
+
+    if allow.is_empty() {
+        return Ok(format!("{} {:?} is not denied", label, text));
+    }
+
+    match allow.find_match(text) {
+        Some(pattern) => Ok(format!("{} {:?} matches allowed pattern {}", label, text, pattern)),
+        None => Err(format!("{} {:?} matches no allowed pattern", label, text)),
+    }
+}
+
+fn description_keywords(settings: &FilterSettings, token: &TokenOpportunity) -> Result<String, String> {
+    if settings.description_allow_keywords.is_empty() && settings.description_deny_keywords.is_empty() {
+        return Ok("No description keywords configured".to_string());
+    }
+
+    // Unknown until the metadata URI has been resolved
+    if token.freshness.metadata.is_none() {
+        return Err("Metadata not fetched yet".to_string());
+    }
+
+    let description = token.metadata.as_ref()
+        .and_then(|metadata| metadata.description.as_deref())
+        .unwrap_or_default()
+        .to_lowercase();
+    let find = |keywords: &[String]| {
+        keywords.iter().find(|keyword| description.contains(&keyword.to_lowercase())).cloned()
+    };
+
+    if let Some(keyword) = find(&settings.description_deny_keywords) {
+        return Err(format!("Description mentions denied keyword {:?}", keyword));
+    }
+
+    if settings.description_allow_keywords.is_empty() {
+        return Ok("Description mentions no denied keyword".to_string());
+    }
+
+    match find(&settings.description_allow_keywords) {
+        Some(keyword) => Ok(format!("Description mentions {:?}", keyword)),
+        None => Err("Description mentions no required keyword".to_string()),
+    }
+}
+
+fn social_links(settings: &FilterSettings, token: &TokenOpportunity) -> Result<String, String> {
+    if !settings.require_website && !settings.require_twitter && !settings.require_telegram {
+        return Ok("No social links required".to_string());
+    }
+
+    if token.freshness.metadata.is_none() {
+        return Err("Metadata not fetched yet".to_string());
+    }
+
+    let metadata = token.metadata.clone().unwrap_or_default();
+    let present = |link: &Option<String>| link.as_deref().is_some_and(|link| !link.trim().is_empty());
+
+    let missing: Vec<&str> = [
+        (settings.require_website, present(&metadata.website), "website"),
+        (settings.require_twitter, present(&metadata.twitter), "twitter"),
+        (settings.require_telegram, present(&metadata.telegram), "telegram"),
+    ]
+    .into_iter()
+    .filter(|(required, present, _)| *required && !present)
+    .map(|(_, _, link)| link)
+    .collect();
+
+    check(
+        missing.is_empty(),
+        if missing.is_empty() {
+            "All required social links present".to_string()
+        } else {
+            format!("Missing {}", missing.join(", "))
+        },
+    )
+}
+
+fn curve_progress(settings: &FilterSettings, token: &TokenOpportunity) -> Result<String, String> {
+    check(
+        token.bonding_curve_progress >= settings.min_curve_progress
+            && token.bonding_curve_progress <= settings.max_curve_progress,
+        format!(
+            "Bonding curve {:.1}% complete (range {:.1}-{:.1}%)",
+            token.bonding_curve_progress, settings.min_curve_progress, settings.max_curve_progress
+        ),
+    )
+}
+
+fn momentum(settings: &FilterSettings, token: &TokenOpportunity) -> Result<String, String> {
+    if settings.min_sol_inflow_per_minute <= 0.0 && settings.min_buy_sell_ratio <= 0.0 && settings.min_unique_buyers == 0 {
+        return Ok("No momentum thresholds configured".to_string());
+    }
+
+    // Unknown until the trade stream has delivered something for this mint
+    let Some(momentum) = &token.momentum else {
+        return Err("No trades seen yet".to_string());
+    };
+
+    check(
+        momentum.sol_inflow_per_minute >= settings.min_sol_inflow_per_minute
+            && momentum.buy_sell_ratio >= settings.min_buy_sell_ratio
+            && momentum.unique_buyers >= settings.min_unique_buyers,
+        format!(
+            "Inflow {:.2} SOL/min (min {:.2}), buy/sell {:.2} (min {:.2}), {} early buyers (min {})",
+            momentum.sol_inflow_per_minute, settings.min_sol_inflow_per_minute,
+            momentum.buy_sell_ratio, settings.min_buy_sell_ratio,
+            momentum.unique_buyers, settings.min_unique_buyers
+        ),
+    )
+}
+
+fn mint_authority(settings: &FilterSettings, token: &TokenOpportunity) -> Result<String, String> {
+    if !settings.reject_mint_authority {
+        return Ok("Mint authority allowed".to_string());
+    }
+
+    // Unknown until the mint account has been decoded
+    match (&token.freshness.mint, &token.mint_authority) {
+        (None, _) => Err("Mint account not decoded yet".to_string()),
+        (Some(_), Some(authority)) => Err(format!("Mint authority {} can mint more supply", authority)),
+        (Some(_), None) => Ok("Mint authority revoked".to_string()),
+    }
+}
+
+fn freeze_authority(settings: &FilterSettings, token: &TokenOpportunity) -> Result<String, String> {
+    if !settings.reject_freeze_authority {
+        return Ok("Freeze authority allowed".to_string());
+    }
+
+    match (&token.freshness.mint, &token.freeze_authority) {
+        (None, _) => Err("Mint account not decoded yet".to_string()),
+        (Some(_), Some(authority)) => Err(format!("Freeze authority {} can freeze holders", authority)),
+        (Some(_), None) => Ok("Freeze authority revoked".to_string()),
+    }
+}
+
+fn token_extensions(settings: &FilterSettings, token: &TokenOpportunity) -> Result<String, String> {
+    if token.risky_extensions.is_empty() {
+        return Ok("No risky Token-2022 extensions".to_string());
+    }
+
+    check(
+        !settings.reject_risky_extensions,
+        format!("Risky extensions: {}", token.risky_extensions.join(", ")),
+    )
+}
+
+/// Holder concentration, creator holding and funding clusters read 0 until the largest holders are analyzed
+fn holders_analyzed(token: &TokenOpportunity) -> Result<(), String> {
+    match token.freshness.holder_analysis {
+        Some(_) => Ok(()),
+        None => Err("Holders not analyzed yet".to_string()),
+    }
+}
+
+fn holder_concentration(settings: &FilterSettings, token: &TokenOpportunity) -> Result<String, String> {
+    holders_analyzed(token)?;
+    check(
+        token.top10_holder_percent <= settings.max_top10_holder_percent,
+        format!("Top 10 holders own {:.1}% (max {:.1}%)", token.top10_holder_percent, settings.max_top10_holder_percent),
+    )
+}
+
+fn creator_holding(settings: &FilterSettings, token: &TokenOpportunity) -> Result<String, String> {
+    holders_analyzed(token)?;
+    check(
+        token.creator_holding_percent <= settings.max_creator_holding_percent,
+        format!("Creator holds {:.1}% (max {:.1}%)", token.creator_holding_percent, settings.max_creator_holding_percent),
+    )
+}
+
+fn funding_clusters(settings: &FilterSettings, token: &TokenOpportunity) -> Result<String, String> {
+    holders_analyzed(token)?;
+    check(
+        token.same_funder_wallets <= settings.max_same_funder_wallets,
+        format!("{} top holders share a funder (max {})", token.same_funder_wallets, settings.max_same_funder_wallets),
+    )
+}
+
+fn bundles(settings: &FilterSettings, token: &TokenOpportunity) -> Result<String, String> {
+    // Only launches whose first trades could be inspected carry a report
+    if token.freshness.bundles.is_none() {
+        return Ok("No bundle report".to_string());
+    }
+
+    check(
+        token.bundle_percent <= settings.max_bundle_percent,
+        format!("Bundled wallets bought {:.1}% (max {:.1}%)", token.bundle_percent, settings.max_bundle_percent),
+    )
+}
+
+fn honeypot(settings: &FilterSettings, token: &TokenOpportunity) -> Result<String, String> {
+    if token.honeypot && settings.exclude_honeypots {
+        return Err("Sell simulation failed".to_string());
+    }
+
+    // Unknown until a round trip has been simulated; one that could not be is not trusted
+    if token.freshness.honeypot.is_none() {
+        return match &token.round_trip_error {
+            Some(error) if settings.exclude_honeypots => Err(format!("Round trip simulation failed: {}", error)),
+            _ => Ok("Round trip not simulated".to_string()),
+        };
+    }
+
+    check(
+        token.round_trip_loss_percent <= settings.max_round_trip_loss_percent,
+        format!("Round trip loses {:.1}% (max {:.1}%)", token.round_trip_loss_percent, settings.max_round_trip_loss_percent),
+    )
+}
+
+fn score(settings: &FilterSettings, token: &TokenOpportunity) -> Result<String, String> {
+    check(
+        token.score >= settings.min_score,
+        format!("Score {} (minimum {})", token.score, settings.min_score),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn passes(name: &str, settings: &FilterSettings, token: &TokenOpportunity) -> bool {
+        evaluate_expression(settings, &RuleExpr::Rule(name.to_string()), token).passed
+    }
+
+    fn expression(json: serde_json::Value) -> RuleExpr {
+        serde_json::from_value(json).unwrap()
+    }
+
+    fn custom(name: &str, field: &str, op: CompareOp, value: serde_json::Value) -> CustomRule {
+        CustomRule { name: name.to_string(), field: field.to_string(), op, value }
+    }
+
+    /// Enough liquidity but too few holders
+    fn thin_token() -> (FilterSettings, TokenOpportunity) {
+        let settings = FilterSettings { min_liquidity: 10.0, min_holders: 50, ..FilterSettings::default() };
+        let token = TokenOpportunity { liquidity: 20.0, holders: 10, ..TokenOpportunity::default() };
+        (settings, token)
+    }
+
+    #[test]
+    fn expressions_combine_every_rule_they_name() {
+        let (settings, token) = thin_token();
+        let evaluate = |json| evaluate_expression(&settings, &expression(json), &token);
+
+        let all = evaluate(serde_json::json!({ "all": [{ "rule": "holders" }, { "rule": "liquidity" }] }));
+        assert!(!all.passed);
+        // Nothing is short-circuited, so every rule is reported
+        assert_eq!(all.results.len(), 2);
+        assert_eq!(all.failures().map(|result| result.rule.as_str()).collect::<Vec<_>>(), ["holders"]);
+
+        let any = evaluate(serde_json::json!({ "any": [{ "rule": "holders" }, { "rule": "liquidity" }] }));
+        assert!(any.passed);
+        assert_eq!(any.results.len(), 2);
+
+        assert!(evaluate(serde_json::json!({ "not": { "rule": "holders" } })).passed);
+        assert!(evaluate(serde_json::json!({ "all": [{ "rule": "liquidity" }, { "not": { "rule": "holders" } }] })).passed);
+        assert!(!evaluate(serde_json::json!({ "rule": "missing" })).passed);
+    }
+
+    #[test]
+    fn the_default_expression_requires_every_rule() {
+        let (mut settings, token) = thin_token();
+        assert!(!evaluate(&settings, &token).passed);
+
+        settings.rules = Some(expression(serde_json::json!({ "rule": "liquidity" })));
+        let verdict = evaluate(&settings, &token);
+        assert!(verdict.passed);
+        assert_eq!(verdict.results.len(), 1);
+    }
+
+    #[test]
+    fn custom_rules_compare_token_fields() {
+        let (mut settings, mut token) = thin_token();
+        token.name = "Pepe Coin".to_string();
+        token.market_cap = 5_000.0;
+        token.metadata = Some(crate::enrichment::TokenMetadata {
+            twitter: Some("https://x.com/pepe".to_string()),
+            ..Default::default()
+        });
+        settings.custom_rules = vec![
+            custom("named_pepe", "name", CompareOp::Contains, serde_json::json!("PEPE")),
+            custom("big_enough", "market_cap", CompareOp::Gte, serde_json::json!(5_000)),
+            custom("tiny", "market_cap", CompareOp::Lt, serde_json::json!(1_000)),
+            custom("has_twitter", "metadata.twitter", CompareOp::Ne, serde_json::Value::Null),
+            custom("has_website", "metadata.website", CompareOp::Ne, serde_json::Value::Null),
+            custom("numeric_name", "name", CompareOp::Gt, serde_json::json!(1)),
+        ];
+
+        assert!(passes("named_pepe", &settings, &token));
+        assert!(passes("big_enough", &settings, &token));
+        assert!(!passes("tiny", &settings, &token));
+        assert!(passes("has_twitter", &settings, &token));
+        assert!(!passes("has_website", &settings, &token));
+        // Ordering needs numbers on both sides
+        assert!(!passes("numeric_name", &settings, &token));
+    }
+
+    #[test]
+    fn rule_definitions_are_checked() {
+        let mut settings = FilterSettings {
+            custom_rules: vec![custom("whale", "market_cap", CompareOp::Gt, serde_json::json!(1))],
+            rules: Some(expression(serde_json::json!({ "any": [{ "rule": "whale" }, { "not": { "rule": "liquidity" } }] }))),
+            ..FilterSettings::default()
+        };
+        assert!(check_rules(&settings).is_ok());
+
+        settings.rules = Some(expression(serde_json::json!({ "all": [{ "rule": "whale" }, { "rule": "unknown" }] })));
+        assert!(check_rules(&settings).unwrap_err().to_string().contains("Unknown rule: unknown"));
+
+        settings.rules = None;
+        settings.custom_rules.push(custom("liquidity", "liquidity", CompareOp::Gt, serde_json::json!(1)));
+        assert!(check_rules(&settings).unwrap_err().to_string().contains("Duplicate rule name: liquidity"));
+    }
+
+    #[test]
+    fn holder_rules_fail_until_holders_are_analyzed() {
+        let settings = FilterSettings::default();
+        let mut token = TokenOpportunity::default();
+        for rule in ["holder_concentration", "creator_holding", "funding_clusters"] {
+            assert!(!passes(rule, &settings, &token), "{} passed before analysis", rule);
+        }
+
+        token.freshness.holder_analysis = Some(chrono::Utc::now());
+        for rule in ["holder_concentration", "creator_holding", "funding_clusters"] {
+            assert!(passes(rule, &settings, &token), "{} failed after analysis", rule);
+        }
+    }
+
+    #[test]
+    fn honeypot_rule_fails_closed_when_the_simulation_errors() {
+        let mut settings = FilterSettings::default();
+        let token = TokenOpportunity {
+            round_trip_error: Some("Buy simulation failed".to_string()),
+            ..TokenOpportunity::default()
+        };
+        assert!(!passes("honeypot", &settings, &token));
+
+        settings.exclude_honeypots = false;
+        assert!(passes("honeypot", &settings, &token));
+        assert!(passes("honeypot", &FilterSettings::default(), &TokenOpportunity::default()));
+    }
+}
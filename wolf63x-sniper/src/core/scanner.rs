@@ -1,11 +1,61 @@
 use wasm_bindgen::prelude::*;
 use serde::{Serialize, Deserialize};
 use crate::console_log;
-use std::collections::HashMap;
+use crate::config::{ScannerSettings, DEFAULT_RPC_URL};
+use crate::events::{Event, EventBus, DEFAULT_EVENT_HISTORY};
+use crate::error::SniperError;
+use crate::enrichment::{self, Enricher, FieldFreshness, TokenEnrichment, TokenHolder, TokenMetadata};
+use crate::filters::TokenFilter;
+use crate::momentum::{Momentum, MomentumTracker, Trade, TradeSide};
+use crate::rules::FilterVerdict;
+use crate::scoring::FeatureScore;
+use crate::{pump_fun, pump_ws};
+use crate::reputation::{CreatorReputation, LaunchOutcome, ReputationStore};
+use crate::telemetry;
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use chrono::{DateTime, Utc};
+use futures::stream::{self, StreamExt};
+use metrics::counter;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+use solana_client::rpc_filter::{Memcmp, RpcFilterType};
+use solana_account_decoder::{UiAccountEncoding, UiDataSliceConfig};
+use solana_sdk::pubkey::Pubkey;
+use tokio::sync::watch;
+
+/// Size of an SPL token account
+const TOKEN_ACCOUNT_SIZE: u64 = 165;
+
+/// Offset of the balance in an SPL token account
+const TOKEN_ACCOUNT_AMOUNT_OFFSET: usize = 64;
+
+/// Lower bound for the scan interval so a bad config cannot hammer the APIs
+pub(crate) const MIN_SCAN_INTERVAL_MS: u32 = 500;
+
+/// Number of opportunities refreshed in parallel on each tick
+const REFRESH_CONCURRENCY: usize = 8;
+
+/// Opportunities refreshed per tick; the rest take their turn on later ticks
+const MAX_REFRESHES_PER_TICK: usize = 40;
+
+/// Refreshes missed in a row before an opportunity is considered dead
+const MAX_MISSED_REFRESHES: u32 = 5;
+
+/// Peak market cap (USD) a launch must reach before a collapse counts as a rug
+const RUG_MIN_PEAK_MARKET_CAP: f64 = 10_000.0;
+
+/// Wait before the first Pump.fun websocket reconnect, doubled up to the maximum
+const WS_RECONNECT_DELAY: Duration = Duration::from_secs(1);
+const WS_MAX_RECONNECT_DELAY: Duration = Duration::from_secs(30);
+
+/// Fraction of the peak market cap below which a launch counts as rugged
+const RUG_DRAWDOWN: f64 = 0.1;
 
 /// Represents a token opportunity
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct TokenOpportunity {
     pub address: String,
     pub symbol: String,
@@ -22,22 +72,95 @@ pub struct TokenOpportunity {
     pub sell_tax: u8,
     pub score: u8,
     pub source: String,
+    /// Contribution of each scoring feature to `score`
+    #[serde(default)]
+    pub score_breakdown: Vec<FeatureScore>,
+    #[serde(default)]
+    pub creator: String,
+    #[serde(default)]
+    pub creator_reputation: Option<CreatorReputation>,
+    #[serde(default)]
+    pub peak_market_cap: f64,
+    #[serde(default)]
+    pub bonding_curve_progress: f64,
+    #[serde(default = "Utc::now")]
+    pub last_updated: DateTime<Utc>,
+    #[serde(default)]
+    pub missed_refreshes: u32,
+    #[serde(default)]
+    pub bonding_curve_complete: bool,
+    #[serde(default)]
+    pub supply: u64,
+    #[serde(default)]
+    pub decimals: u8,
+    #[serde(default)]
+    pub mint_authority: Option<String>,
+    #[serde(default)]
+    pub freeze_authority: Option<String>,
+    #[serde(default)]
+    pub token_2022: bool,
+    #[serde(default)]
+    pub transfer_fee_bps: u16,
+    #[serde(default)]
+    pub risky_extensions: Vec<String>,
+    #[serde(default)]
+    pub metadata: Option<TokenMetadata>,
+    #[serde(default)]
+    pub top_holders: Vec<TokenHolder>,
+    #[serde(default)]
+    pub top10_holder_percent: f64,
+    #[serde(default)]
+    pub creator_holding_percent: f64,
+    #[serde(default)]
+    pub same_funder_wallets: u32,
+    #[serde(default)]
+    pub bundle_percent: f64,
+    #[serde(default)]
+    pub bundled_wallets: u32,
+    #[serde(default)]
+    pub honeypot: bool,
+    #[serde(default)]
+    pub round_trip_loss_percent: f64,
+    /// Why the last round trip could not be simulated, `None` once one is
+    #[serde(default)]
+    pub round_trip_error: Option<String>,
+    /// Near-duplicate launches seen shortly before this one
+    #[serde(default)]
+    pub similar_tokens: u32,
+    /// Trading activity from the trade stream, `None` until a trade is seen
+    #[serde(default)]
+    pub momentum: Option<Momentum>,
+    /// Strategy profile the token was routed to, `None` for the top-level settings
+    #[serde(default)]
+    pub profile: Option<String>,
+    /// Result of the last filter pass, explaining why the token was skipped
+    #[serde(default)]
+    pub verdict: Option<FilterVerdict>,
+    #[serde(default)]
+    pub freshness: FieldFreshness,
+    /// When the scanner first saw the token
+    #[serde(default)]
+    pub detected_at: DateTime<Utc>,
 }
 
 /// Scanner for finding token opportunities
 #[wasm_bindgen]
-use std::sync::{Arc, Mutex};
-
 pub struct Scanner {
     sources: Vec<String>,
-    min_liquidity: f64,
-    min_holders: u32,
-    max_buy_tax: u8,
-    max_sell_tax: u8,
-    require_lp_lock: bool,
-    min_score: u8,
+    /// Off in the configuration; `start` refuses to run
+    enabled: bool,
     active: bool,
     scan_interval_ms: u32,
+    max_opportunity_age_minutes: u32,
+    max_opportunities: usize,
+    rpc_url: String,
+    filter: Arc<Mutex<TokenFilter>>,
+    enricher: Arc<Enricher>,
+    reputation: Arc<Mutex<ReputationStore>>,
+    momentum: Arc<Mutex<MomentumTracker>>,
+    events: Arc<EventBus>,
+    shutdown: Option<watch::Sender<bool>>,
+    #[wasm_bindgen(skip)]
     pub opportunities: Arc<Mutex<Vec<TokenOpportunity>>>,
 }
 
@@ -45,44 +168,17 @@ pub struct Scanner {
 impl Scanner {
     #[wasm_bindgen(constructor)]
     pub fn new() -> Self {
-        Self {
-            sources: vec!["pump.fun".to_string()],
-            min_liquidity: 25.0,
-            min_holders: 50,
-            max_buy_tax: 10,
-            max_sell_tax: 10,
-            require_lp_lock: true,
-            min_score: 75,
-            active: false,
-            scan_interval_ms: 5000,
-            opportunities: Arc::new(Mutex::new(Vec::new())),
-        }
+        Self::with_rpc_url(DEFAULT_RPC_URL)
     }
 
     /// Start scanning for opportunities
     pub fn start(&mut self) -> Result<(), JsValue> {
-        if self.active {
-            return Ok(());
-        }
-        
-        console_log!("Starting token scanner...");
-        self.active = true;
-        
-        // In a real implementation, this would start a background task
-        // that periodically scans for new tokens
-        
-        Ok(())
+        Ok(self.start_scanning()?)
     }
     
     /// Stop scanning
     pub fn stop(&mut self) -> Result<(), JsValue> {
-        if !self.active {
-            return Ok(());
-        }
-        
-        console_log!("Stopping token scanner...");
-        self.active = false;
-        
+        self.stop_scanning();
         Ok(())
     }
     
@@ -94,34 +190,52 @@ impl Scanner {
             self.sources = sources;
         }
         
-        if let Some(min_liquidity) = config.min_liquidity {
-            self.min_liquidity = min_liquidity;
-        }
-        
-        if let Some(min_holders) = config.min_holders {
-            self.min_holders = min_holders;
-        }
-        
-        if let Some(max_buy_tax) = config.max_buy_tax {
-            self.max_buy_tax = max_buy_tax;
-        }
-        
-        if let Some(max_sell_tax) = config.max_sell_tax {
-            self.max_sell_tax = max_sell_tax;
+        // Thresholds live in the filter so the rules and the scanner never disagree
+        {
+            let filter = self.filter.lock().unwrap();
+            let mut settings = filter.settings_mut();
+            
+            if let Some(min_liquidity) = config.min_liquidity {
+                settings.min_liquidity = min_liquidity;
+            }
+            
+            if let Some(min_holders) = config.min_holders {
+                settings.min_holders = min_holders;
+            }
+            
+            if let Some(max_buy_tax) = config.max_buy_tax {
+                settings.max_buy_tax = max_buy_tax;
+            }
+            
+            if let Some(max_sell_tax) = config.max_sell_tax {
+                settings.max_sell_tax = max_sell_tax;
+            }
+            
+            if let Some(require_lp_lock) = config.require_lp_lock {
+                settings.require_lp_lock = require_lp_lock;
+            }
+            
+            if let Some(min_score) = config.min_score {
+                settings.min_score = min_score;
+            }
         }
         
-        if let Some(require_lp_lock) = config.require_lp_lock {
-            self.require_lp_lock = require_lp_lock;
+        if let Some(max_opportunity_age_minutes) = config.max_opportunity_age_minutes {
+            self.max_opportunity_age_minutes = max_opportunity_age_minutes;
         }
         
-        if let Some(min_score) = config.min_score {
-            self.min_score = min_score;
-        }
+        let interval_changed = config.scan_interval_ms
+            .is_some_and(|interval| interval != self.scan_interval_ms);
         
         if let Some(scan_interval_ms) = config.scan_interval_ms {
             self.scan_interval_ms = scan_interval_ms;
         }
         
+        // Restart the running loop so the new interval takes effect
+        if interval_changed {
+            self.restart_scan_loop();
+        }
+        
         console_log!("Scanner configuration updated");
         
         Ok(())
@@ -162,60 +276,561 @@ struct ScannerConfig {
     require_lp_lock: Option<bool>,
     min_score: Option<u8>,
     scan_interval_ms: Option<u32>,
+    max_opportunity_age_minutes: Option<u32>,
 }
 
-mod pump_ws;
-
 // Native Rust implementation (not exposed to WASM)
 impl Scanner {
+    /// Scanner querying the RPC endpoint from the configuration
+    pub fn with_rpc_url(rpc_url: &str) -> Self {
+        Self {
+            sources: vec!["pump.fun".to_string()],
+            enabled: true,
+            active: false,
+            scan_interval_ms: 5000,
+            max_opportunity_age_minutes: 60,
+            max_opportunities: 1000,
+            rpc_url: rpc_url.to_string(),
+            filter: Arc::new(Mutex::new(TokenFilter::new())),
+            enricher: Arc::new(Enricher::new(rpc_url.to_string())),
+            reputation: Arc::new(Mutex::new(ReputationStore::new())),
+            momentum: Arc::new(Mutex::new(MomentumTracker::new())),
+            events: Arc::new(EventBus::new(DEFAULT_EVENT_HISTORY)),
+            shutdown: None,
+            opportunities: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+    
+    /// Start the scan loop, unless the configuration disables the scanner
+    pub fn start_scanning(&mut self) -> Result<(), SniperError> {
+        if self.active {
+            return Ok(());
+        }
+        if !self.enabled {
+            return Err(SniperError::InvalidRequest("Scanner is disabled in the configuration".to_string()));
+        }
+        
+        console_log!("Starting token scanner...");
+        self.active = true;
+        self.spawn_scan_loop();
+        
+        Ok(())
+    }
+    
+    /// Stop the scan loop if it is running
+    pub fn stop_scanning(&mut self) {
+        if !self.active {
+            return;
+        }
+        
+        console_log!("Stopping token scanner...");
+        self.active = false;
+        
+        if let Some(shutdown) = self.shutdown.take() {
+            let _ = shutdown.send(true);
+        }
+    }
+    
+    /// Use a shared (usually persisted) creator reputation store
+    pub fn set_reputation_store(&mut self, reputation: Arc<Mutex<ReputationStore>>) {
+        self.reputation = reputation;
+    }
+    
+    /// Filter shared with the rest of the app, so config changes apply to scanning
+    ///
+    /// Takes effect the next time the scan loop starts.
+    pub fn set_filter(&mut self, filter: Arc<Mutex<TokenFilter>>) {
+        self.filter = filter;
+    }
+    
+    /// Bus that new opportunities and verdict changes are published on
+    ///
+    /// Takes effect the next time the scan loop starts.
+    pub fn set_event_bus(&mut self, events: Arc<EventBus>) {
+        self.events = events;
+    }
+    
+    /// Wallet used as fee payer when simulating buy/sell round trips
+    ///
+    /// Applies to running scans as well.
+    pub fn set_simulation_payer(&mut self, payer: &str) -> anyhow::Result<()> {
+        self.enricher.set_simulation_payer(Some(Pubkey::from_str(payer)?));
+        Ok(())
+    }
+    
+    fn rebuild_enricher(&mut self) {
+        let enricher = Enricher::new(self.rpc_url.clone());
+        enricher.set_simulation_payer(self.enricher.simulation_payer());
+        self.enricher = Arc::new(enricher);
+    }
+    
+    /// Apply the scanner section of a loaded config file
+    ///
+    /// A running scan loop is restarted when the interval or RPC endpoint
+    /// changes, and stopped when the scanner is disabled; the websocket keeps
+    /// the opportunity cap it started with.
+    pub fn apply_settings(&mut self, settings: &ScannerSettings, rpc_url: &str) {
+        self.enabled = settings.enabled;
+        if !self.enabled && self.active {
+            self.stop_scanning();
+        }
+        let mut restart = settings.interval_ms != self.scan_interval_ms;
+        self.scan_interval_ms = settings.interval_ms;
+        self.max_opportunities = settings.max_pairs;
+        if rpc_url != self.rpc_url {
+            self.rpc_url = rpc_url.to_string();
+            self.rebuild_enricher();
+            restart = true;
+        }
+        if restart {
+            self.restart_scan_loop();
+        }
+    }
+    
+    /// Restart the scan loop, if running, so it picks up new settings
+    fn restart_scan_loop(&mut self) {
+        if !self.active {
+            return;
+        }
+        if let Some(shutdown) = self.shutdown.take() {
+            let _ = shutdown.send(true);
+        }
+        self.spawn_scan_loop();
+    }
+    
+    /// Creator reputation store used to track launches
+    pub fn reputation_store(&self) -> Arc<Mutex<ReputationStore>> {
+        self.reputation.clone()
+    }
+    
     /// Fetch new tokens from pump.fun
-    async fn fetch_from_pump_fun(&self) -> anyhow::Result<Vec<TokenOpportunity>> {
-        // For now, use websocket for real-time updates
-        // This method could trigger the websocket listener if not already running
-        Ok(vec![])
+    async fn fetch_from_pump_fun(client: &reqwest::Client) -> anyhow::Result<Vec<TokenOpportunity>> {
+        // The websocket delivers launches in real time; this catches anything it missed
+        let coins = pump_fun::fetch_latest_coins(client, 50).await?;
+        
+        Ok(coins.iter().map(|coin| {
+            let mut token = TokenOpportunity {
+                address: coin.mint.clone(),
+                symbol: coin.symbol.clone(),
+                name: coin.name.clone(),
+                creator: coin.creator.clone(),
+                created_at: DateTime::<Utc>::from_timestamp(coin.created_timestamp / 1000, 0)
+                    .unwrap_or_else(Utc::now),
+                source: "pump.fun".to_string(),
+                last_updated: Utc::now(),
+                ..TokenOpportunity::default()
+            };
+            apply_coin_snapshot(&mut token, coin);
+            token
+        }).collect())
+    }
+
+    /// Spawn the background task that periodically refreshes opportunities
+    fn spawn_scan_loop(&mut self) {
+        let (shutdown_tx, mut shutdown_rx) = watch::channel(false);
+        self.shutdown = Some(shutdown_tx);
+        
+        let context = ScanContext {
+            client: enrichment::http_client(),
+            rpc: Arc::new(telemetry::rpc_client(self.rpc_url.clone())),
+            enricher: self.enricher.clone(),
+            reputation: self.reputation.clone(),
+            momentum: self.momentum.clone(),
+            opportunities: self.opportunities.clone(),
+            filter: self.filter.clone(),
+            events: self.events.clone(),
+            max_age: chrono::Duration::minutes(self.max_opportunity_age_minutes as i64),
+            refresh_cursor: 0,
+        };
+        let interval = Duration::from_millis(self.scan_interval_ms.max(MIN_SCAN_INTERVAL_MS) as u64);
+        
+        tokio::spawn(async move {
+            let mut context = context;
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            
+            loop {
+                tokio::select! {
+                    _ = ticker.tick() => {
+                        Self::scan_once(&mut context).await;
+                    }
+                    changed = shutdown_rx.changed() => {
+                        if changed.is_err() || *shutdown_rx.borrow() {
+                            break;
+                        }
+                    }
+                }
+            }
+            
+            log::info!("Token scanner loop stopped");
+        });
+    }
+    
+    /// Run a single scan: pick up missed launches, refresh live data, re-score and prune
+    async fn scan_once(context: &mut ScanContext) {
+        let ScanContext { client, rpc, enricher, reputation, momentum, opportunities, filter, events, max_age, refresh_cursor } = context;
+        let max_age = *max_age;
+        
+        let discovered = match Self::fetch_from_pump_fun(client).await {
+            Ok(tokens) => tokens,
+            Err(e) => {
+                log::warn!("Failed to fetch latest Pump.fun coins: {}", e);
+                Vec::new()
+            }
+        };
+        
+        // Snapshot the tracked mints so the lock is not held across network calls
+        let tracked: Vec<(String, String)> = {
+            let mut filter = filter.lock().unwrap();
+            let mut opps = opportunities.lock().unwrap();
+            for mut token in discovered {
+                if !opps.iter().any(|t| t.address == token.address) {
+                    detect(&mut token);
+                    track_launch(reputation, &mut token);
+                    score_token(&mut filter, &mut token, events);
+                    events.publish(Event::NewOpportunity(Box::new(token.clone())));
+                    opps.insert(0, token);
+                }
+            }
+            opps.iter().map(|t| (t.address.clone(), t.creator.clone())).collect()
+        };
+        let mints = refresh_window(&tracked, refresh_cursor, MAX_REFRESHES_PER_TICK);
+        let attempted: HashSet<String> = mints.iter().map(|(mint, _)| mint.clone()).collect();
+        
+        let results: Vec<(String, anyhow::Result<LiveData>)> = stream::iter(mints)
+            .map(|(mint, creator)| {
+                let client = client.clone();
+                let rpc = rpc.clone();
+                let enricher = enricher.clone();
+                async move {
+                    let creator = Some(creator.as_str()).filter(|c| !c.is_empty());
+                    let (live, enrichment) = tokio::join!(
+                        Self::fetch_live_data(&client, &rpc, &mint),
+                        enricher.enrich(&mint, creator),
+                    );
+                    let live = live.map(|mut live| {
+                        match enrichment {
+                            Ok(enrichment) => live.enrichment = Some(enrichment),
+                            Err(e) => log::debug!("Failed to enrich {}: {}", mint, e),
+                        }
+                        live
+                    });
+                    (mint, live)
+                }
+            })
+            .buffer_unordered(REFRESH_CONCURRENCY)
+            .collect()
+            .await;
+        
+        let refreshed: HashMap<String, LiveData> = results.into_iter()
+            .filter_map(|(mint, live)| match live {
+                Ok(live) => Some((mint, live)),
+                Err(e) => {
+                    log::debug!("Failed to refresh {}: {}", mint, e);
+                    None
+                }
+            })
+            .collect();
+        
+        let mut filter = filter.lock().unwrap();
+        let mut opps = opportunities.lock().unwrap();
+        let mut reputation = reputation.lock().unwrap();
+        let mut momentum = momentum.lock().unwrap();
+        let now = Utc::now();
+        let momentum_window = chrono::Duration::minutes(filter.settings().momentum_window_minutes as i64);
+        let early_period = chrono::Duration::minutes(filter.settings().early_buyer_minutes as i64);
+        
+        for token in opps.iter_mut() {
+            match refreshed.get(&token.address) {
+                Some(live) => {
+                    apply_coin_snapshot(token, &live.coin);
+                    if let Some(holders) = live.holders {
+                        token.holders = holders;
+                    }
+                    if let Some(enrichment) = &live.enrichment {
+                        enrichment.apply_to(token);
+                    }
+                    token.last_updated = now;
+                    token.missed_refreshes = 0;
+                }
+                None if attempted.contains(&token.address) => token.missed_refreshes += 1,
+                None => {}
+            }
+            
+            token.peak_market_cap = token.peak_market_cap.max(token.market_cap);
+            if !token.creator.is_empty() {
+                if let Some(outcome) = launch_outcome(token) {
+                    reputation.record_outcome(&token.creator, &token.address, outcome);
+                }
+                token.creator_reputation = reputation.summary(&token.creator);
+            }
+            
+            token.momentum = momentum.momentum(&token.address, token.created_at, momentum_window, early_period);
+            score_token(&mut filter, token, events);
+        }
+        
+        // Launches that aged out without migrating or rugging were abandoned
+        for token in opps.iter().filter(|t| now - t.created_at > max_age) {
+            if !token.creator.is_empty() {
+                reputation.record_outcome(&token.creator, &token.address, LaunchOutcome::Abandoned);
+            }
+        }
+        
+        let before = opps.len();
+        opps.retain(|token| {
+            now - token.created_at <= max_age && token.missed_refreshes < MAX_MISSED_REFRESHES
+        });
+        
+        if opps.len() != before {
+            log::debug!("Dropped {} stale opportunities", before - opps.len());
+            enricher.retain(|mint| opps.iter().any(|t| t.address == mint));
+            momentum.retain(|mint| opps.iter().any(|t| t.address == mint));
+        }
+    }
+    
+    /// Fetch live market data and the holder count for a mint
+    async fn fetch_live_data(client: &reqwest::Client, rpc: &RpcClient, mint: &str) -> anyhow::Result<LiveData> {
+        let coin = pump_fun::fetch_coin(client, mint).await?;
+        let holders = match Self::fetch_holder_count(rpc, mint).await {
+            Ok(count) => Some(count),
+            Err(e) => {
+                log::debug!("Failed to count holders for {}: {}", mint, e);
+                None
+            }
+        };
+        
+        Ok(LiveData { coin, holders, enrichment: None })
+    }
+    
+    /// Count token accounts holding a mint, downloading only their balances
+    ///
+    /// Emptied accounts stay open until closed, so only non-zero balances count.
+    async fn fetch_holder_count(rpc: &RpcClient, mint: &str) -> anyhow::Result<u32> {
+        let mint = Pubkey::from_str(mint)?;
+        let token_program = Pubkey::from_str(pump_fun::TOKEN_PROGRAM_ID)?;
+        
+        let config = RpcProgramAccountsConfig {
+            filters: Some(vec![
+                RpcFilterType::DataSize(TOKEN_ACCOUNT_SIZE),
+                RpcFilterType::Memcmp(Memcmp::new_raw_bytes(0, mint.to_bytes().to_vec())),
+            ]),
+            account_config: RpcAccountInfoConfig {
+                encoding: Some(UiAccountEncoding::Base64),
+                data_slice: Some(UiDataSliceConfig { offset: TOKEN_ACCOUNT_AMOUNT_OFFSET, length: 8 }),
+                ..RpcAccountInfoConfig::default()
+            },
+            ..RpcProgramAccountsConfig::default()
+        };
+        
+        let accounts = rpc.get_program_accounts_with_config(&token_program, config).await?;
+        let holders = accounts.iter().filter(|(_, account)| account.data.iter().any(|byte| *byte != 0)).count();
+        Ok(holders as u32)
     }
 
     /// Start websocket listener for Pump.fun new pairs
     pub async fn start_pump_fun_ws(&mut self) {
+        if !self.enabled {
+            log::info!("Scanner is disabled in the configuration, not listening for new pairs");
+            return;
+        }
         let opps_arc = self.opportunities.clone();
+        let enricher = self.enricher.clone();
+        let reputation = self.reputation.clone();
+        let filter = self.filter.clone();
+        let momentum = self.momentum.clone();
+        let trade_momentum = self.momentum.clone();
+        let events = self.events.clone();
+        let max_opportunities = self.max_opportunities;
+        let mut on_token = move |evt: pump_ws::NewTokenEvent| {
+            // The creator's initial buy is the first trade on the curve
+            if evt.sol_amount > 0.0 {
+                momentum.lock().unwrap().record(Trade {
+                    mint: evt.mint.clone(),
+                    trader: evt.trader_public_key.clone(),
+                    side: TradeSide::Buy,
+                    sol_amount: evt.sol_amount,
+                    at: Utc::now(),
+                });
+            }
+            
+            // Same lock order as the scan loop: filter before opportunities
+            let mut token_filter = filter.lock().unwrap();
+            let mut opps = opps_arc.lock().unwrap();
+            // Convert NewTokenEvent to TokenOpportunity, enrichment fills in the rest
+            if !opps.iter().any(|t| t.address == evt.mint) {
+                let mut token = TokenOpportunity {
+                    address: evt.mint.clone(),
+                    symbol: evt.symbol.clone(),
+                    name: evt.name.clone(),
+                    creator: evt.trader_public_key.clone(),
+                    price: evt.price_usd.parse().unwrap_or(0.0),
+                    liquidity: evt.liquidity.parse().unwrap_or(0.0),
+                    created_at: chrono::Utc::now(),
+                    source: "pump.fun".to_string(),
+                    last_updated: chrono::Utc::now(),
+                    ..TokenOpportunity::default()
+                };
+                detect(&mut token);
+                track_launch(&reputation, &mut token);
+                score_token(&mut token_filter, &mut token, &events);
+                events.publish(Event::NewOpportunity(Box::new(token.clone())));
+                opps.insert(0, token);
+                if opps.len() > max_opportunities { opps.truncate(max_opportunities); }
+                
+                Self::spawn_enrichment(enricher.clone(), filter.clone(), opps_arc.clone(), events.clone(), evt.mint.clone(), evt.trader_public_key.clone());
+            }
+        };
+        let mut on_trade = move |trade: pump_ws::TradeEvent| {
+            let side = match trade.tx_type.as_str() {
+                "buy" => TradeSide::Buy,
+                _ => TradeSide::Sell,
+            };
+            trade_momentum.lock().unwrap().record(Trade {
+                mint: trade.mint,
+                trader: trade.trader,
+                side,
+                sol_amount: trade.sol_amount,
+                at: Utc::now(),
+            });
+        };
+        
+        // The feed drops now and then; reconnect with backoff for as long as the process runs
         tokio::spawn(async move {
-            pump_ws::listen_new_pump_fun_pairs(move |evt| {
-                let mut opps = opps_arc.lock().unwrap();
-                // Convert NewTokenEvent to TokenOpportunity (minimal fields for pairs menu)
-                if !opps.iter().any(|t| t.address == evt.mint) {
-                    let token = TokenOpportunity {
-                        address: evt.mint.clone(),
-                        symbol: evt.symbol.clone(),
-                        name: evt.name.clone(),
-                        price: evt.priceUsd.parse().unwrap_or(0.0),
-                        market_cap: 0.0,
-                        volume_24h: 0.0,
-                        liquidity: evt.liquidity.parse().unwrap_or(0.0),
-                        holders: 0,
-                        created_at: chrono::Utc::now(),
-                        lp_locked: false,
-                        lp_lock_end: None,
-                        buy_tax: 0,
-                        sell_tax: 0,
-                        score: 0,
-                        source: "pump.fun".to_string(),
-                    };
-                    opps.insert(0, token);
-                    if opps.len() > 1000 { opps.truncate(1000); }
+            let mut delay = WS_RECONNECT_DELAY;
+            let mut trade_subscriptions = pump_ws::TradeSubscriptions::default();
+            loop {
+                match pump_ws::listen_new_pump_fun_pairs(&mut on_token, &mut on_trade, &mut trade_subscriptions).await {
+                    Ok(()) => {
+                        delay = WS_RECONNECT_DELAY;
+                        log::warn!("Pump.fun websocket closed, reconnecting in {:?}", delay);
+                    }
+                    Err(e) => log::warn!("{}, reconnecting in {:?}", e, delay),
                 }
-            }).await;
+                tokio::time::sleep(delay).await;
+                delay = (delay * 2).min(WS_MAX_RECONNECT_DELAY);
+                counter!(telemetry::WEBSOCKET_RECONNECTS_TOTAL).increment(1);
+            }
+        });
+    }
+    
+    /// Enrich a newly seen mint in the background and write the result back
+    fn spawn_enrichment(
+        enricher: Arc<Enricher>,
+        filter: Arc<Mutex<TokenFilter>>,
+        opportunities: Arc<Mutex<Vec<TokenOpportunity>>>,
+        events: Arc<EventBus>,
+        mint: String,
+        creator: String,
+    ) {
+        tokio::spawn(async move {
+            let creator = Some(creator.as_str()).filter(|c| !c.is_empty());
+            match enricher.enrich(&mint, creator).await {
+                Ok(enrichment) => {
+                    let mut filter = filter.lock().unwrap();
+                    let mut opps = opportunities.lock().unwrap();
+                    if let Some(token) = opps.iter_mut().find(|t| t.address == mint) {
+                        enrichment.apply_to(token);
+                        score_token(&mut filter, token, &events);
+                    }
+                }
+                Err(e) => log::debug!("Failed to enrich {}: {}", mint, e),
+            }
+        });
+    }
+}
+
+/// Handles the scan loop works with
+struct ScanContext {
+    client: reqwest::Client,
+    rpc: Arc<RpcClient>,
+    enricher: Arc<Enricher>,
+    reputation: Arc<Mutex<ReputationStore>>,
+    momentum: Arc<Mutex<MomentumTracker>>,
+    opportunities: Arc<Mutex<Vec<TokenOpportunity>>>,
+    filter: Arc<Mutex<TokenFilter>>,
+    events: Arc<EventBus>,
+    max_age: chrono::Duration,
+    /// Position in the opportunity list the next tick's refreshes start at
+    refresh_cursor: usize,
+}
+
+/// Live data fetched for a tracked opportunity on each scan
+struct LiveData {
+    coin: pump_fun::PumpCoin,
+    holders: Option<u32>,
+    enrichment: Option<TokenEnrichment>,
+}
+
+/// Up to `limit` items starting at `cursor`, wrapping around, moving the cursor past them
+fn refresh_window<T: Clone>(items: &[T], cursor: &mut usize, limit: usize) -> Vec<T> {
+    if items.len() <= limit {
+        *cursor = 0;
+        return items.to_vec();
+    }
+    let start = *cursor % items.len();
+    *cursor = (start + limit) % items.len();
+    items.iter().cycle().skip(start).take(limit).cloned().collect()
+}
+
+/// Stamp a token seen for the first time and count it against its source
+fn detect(token: &mut TokenOpportunity) {
+    token.detected_at = Utc::now();
+    counter!(telemetry::OPPORTUNITIES_TOTAL, "source" => token.source.clone()).increment(1);
+}
+
+/// Record a newly seen launch against its creator and attach their history
+fn track_launch(reputation: &Mutex<ReputationStore>, token: &mut TokenOpportunity) {
+    if token.creator.is_empty() {
+        return;
+    }
+    
+    let mut reputation = reputation.lock().unwrap();
+    reputation.record_launch(&token.creator, &token.address, &token.symbol, token.created_at);
+    token.creator_reputation = reputation.summary(&token.creator);
+}
+
+/// Score a token and record why the filters accept or reject it
+fn score_token(filter: &mut TokenFilter, token: &mut TokenOpportunity, events: &EventBus) {
+    filter.track_similarity(token);
+    filter.route(token);
+    let breakdown = filter.score_breakdown(token);
+    token.score = breakdown.score;
+    token.score_breakdown = breakdown.features;
+    
+    // A new token's first verdict goes out with its opportunity event
+    let verdict = filter.evaluate(token);
+    let flipped = token.verdict.as_ref().map(|previous| previous.passed != verdict.passed);
+    if flipped == Some(true) {
+        events.publish(Event::FilterVerdict {
+            token_address: token.address.clone(),
+            verdict: verdict.clone(),
         });
     }
+    if flipped != Some(false) {
+        telemetry::record_verdict(&verdict);
+    }
+    token.verdict = Some(verdict);
+}
+
+/// Settle a launch once it migrates off the curve or collapses from its peak
+fn launch_outcome(token: &TokenOpportunity) -> Option<LaunchOutcome> {
+    if token.bonding_curve_complete {
+        return Some(LaunchOutcome::Migrated);
+    }
     
-    /// Apply filters to token opportunities
-    fn apply_filters(&self, opportunities: Vec<TokenOpportunity>) -> Vec<TokenOpportunity> {
-        opportunities.into_iter().filter(|op| {
-            op.liquidity >= self.min_liquidity &&
-            op.holders >= self.min_holders &&
-            op.buy_tax <= self.max_buy_tax &&
-            op.sell_tax <= self.max_sell_tax &&
-            (!self.require_lp_lock || op.lp_locked) &&
-            op.score >= self.min_score
-        }).collect()
+    if token.peak_market_cap >= RUG_MIN_PEAK_MARKET_CAP
+        && token.market_cap < token.peak_market_cap * RUG_DRAWDOWN
+    {
+        return Some(LaunchOutcome::Rugged);
     }
+    
+    None
+}
+
+/// Copy the market fields of a Pump.fun coin snapshot onto an opportunity
+fn apply_coin_snapshot(token: &mut TokenOpportunity, coin: &pump_fun::PumpCoin) {
+    token.price = coin.price_usd();
+    token.market_cap = coin.usd_market_cap;
+    token.liquidity = coin.liquidity_sol();
+    token.bonding_curve_progress = coin.curve_progress();
 }
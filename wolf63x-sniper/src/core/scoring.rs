@@ -0,0 +1,238 @@
+use serde::{Serialize, Deserialize};
+use crate::scanner::TokenOpportunity;
+
+/// Token signal a scoring feature reads
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Signal {
+    Liquidity,
+    Holders,
+    /// Average of buy and sell tax, in percent
+    AverageTax,
+    /// 1 when the LP is locked, 0 otherwise
+    LpLocked,
+    LpLockDays,
+    AgeMinutes,
+    /// 1 when a mint authority remains, unknown until the mint is decoded
+    MintAuthority,
+    FreezeAuthority,
+    RiskyExtensions,
+    Top10HolderPercent,
+    CreatorHoldingPercent,
+    SameFunderWallets,
+    BundlePercent,
+    RoundTripLossPercent,
+    CreatorRugs,
+    CreatorMigrations,
+    /// Near-duplicate launches seen shortly before this one
+    SimilarTokens,
+    MarketCap,
+    Volume24h,
+    BondingCurveProgress,
+    SolInflowPerMinute,
+    BuySellRatio,
+    UniqueBuyers,
+}
+
+impl Signal {
+    /// Current value of the signal, `None` while it is unknown
+    pub fn value(&self, token: &TokenOpportunity) -> Option<f64> {
+        let flag = |set: bool| if set { 1.0 } else { 0.0 };
+        let mint_decoded = token.freshness.mint.is_some();
+
+        match self {
+            Signal::Liquidity => Some(token.liquidity),
+            Signal::Holders => Some(token.holders as f64),
+            Signal::AverageTax => Some((token.buy_tax as f64 + token.sell_tax as f64) / 2.0),
+            Signal::LpLocked => Some(flag(token.lp_locked)),
+            Signal::LpLockDays => token.lp_lock_end
+                .filter(|_| token.lp_locked)
+                .map(|end| (end - chrono::Utc::now()).num_days() as f64),
+            Signal::AgeMinutes => Some((chrono::Utc::now() - token.created_at).num_minutes() as f64),
+            Signal::MintAuthority => mint_decoded.then(|| flag(token.mint_authority.is_some())),
+            Signal::FreezeAuthority => mint_decoded.then(|| flag(token.freeze_authority.is_some())),
+            Signal::RiskyExtensions => mint_decoded.then_some(token.risky_extensions.len() as f64),
+            Signal::Top10HolderPercent => token.freshness.holder_analysis.map(|_| token.top10_holder_percent),
+            Signal::CreatorHoldingPercent => token.freshness.holder_analysis.map(|_| token.creator_holding_percent),
+            Signal::SameFunderWallets => token.freshness.holder_analysis.map(|_| token.same_funder_wallets as f64),
+            Signal::BundlePercent => token.freshness.bundles.map(|_| token.bundle_percent),
+            Signal::RoundTripLossPercent => token.freshness.honeypot.map(|_| token.round_trip_loss_percent),
+            Signal::CreatorRugs => token.creator_reputation.as_ref().map(|r| r.rugged as f64),
+            Signal::CreatorMigrations => token.creator_reputation.as_ref().map(|r| r.migrated as f64),
+            Signal::SimilarTokens => Some(token.similar_tokens as f64),
+            Signal::MarketCap => Some(token.market_cap),
+            Signal::Volume24h => Some(token.volume_24h),
+            Signal::BondingCurveProgress => Some(token.bonding_curve_progress),
+            Signal::SolInflowPerMinute => token.momentum.as_ref().map(|m| m.sol_inflow_per_minute),
+            Signal::BuySellRatio => token.momentum.as_ref().map(|m| m.buy_sell_ratio),
+            Signal::UniqueBuyers => token.momentum.as_ref().map(|m| m.unique_buyers as f64),
+        }
+    }
+}
+
+/// Points awarded while a signal stays below `below`; the band without a bound catches the rest
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScoreBand {
+    #[serde(default)]
+    pub below: Option<f64>,
+    pub points: f64,
+}
+
+/// A weighted, piecewise-constant contribution to the score
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScoreFeature {
+    pub name: String,
+    pub signal: Signal,
+    #[serde(default = "default_weight")]
+    pub weight: f64,
+    /// Checked in order; the first matching band wins
+    pub bands: Vec<ScoreBand>,
+}
+
+fn default_weight() -> f64 {
+    1.0
+}
+
+impl ScoreFeature {
+    fn new(name: &str, signal: Signal, bands: &[(Option<f64>, f64)]) -> Self {
+        Self {
+            name: name.to_string(),
+            signal,
+            weight: default_weight(),
+            bands: bands.iter().map(|&(below, points)| ScoreBand { below, points }).collect(),
+        }
+    }
+
+    /// Weighted points for a signal value
+    pub fn points(&self, value: f64) -> f64 {
+        self.bands.iter()
+            .find(|band| band.below.is_none_or(|below| value < below))
+            .map_or(0.0, |band| band.points * self.weight)
+    }
+}
+
+/// Contribution of one feature to a token's score
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeatureScore {
+    pub feature: String,
+    /// Signal value, `None` when it was unknown and skipped
+    pub value: Option<f64>,
+    pub points: f64,
+}
+
+/// Final score with the contribution of each feature
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ScoreBreakdown {
+    pub score: u8,
+    pub features: Vec<FeatureScore>,
+}
+
+/// Scoring model: a base score adjusted by every feature, clamped to 0-100
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScoringModel {
+    #[serde(default = "default_base")]
+    pub base: f64,
+    pub features: Vec<ScoreFeature>,
+}
+
+fn default_base() -> f64 {
+    100.0
+}
+
+impl Default for ScoringModel {
+    fn default() -> Self {
+        Self {
+            base: default_base(),
+            features: vec![
+                ScoreFeature::new("liquidity", Signal::Liquidity, &[(Some(25.0), -20.0), (Some(50.0), -10.0), (None, 0.0)]),
+                ScoreFeature::new("holders", Signal::Holders, &[(Some(50.0), -15.0), (Some(100.0), -7.0), (None, 0.0)]),
+                ScoreFeature::new("taxes", Signal::AverageTax, &[(Some(1.0), 0.0), (Some(5.0), -5.0), (Some(10.0), -15.0), (None, -25.0)]),
+                ScoreFeature::new("lp_locked", Signal::LpLocked, &[(Some(0.5), -30.0), (None, 0.0)]),
+                ScoreFeature::new("lp_lock_days", Signal::LpLockDays, &[(Some(30.0), -25.0), (Some(90.0), -15.0), (Some(180.0), -5.0), (None, 0.0)]),
+                ScoreFeature::new("token_age", Signal::AgeMinutes, &[(Some(3.0), -10.0), (None, 0.0)]),
+                ScoreFeature::new("mint_authority", Signal::MintAuthority, &[(Some(0.5), 0.0), (None, -20.0)]),
+                ScoreFeature::new("freeze_authority", Signal::FreezeAuthority, &[(Some(0.5), 0.0), (None, -15.0)]),
+                ScoreFeature::new("token_extensions", Signal::RiskyExtensions, &[(Some(0.5), 0.0), (None, -10.0)]),
+                ScoreFeature::new("holder_concentration", Signal::Top10HolderPercent, &[(Some(30.0), 0.0), (None, -15.0)]),
+                ScoreFeature::new("creator_holding", Signal::CreatorHoldingPercent, &[(Some(10.0), 0.0), (None, -15.0)]),
+                ScoreFeature::new("funding_clusters", Signal::SameFunderWallets, &[(Some(4.0), 0.0), (None, -10.0)]),
+                ScoreFeature::new("bundles", Signal::BundlePercent, &[(Some(20.0), 0.0), (None, -15.0)]),
+                ScoreFeature::new("round_trip_loss", Signal::RoundTripLossPercent, &[(Some(10.0), 0.0), (None, -20.0)]),
+                ScoreFeature::new("creator_rugs", Signal::CreatorRugs, &[(Some(1.0), 0.0), (Some(2.0), -15.0), (None, -30.0)]),
+                ScoreFeature::new("curve_progress", Signal::BondingCurveProgress, &[(Some(2.0), -5.0), (Some(85.0), 0.0), (None, -10.0)]),
+                ScoreFeature::new("sol_outflow", Signal::SolInflowPerMinute, &[(Some(0.0), -10.0), (None, 0.0)]),
+                ScoreFeature::new("sell_pressure", Signal::BuySellRatio, &[(Some(1.0), -10.0), (None, 0.0)]),
+                ScoreFeature::new("early_buyers", Signal::UniqueBuyers, &[(Some(5.0), -10.0), (Some(10.0), -5.0), (None, 0.0)]),
+                ScoreFeature::new("creator_migrations", Signal::CreatorMigrations, &[(Some(1.0), 0.0), (Some(2.0), 5.0), (Some(3.0), 10.0), (None, 15.0)]),
+            ],
+        }
+    }
+}
+
+impl ScoringModel {
+    /// Score a token, skipping features whose signal is still unknown
+    pub fn score(&self, token: &TokenOpportunity) -> ScoreBreakdown {
+        let features: Vec<FeatureScore> = self.features.iter().map(|feature| {
+            let value = feature.signal.value(token);
+            FeatureScore {
+                feature: feature.name.clone(),
+                value,
+                points: value.map_or(0.0, |value| feature.points(value)),
+            }
+        }).collect();
+
+        let total = self.base + features.iter().map(|feature| feature.points).sum::<f64>();
+
+        ScoreBreakdown {
+            score: total.round().clamp(0.0, 100.0) as u8,
+            features,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn liquidity_feature(weight: f64, bands: &[(Option<f64>, f64)]) -> ScoreFeature {
+        ScoreFeature { weight, ..ScoreFeature::new("liquidity", Signal::Liquidity, bands) }
+    }
+
+    #[test]
+    fn the_first_band_the_value_is_below_wins() {
+        let feature = liquidity_feature(2.0, &[(Some(10.0), -5.0), (Some(20.0), -2.0), (None, 0.0)]);
+        assert_eq!(feature.points(5.0), -10.0);
+        assert_eq!(feature.points(10.0), -4.0);
+        assert_eq!(feature.points(25.0), 0.0);
+
+        // Without a catch-all band, values above every bound score nothing
+        assert_eq!(liquidity_feature(1.0, &[(Some(10.0), -5.0)]).points(50.0), 0.0);
+    }
+
+    #[test]
+    fn unknown_signals_are_skipped() {
+        let model = ScoringModel {
+            base: 50.0,
+            features: vec![
+                liquidity_feature(1.0, &[(None, 10.0)]),
+                ScoreFeature::new("mint_authority", Signal::MintAuthority, &[(None, -20.0)]),
+            ],
+        };
+        let breakdown = model.score(&TokenOpportunity::default());
+
+        assert_eq!(breakdown.score, 60);
+        assert_eq!(breakdown.features[0].value, Some(0.0));
+        assert_eq!(breakdown.features[1].value, None);
+        assert_eq!(breakdown.features[1].points, 0.0);
+    }
+
+    #[test]
+    fn scores_are_clamped_to_0_100() {
+        let token = TokenOpportunity::default();
+        let model = |base, points| ScoringModel { base, features: vec![liquidity_feature(1.0, &[(None, points)])] };
+
+        assert_eq!(model(95.0, 10.0).score(&token).score, 100);
+        assert_eq!(model(5.0, -20.0).score(&token).score, 0);
+        assert_eq!(model(40.0, 2.4).score(&token).score, 42);
+    }
+}
@@ -0,0 +1,264 @@
+use anyhow::{Result, anyhow};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::Mutex;
+use crate::config::{ApiToken, BotConfig};
+
+/// Shown in place of a secret value
+pub const REDACTED: &str = "***";
+
+/// Secrets shorter than this are not redacted, they would mangle unrelated text
+const MIN_REDACTED_LEN: usize = 4;
+
+/// Whether a setting references a secret as `${NAME}`
+pub fn contains_reference(value: &str) -> bool {
+    value.find("${").is_some_and(|start| value[start..].contains('}'))
+}
+
+/// Secret values kept out of the config, referenced from it as `${NAME}`
+///
+/// A reference resolves from the environment variable `NAME` first, then
+/// from the secrets file. Every value that was loaded or resolved is masked
+/// by `redact`.
+#[derive(Debug, Default)]
+pub struct Secrets {
+    file_values: HashMap<String, String>,
+    /// Values to mask, including those resolved from the environment
+    known: Mutex<HashSet<String>>,
+}
+
+impl Secrets {
+    /// Secrets resolved from the environment only
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load a secrets file of `NAME = "value"` pairs, as TOML or, for `.json`, a JSON object
+    ///
+    /// On Unix the file must not be accessible to group or others.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        check_permissions(path)?;
+
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| anyhow!("Cannot read secrets file {}: {}", path.display(), e))?;
+        let is_json = path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+        let file_values: HashMap<String, String> = if is_json {
+            serde_json::from_str(&text).map_err(|e| anyhow!("Invalid secrets file {}: {}", path.display(), e))?
+        } else {
+            toml::from_str(&text).map_err(|e| anyhow!("Invalid secrets file {}: {}", path.display(), e))?
+        };
+
+        let known = file_values.values()
+            .filter(|value| value.len() >= MIN_REDACTED_LEN)
+            .cloned()
+            .collect();
+
+        Ok(Self {
+            file_values,
+            known: Mutex::new(known),
+        })
+    }
+
+    /// Value of a named secret
+    pub fn get(&self, name: &str) -> Option<String> {
+        let value = std::env::var(name).ok()
+            .filter(|value| !value.is_empty())
+            .or_else(|| self.file_values.get(name).cloned())?;
+
+        if value.len() >= MIN_REDACTED_LEN {
+            self.known.lock().unwrap().insert(value.clone());
+        }
+        Some(value)
+    }
+
+    /// Replace every `${NAME}` in a setting with its secret value
+    pub fn resolve(&self, value: &str) -> Result<String> {
+        let mut resolved = String::with_capacity(value.len());
+        let mut rest = value;
+
+        while let Some(start) = rest.find("${") {
+            let Some(len) = rest[start + 2..].find('}') else {
+                break;
+            };
+            let name = &rest[start + 2..start + 2 + len];
+            let secret = self.get(name)
+                .ok_or_else(|| anyhow!("Secret {} is not set in the environment or the secrets file", name))?;
+
+            resolved.push_str(&rest[..start]);
+            resolved.push_str(&secret);
+            rest = &rest[start + 3 + len..];
+        }
+
+        resolved.push_str(rest);
+        Ok(resolved)
+    }
+
+    /// A copy of `config` with every secret-bearing setting resolved
+    ///
+    /// These are the RPC URL, the fallback RPC and Jito endpoints, and the
+    /// notification webhook. The copy is for handing to the services only;
+    /// it must never be saved or served back through the API.
+    pub fn resolve_config(&self, config: &BotConfig) -> Result<BotConfig> {
+        let mut resolved = config.clone();
+        resolved.rpc_url = self.resolve_url("rpcUrl", &config.rpc_url)?;
+
+        let advanced = &mut resolved.advanced_settings;
+        advanced.rpc_endpoints = config.advanced_settings.rpc_endpoints.iter()
+            .map(|endpoint| self.resolve_url("advanced.rpcEndpoints", endpoint))
+            .collect::<Result<_>>()?;
+        advanced.jito_endpoints = config.advanced_settings.jito_endpoints.iter()
+            .map(|endpoint| self.resolve_url("advanced.jitoEndpoints", endpoint))
+            .collect::<Result<_>>()?;
+
+        resolved.notification_settings.webhook_url = config.notification_settings.webhook_url.as_deref()
+            .map(|url| self.resolve_url("ui.notifications.webhookUrl", url))
+            .transpose()?;
+
+        Ok(resolved)
+    }
+
+    /// API tokens with their secret values resolved
+    pub fn resolve_api_tokens(&self, tokens: &[ApiToken]) -> Result<Vec<ApiToken>> {
+        tokens.iter()
+            .map(|token| Ok(ApiToken {
+                token: self.resolve(&token.token).map_err(|e| anyhow!("API token {}: {}", token.name, e))?,
+                ..token.clone()
+            }))
+            .collect()
+    }
+
+    /// Resolve a URL setting, checking it is still a valid URL afterwards
+    fn resolve_url(&self, setting: &str, value: &str) -> Result<String> {
+        let resolved = self.resolve(value).map_err(|e| anyhow!("{}: {}", setting, e))?;
+        url::Url::parse(&resolved)
+            .map_err(|e| anyhow!("{} is not a valid URL once secrets are resolved: {}", setting, e))?;
+        Ok(resolved)
+    }
+
+    /// Mask every known secret value in a piece of text
+    pub fn redact(&self, text: &str) -> String {
+        let known = self.known.lock().unwrap();
+        if known.is_empty() {
+            return text.to_string();
+        }
+
+        // Longest first, so a secret containing another is masked whole
+        let mut secrets: Vec<&String> = known.iter().collect();
+        secrets.sort_by_key(|secret| std::cmp::Reverse(secret.len()));
+
+        secrets.into_iter().fold(text.to_string(), |text, secret| text.replace(secret.as_str(), REDACTED))
+    }
+
+    /// Mask secrets in every string of a JSON value
+    pub fn redact_json(&self, value: &mut serde_json::Value) {
+        match value {
+            serde_json::Value::String(text) => *text = self.redact(text),
+            serde_json::Value::Array(items) => items.iter_mut().for_each(|item| self.redact_json(item)),
+            serde_json::Value::Object(fields) => fields.values_mut().for_each(|field| self.redact_json(field)),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(unix)]
+fn check_permissions(path: &Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mode = std::fs::metadata(path)
+        .map_err(|e| anyhow!("Cannot read secrets file {}: {}", path.display(), e))?
+        .permissions()
+        .mode();
+
+    if mode & 0o077 != 0 {
+        return Err(anyhow!(
+            "Secrets file {} is accessible to other users (mode {:o}); run chmod 600 on it",
+            path.display(), mode & 0o777
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn check_permissions(_path: &Path) -> Result<()> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn secrets(values: &[(&str, &str)]) -> Secrets {
+        Secrets {
+            file_values: values.iter().map(|(name, value)| (name.to_string(), value.to_string())).collect(),
+            known: Mutex::new(HashSet::new()),
+        }
+    }
+
+    #[test]
+    fn resolves_every_reference() {
+        let secrets = secrets(&[("WOLF_TEST_KEY", "abc123"), ("WOLF_TEST_HOST", "rpc.example")]);
+        assert_eq!(
+            secrets.resolve("https://${WOLF_TEST_HOST}/?api-key=${WOLF_TEST_KEY}").unwrap(),
+            "https://rpc.example/?api-key=abc123"
+        );
+        assert_eq!(secrets.resolve("no references").unwrap(), "no references");
+        assert_eq!(secrets.resolve("unterminated ${WOLF_TEST_KEY").unwrap(), "unterminated ${WOLF_TEST_KEY");
+    }
+
+    #[test]
+    fn missing_secret_is_an_error() {
+        let error = secrets(&[]).resolve("${WOLF_TEST_MISSING}").unwrap_err();
+        assert!(error.to_string().contains("WOLF_TEST_MISSING"));
+    }
+
+    #[test]
+    fn redacts_resolved_values_only() {
+        let secrets = secrets(&[("WOLF_TEST_KEY", "abc123"), ("WOLF_TEST_LONG", "abc123456"), ("WOLF_TEST_SHORT", "ab")]);
+        assert_eq!(secrets.redact("key abc123"), "key abc123", "nothing resolved yet");
+
+        secrets.resolve("${WOLF_TEST_KEY} ${WOLF_TEST_LONG} ${WOLF_TEST_SHORT}").unwrap();
+        assert_eq!(secrets.redact("key abc123, long abc123456, short ab"), "key ***, long ***, short ab");
+    }
+
+    #[test]
+    fn loaded_file_values_are_redacted() {
+        let path = std::env::temp_dir().join(format!("wolf63x-secrets-{}.toml", std::process::id()));
+        std::fs::write(&path, "WOLF_TEST_TOKEN = \"token-value\"\n").unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600)).unwrap();
+        }
+
+        let secrets = Secrets::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let mut value = serde_json::json!({ "tokens": ["token-value", "other"] });
+        secrets.redact_json(&mut value);
+        assert_eq!(value, serde_json::json!({ "tokens": ["***", "other"] }));
+    }
+
+    #[test]
+    fn resolves_every_secret_bearing_setting() {
+        let secrets = secrets(&[("WOLF_TEST_KEY", "abc123")]);
+        let mut config = BotConfig {
+            rpc_url: "https://rpc.example/?api-key=${WOLF_TEST_KEY}".to_string(),
+            ..BotConfig::default()
+        };
+        config.advanced_settings.rpc_endpoints = vec!["https://fallback.example/${WOLF_TEST_KEY}".to_string()];
+        config.advanced_settings.jito_endpoints = vec!["https://jito.example/?uuid=${WOLF_TEST_KEY}".to_string()];
+        config.notification_settings.webhook_url = Some("https://hooks.example/${WOLF_TEST_KEY}".to_string());
+
+        let resolved = secrets.resolve_config(&config).unwrap();
+        assert_eq!(resolved.rpc_url, "https://rpc.example/?api-key=abc123");
+        assert_eq!(resolved.advanced_settings.rpc_endpoints, vec!["https://fallback.example/abc123".to_string()]);
+        assert_eq!(resolved.advanced_settings.jito_endpoints, vec!["https://jito.example/?uuid=abc123".to_string()]);
+        assert_eq!(resolved.notification_settings.webhook_url.as_deref(), Some("https://hooks.example/abc123"));
+        assert_eq!(config.rpc_url, "https://rpc.example/?api-key=${WOLF_TEST_KEY}", "the original keeps its references");
+
+        config.advanced_settings.jito_endpoints = vec!["${WOLF_TEST_KEY}".to_string()];
+        let error = secrets.resolve_config(&config).unwrap_err();
+        assert!(error.to_string().contains("advanced.jitoEndpoints"));
+    }
+}
@@ -0,0 +1,250 @@
+use chrono::{DateTime, Duration, Utc};
+use std::collections::{HashSet, VecDeque};
+use crate::scanner::TokenOpportunity;
+
+/// Upper bound on remembered launches, whatever the window
+const MAX_ENTRIES: usize = 10_000;
+
+/// Name similarity (0-1) above which two launches are near-duplicates
+const NAME_SIMILARITY: f64 = 0.85;
+
+/// Description word overlap (Jaccard, 0-1) above which two launches are near-duplicates
+const DESCRIPTION_SIMILARITY: f64 = 0.8;
+
+/// Descriptions shorter than this many words are too generic to compare
+const MIN_DESCRIPTION_WORDS: usize = 5;
+
+/// Names are compared on this many leading characters, bounding each edit distance
+const MAX_NAME_CHARS: usize = 32;
+
+/// What a launch is compared on
+#[derive(Debug, Clone)]
+struct Fingerprint {
+    mint: String,
+    name: Vec<char>,
+    symbol: String,
+    image_hash: Option<String>,
+    description: HashSet<String>,
+    seen_at: DateTime<Utc>,
+}
+
+impl Fingerprint {
+    fn of(token: &TokenOpportunity, seen_at: DateTime<Utc>) -> Self {
+        let metadata = token.metadata.as_ref();
+        Self {
+            mint: token.address.clone(),
+            name: normalize(&token.name).chars().take(MAX_NAME_CHARS).collect(),
+            symbol: normalize(&token.symbol),
+            image_hash: metadata.and_then(|m| m.image_hash.clone()),
+            description: metadata
+                .and_then(|m| m.description.as_deref())
+                .map(words)
+                .unwrap_or_default(),
+            seen_at,
+        }
+    }
+
+    fn is_similar(&self, other: &Fingerprint) -> bool {
+        if !self.symbol.is_empty() && self.symbol == other.symbol {
+            return true;
+        }
+
+        if !self.name.is_empty() && !other.name.is_empty()
+            && name_similarity(&self.name, &other.name) >= NAME_SIMILARITY
+        {
+            return true;
+        }
+
+        if self.image_hash.is_some() && self.image_hash == other.image_hash {
+            return true;
+        }
+
+        self.description.len() >= MIN_DESCRIPTION_WORDS
+            && other.description.len() >= MIN_DESCRIPTION_WORDS
+            && jaccard(&self.description, &other.description) >= DESCRIPTION_SIMILARITY
+    }
+}
+
+/// Index of recent launches used to spot copycats of trending tickers
+#[derive(Debug, Default)]
+pub struct SimilarityIndex {
+    entries: VecDeque<Fingerprint>,
+}
+
+impl SimilarityIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record or refresh a launch and count near-duplicates seen before it within `window`
+    ///
+    /// Only earlier launches are counted, so the first of a copycat wave is not
+    /// penalized for the spam that follows it. A launch is dated by its creation,
+    /// so one observed again after being pruned does not come back as new.
+    pub fn observe(&mut self, token: &TokenOpportunity, window: Duration) -> u32 {
+        let now = Utc::now();
+        let cutoff = now - window;
+        self.prune(cutoff);
+
+        let position = self.entries.iter().position(|entry| entry.mint == token.address);
+        let seen_at = position.map_or(token.created_at.min(now), |index| self.entries[index].seen_at);
+        let fingerprint = Fingerprint::of(token, seen_at);
+
+        match position {
+            // Metadata arrives after the launch event, so refresh the fingerprint in place
+            Some(index) => self.entries[index] = fingerprint.clone(),
+            // Too old to be copied by anything still in the window
+            None if seen_at < cutoff => {}
+            None => {
+                // Kept in launch order, which scans do not always deliver
+                let index = self.entries.partition_point(|entry| entry.seen_at <= seen_at);
+                self.entries.insert(index, fingerprint.clone());
+                if self.entries.len() > MAX_ENTRIES {
+                    self.entries.pop_front();
+                }
+            }
+        }
+
+        self.entries.iter()
+            .filter(|entry| entry.mint != fingerprint.mint && entry.seen_at <= seen_at)
+            .filter(|entry| fingerprint.is_similar(entry))
+            .count() as u32
+    }
+
+    /// Forget launches seen before `cutoff`
+    fn prune(&mut self, cutoff: DateTime<Utc>) {
+        while self.entries.front().is_some_and(|entry| entry.seen_at < cutoff) {
+            self.entries.pop_front();
+        }
+    }
+}
+
+/// Lowercase alphanumerics with common look-alike digits folded to letters
+fn normalize(text: &str) -> String {
+    text.chars()
+        .filter(|c| c.is_alphanumeric())
+        .map(|c| match c {
+            '0' => 'o',
+            '1' => 'i',
+            '3' => 'e',
+            '4' => 'a',
+            '5' => 's',
+            '7' => 't',
+            c => c,
+        })
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+fn words(text: &str) -> HashSet<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| word.len() > 2)
+        .map(str::to_lowercase)
+        .collect()
+}
+
+fn jaccard(a: &HashSet<String>, b: &HashSet<String>) -> f64 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(b).count() as f64 / union as f64
+}
+
+/// 1 minus the edit distance relative to the longer name, or 0 when the lengths alone rule out a match
+fn name_similarity(a: &[char], b: &[char]) -> f64 {
+    let longest = a.len().max(b.len());
+    if longest == 0 {
+        return 1.0;
+    }
+
+    // The distance is at least the difference in length, so skip names too far apart
+    let shortest = a.len().min(b.len());
+    if ((longest - shortest) as f64) > longest as f64 * (1.0 - NAME_SIMILARITY) {
+        return 0.0;
+    }
+
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.iter().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+
+    1.0 - previous[b.len()] as f64 / longest as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrichment::TokenMetadata;
+
+    fn token(mint: &str, name: &str, symbol: &str, minutes_ago: i64) -> TokenOpportunity {
+        TokenOpportunity {
+            address: mint.to_string(),
+            name: name.to_string(),
+            symbol: symbol.to_string(),
+            created_at: Utc::now() - Duration::minutes(minutes_ago),
+            ..TokenOpportunity::default()
+        }
+    }
+
+    fn with_metadata(mut token: TokenOpportunity, description: &str, image_hash: Option<&str>) -> TokenOpportunity {
+        token.metadata = Some(TokenMetadata {
+            description: Some(description.to_string()),
+            image_hash: image_hash.map(str::to_string),
+            ..TokenMetadata::default()
+        });
+        token
+    }
+
+    fn similar(a: &TokenOpportunity, b: &TokenOpportunity) -> bool {
+        Fingerprint::of(a, Utc::now()).is_similar(&Fingerprint::of(b, Utc::now()))
+    }
+
+    #[test]
+    fn near_duplicates_are_similar() {
+        let original = token("a", "Pepe Coin", "PEPE", 0);
+        assert!(similar(&original, &token("b", "Something", "pepe", 0)));
+        assert!(similar(&original, &token("b", "P3pe Coin!", "PEPE2", 0)));
+        assert!(!similar(&original, &token("b", "Doge Moon", "DOGE", 0)));
+
+        let art = with_metadata(token("b", "Frog", "FROG", 0), "", Some("hash"));
+        assert!(similar(&art, &with_metadata(token("c", "Toad", "TOAD", 0), "", Some("hash"))));
+
+        let description = "the first frog on solana with a real community";
+        let story = with_metadata(token("b", "Frog", "FROG", 0), description, None);
+        assert!(similar(&story, &with_metadata(token("c", "Toad", "TOAD", 0), description, None)));
+        assert!(!similar(
+            &with_metadata(token("b", "Frog", "FROG", 0), "frog", None),
+            &with_metadata(token("c", "Toad", "TOAD", 0), "frog", None),
+        ));
+    }
+
+    #[test]
+    fn only_earlier_launches_are_counted() {
+        let mut index = SimilarityIndex::new();
+        let window = Duration::minutes(10);
+        assert_eq!(index.observe(&token("a", "Pepe", "PEPE", 3), window), 0);
+        assert_eq!(index.observe(&token("b", "Pepe", "PEPE", 2), window), 1);
+
+        // Refreshing a launch keeps its place in the wave and never counts itself
+        assert_eq!(index.observe(&token("a", "Pepe", "PEPE", 3), window), 0);
+        assert_eq!(index.observe(&token("b", "Pepe", "PEPE", 2), window), 1);
+
+        // A copycat delivered late is still ordered by launch
+        assert_eq!(index.observe(&token("c", "Pepe", "PEPE", 1), window), 2);
+        assert_eq!(index.observe(&token("d", "Pepe", "PEPE", 5), window), 0);
+    }
+
+    #[test]
+    fn launches_older_than_the_window_do_not_come_back_as_new() {
+        let mut index = SimilarityIndex::new();
+        let window = Duration::minutes(10);
+        assert_eq!(index.observe(&token("old", "Pepe", "PEPE", 30), window), 0);
+        assert_eq!(index.observe(&token("new", "Pepe", "PEPE", 0), window), 0);
+    }
+}
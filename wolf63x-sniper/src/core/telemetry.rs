@@ -0,0 +1,129 @@
+use async_trait::async_trait;
+use metrics::{counter, describe_counter, describe_gauge, describe_histogram, histogram, Unit};
+use solana_client::client_error::Result as ClientResult;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_client::RpcClientConfig;
+use solana_client::rpc_request::RpcRequest;
+use solana_client::rpc_sender::{RpcSender, RpcTransportStats};
+use solana_rpc_client::http_sender::HttpSender;
+use std::time::Instant;
+use crate::rules::FilterVerdict;
+
+// Metric names, shared by the code recording them and the exporter configuring them
+
+/// Tokens seen for the first time, by `source`
+pub const OPPORTUNITIES_TOTAL: &str = "wolf63x_opportunities_total";
+/// Filter decisions on new tokens and tokens whose verdict flipped, by `result`
+pub const FILTER_VERDICTS_TOTAL: &str = "wolf63x_filter_verdicts_total";
+/// Each rule's outcome in those decisions, by `rule` and `result`
+pub const FILTER_RULE_RESULTS_TOTAL: &str = "wolf63x_filter_rule_results_total";
+/// Orders sent to the network, by `side`
+pub const ORDERS_SENT_TOTAL: &str = "wolf63x_orders_sent_total";
+/// Orders confirmed on chain, by `side`
+pub const ORDERS_LANDED_TOTAL: &str = "wolf63x_orders_landed_total";
+/// Orders that failed to build, send or confirm, by `side`
+pub const ORDERS_FAILED_TOTAL: &str = "wolf63x_orders_failed_total";
+/// From a token being seen to its buy being sent
+pub const DETECTION_TO_SEND_SECONDS: &str = "wolf63x_detection_to_send_seconds";
+/// From an order being sent to its confirmation, by `side`
+pub const SEND_TO_CONFIRM_SECONDS: &str = "wolf63x_send_to_confirm_seconds";
+/// RPC round trips, by `endpoint` host and `method`
+pub const RPC_REQUEST_SECONDS: &str = "wolf63x_rpc_request_seconds";
+/// Failed RPC requests, by `endpoint` host and `method`
+pub const RPC_ERRORS_TOTAL: &str = "wolf63x_rpc_errors_total";
+/// Pump.fun websocket reconnects after a drop or failed connect
+pub const WEBSOCKET_RECONNECTS_TOTAL: &str = "wolf63x_websocket_reconnects_total";
+/// 1 while the Pump.fun websocket is connected
+pub const WEBSOCKET_CONNECTED: &str = "wolf63x_websocket_connected";
+/// Running total of realized profit and loss
+pub const REALIZED_PNL_SOL: &str = "wolf63x_realized_pnl_sol";
+/// Realized profit and loss of each sell, in percent
+pub const TRADE_PNL_PERCENT: &str = "wolf63x_trade_pnl_percent";
+
+/// Buckets for latencies, 10ms to 1 minute
+pub const LATENCY_BUCKETS: &[f64] = &[0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0];
+
+/// Buckets for per-trade PnL, a total loss to a 10x
+pub const PNL_PERCENT_BUCKETS: &[f64] = &[-100.0, -50.0, -25.0, -10.0, 0.0, 10.0, 25.0, 50.0, 100.0, 250.0, 1000.0];
+
+/// Register help text for every metric with the installed recorder
+pub fn describe() {
+    describe_counter!(OPPORTUNITIES_TOTAL, "Tokens seen for the first time, by source");
+    describe_counter!(FILTER_VERDICTS_TOTAL, "Filter decisions on new tokens and flipped verdicts");
+    describe_counter!(FILTER_RULE_RESULTS_TOTAL, "Rule outcomes in filter decisions");
+    describe_counter!(ORDERS_SENT_TOTAL, "Orders sent");
+    describe_counter!(ORDERS_LANDED_TOTAL, "Orders confirmed on chain");
+    describe_counter!(ORDERS_FAILED_TOTAL, "Orders that failed");
+    describe_histogram!(DETECTION_TO_SEND_SECONDS, Unit::Seconds, "Time from detecting a token to sending its buy");
+    describe_histogram!(SEND_TO_CONFIRM_SECONDS, Unit::Seconds, "Time from sending an order to its confirmation");
+    describe_histogram!(RPC_REQUEST_SECONDS, Unit::Seconds, "RPC request latency");
+    describe_counter!(RPC_ERRORS_TOTAL, "Failed RPC requests");
+    describe_counter!(WEBSOCKET_RECONNECTS_TOTAL, "Pump.fun websocket reconnects");
+    describe_gauge!(WEBSOCKET_CONNECTED, "Whether the Pump.fun websocket is connected");
+    describe_gauge!(REALIZED_PNL_SOL, "Realized profit and loss in SOL, from the shared trade log");
+    describe_histogram!(TRADE_PNL_PERCENT, Unit::Percent, "Realized profit and loss per sell");
+}
+
+/// Count a filter decision and the outcome of every rule in it
+pub fn record_verdict(verdict: &FilterVerdict) {
+    counter!(FILTER_VERDICTS_TOTAL, "result" => outcome(verdict.passed)).increment(1);
+    for result in &verdict.results {
+        counter!(FILTER_RULE_RESULTS_TOTAL, "rule" => result.rule.clone(), "result" => outcome(result.passed)).increment(1);
+    }
+}
+
+fn outcome(passed: bool) -> &'static str {
+    if passed { "pass" } else { "reject" }
+}
+
+/// An RPC client recording the latency and errors of every request
+pub fn rpc_client(url: String) -> RpcClient {
+    RpcClient::new_sender(TimedSender::new(url), RpcClientConfig::default())
+}
+
+/// Host of an RPC URL; paths and query strings often carry API keys
+pub fn endpoint_label(url: &str) -> String {
+    url::Url::parse(url)
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_string))
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+/// HTTP transport that times each request
+struct TimedSender {
+    inner: HttpSender,
+    endpoint: String,
+}
+
+impl TimedSender {
+    fn new(url: String) -> Self {
+        Self {
+            endpoint: endpoint_label(&url),
+            inner: HttpSender::new(url),
+        }
+    }
+}
+
+#[async_trait]
+impl RpcSender for TimedSender {
+    async fn send(&self, request: RpcRequest, params: serde_json::Value) -> ClientResult<serde_json::Value> {
+        let started = Instant::now();
+        let response = self.inner.send(request, params).await;
+
+        let method = request.to_string();
+        histogram!(RPC_REQUEST_SECONDS, "endpoint" => self.endpoint.clone(), "method" => method.clone())
+            .record(started.elapsed().as_secs_f64());
+        if response.is_err() {
+            counter!(RPC_ERRORS_TOTAL, "endpoint" => self.endpoint.clone(), "method" => method).increment(1);
+        }
+        response
+    }
+
+    fn get_transport_stats(&self) -> RpcTransportStats {
+        self.inner.get_transport_stats()
+    }
+
+    fn url(&self) -> String {
+        self.inner.url()
+    }
+}
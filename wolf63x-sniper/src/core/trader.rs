@@ -1,14 +1,29 @@
 use wasm_bindgen::prelude::*;
+use solana_client::nonblocking::rpc_client::RpcClient;
 use solana_sdk::{
     commitment_config::CommitmentConfig,
     instruction::Instruction,
+    native_token::LAMPORTS_PER_SOL,
     pubkey::Pubkey,
-    signature::Signature,
+    signature::{Keypair, Signature},
+    signer::Signer,
     transaction::Transaction,
 };
-use anyhow::{Result, anyhow};
+use anyhow::anyhow;
+use std::future::Future;
 use std::str::FromStr;
+use chrono::{NaiveDate, Utc};
+use std::time::{Duration, Instant};
 use crate::{console_log, wallet::Wallet};
+use crate::config::{AdvancedSettings, TradeSettings};
+use crate::error::SniperError;
+use crate::pump_fun::{self, BondingCurveState};
+
+/// A blockhash lasts about 150 slots, a minute or so; a transaction not
+/// confirmed by then can no longer land
+const CONFIRM_TIMEOUT: Duration = Duration::from_secs(90);
+
+const CONFIRM_POLL_INTERVAL: Duration = Duration::from_millis(500);
 
 /// Represents a token trade
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -23,6 +38,9 @@ pub struct Trade {
     pub status: TradeStatus,
     pub profit_loss: f64,
     pub profit_loss_percent: f64,
+    /// Strategy profile that opened the trade
+    #[serde(default)]
+    pub profile: Option<String>,
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -37,54 +55,40 @@ pub enum TradeStatus {
 pub struct Trader {
     wallet: Wallet,
     rpc_url: String,
+    settings: TradeSettings,
+    /// Fallback endpoints and retries for RPC calls
+    advanced: AdvancedSettings,
+    /// Buys made on the day they were counted on, for `max_trades_per_day`
+    buys_today: (NaiveDate, u32),
 }
 
 #[wasm_bindgen]
 impl Trader {
     #[wasm_bindgen(constructor)]
     pub fn new(wallet: Wallet, rpc_url: String) -> Self {
-        Self { wallet, rpc_url }
+        Self {
+            wallet,
+            rpc_url,
+            settings: TradeSettings::default(),
+            advanced: AdvancedSettings::default(),
+            buys_today: (Utc::now().date_naive(), 0),
+        }
     }
 
     /// Snipe a token as soon as it's available
     pub async fn snipe_token(&self, token_address: &str, amount_sol: f64, slippage: f64) -> Result<String, JsValue> {
-        if !self.wallet.connected {
-            return Err(JsValue::from_str("Wallet not connected"));
-        }
-
-        console_log!("Sniping token: {} with {} SOL and {}% slippage", token_address, amount_sol, slippage);
-        
-        // In a real implementation, this would:
-        // 1. Create a swap instruction using Jupiter or Raydium
-        // 2. Build and sign the transaction
-        // 3. Send the transaction with max priority
-        // 4. Monitor for confirmation
-        
-        // For demo purposes, we'll simulate a successful transaction
-        let signature = "5KtPn1LGuxhFLF2W1KqxjEDYUdpT5LUEo7DGYLUYgXHEbDp6hqiYMf3hZ1SnZBrSqNpYdnCvUxUpWTJmJP3nKXKE";
-        
-        Ok(signature.to_string())
+        self.check_daily_limit(&self.settings)?;
+        Ok(self.snipe_with_settings(token_address, amount_sol, slippage, &self.settings).await?)
     }
     
-    /// Sell a token
-    pub async fn sell_token(&self, token_address: &str, amount: f64, slippage: f64) -> Result<String, JsValue> {
-        if !self.wallet.connected {
-            return Err(JsValue::from_str("Wallet not connected"));
-        }
-        
-        console_log!("Selling token: {} with amount {} and {}% slippage", token_address, amount, slippage);
-        
-        // Similar to snipe_token, but selling instead of buying
-        let signature = "4VdA5hYvVxQALSEtKcANnqPWXtFqJdmTSWLzPsttfYjN9QY8vJYXHnLcnBMJYMgTQF7iNEZLwAcuRaptW2YwKFdK";
-        
-        Ok(signature.to_string())
+    /// Sell `percent` of the wallet's holdings of a token
+    pub async fn sell_token(&self, token_address: &str, percent: f64, slippage: f64) -> Result<String, JsValue> {
+        Ok(self.sell(token_address, percent, slippage).await?)
     }
     
     /// Set take profit for a token
     pub fn set_take_profit(&self, token_address: &str, percentage: f64) -> Result<(), JsValue> {
-        if !self.wallet.connected {
-            return Err(JsValue::from_str("Wallet not connected"));
-        }
+        self.wallet.ensure_connected()?;
         
         console_log!("Setting take profit for token: {} at {}%", token_address, percentage);
         
@@ -96,9 +100,7 @@ impl Trader {
     
     /// Set stop loss for a token
     pub fn set_stop_loss(&self, token_address: &str, percentage: f64) -> Result<(), JsValue> {
-        if !self.wallet.connected {
-            return Err(JsValue::from_str("Wallet not connected"));
-        }
+        self.wallet.ensure_connected()?;
         
         console_log!("Setting stop loss for token: {} at {}%", token_address, percentage);
         
@@ -110,24 +112,286 @@ impl Trader {
 
 // Native Rust implementation (not exposed to WASM)
 impl Trader {
-    /// Build a swap transaction
-    fn build_swap_transaction(&self, token_address: &str, amount_sol: f64) -> Result<Transaction> {
-        // This would use Jupiter or Raydium SDK to build the swap instruction
-        // For now, we'll just return a placeholder
-        Err(anyhow!("Not implemented"))
+    /// Trade with a newly connected (or disconnected) wallet
+    pub fn set_wallet(&mut self, wallet: Wallet) {
+        self.wallet = wallet;
+    }
+    
+    /// Apply trade and RPC settings from the configuration, with secrets already resolved
+    pub fn apply_settings(&mut self, settings: &TradeSettings, advanced: &AdvancedSettings, rpc_url: &str) {
+        self.settings = settings.clone();
+        self.advanced = advanced.clone();
+        self.rpc_url = rpc_url.to_string();
+    }
+    
+    /// Copy of the trader for sending one order while this one takes the next
+    pub fn session(&self) -> Self {
+        Self {
+            wallet: self.wallet.session(),
+            rpc_url: self.rpc_url.clone(),
+            settings: self.settings.clone(),
+            advanced: self.advanced.clone(),
+            buys_today: self.buys_today,
+        }
+    }
+
+    /// Count a buy towards the daily limit of `settings`, or the trader's own, before it is sent
+    ///
+    /// Returns the day it was counted on, for `release_buy` if it fails.
+    pub fn reserve_buy(&mut self, settings: Option<&TradeSettings>) -> Result<NaiveDate, SniperError> {
+        let today = Utc::now().date_naive();
+        if self.buys_today.0 != today {
+            self.buys_today = (today, 0);
+        }
+        self.check_daily_limit(settings.unwrap_or(&self.settings))?;
+        self.buys_today.1 += 1;
+        Ok(today)
+    }
+
+    /// Buys counted towards the daily limit, and the day they were counted on
+    pub fn buys_today(&self) -> (NaiveDate, u32) {
+        self.buys_today
+    }
+
+    /// Take over a count kept elsewhere, e.g. by another process
+    pub fn set_buys_today(&mut self, day: NaiveDate, buys: u32) {
+        self.buys_today = (day, buys);
+    }
+
+    /// Stop counting a reserved buy that did not go through
+    pub fn release_buy(&mut self, day: NaiveDate) {
+        if self.buys_today.0 == day {
+            self.buys_today.1 = self.buys_today.1.saturating_sub(1);
+        }
+    }
+
+    /// Buy within the limits of `settings`, or the trader's own
+    ///
+    /// The daily limit is left to `reserve_buy`.
+    pub async fn buy(
+        &self,
+        token_address: &str,
+        amount_sol: f64,
+        slippage: f64,
+        settings: Option<&TradeSettings>,
+    ) -> Result<String, SniperError> {
+        self.snipe_with_settings(token_address, amount_sol, slippage, settings.unwrap_or(&self.settings)).await
+    }
+
+    fn check_daily_limit(&self, settings: &TradeSettings) -> Result<(), SniperError> {
+        let (day, buys) = self.buys_today;
+        if day == Utc::now().date_naive() && buys >= settings.max_trades_per_day {
+            return Err(SniperError::LimitReached(format!("{} trades per day", settings.max_trades_per_day)));
+        }
+        Ok(())
+    }
+    
+    /// Snipe a token within the buy limits of `settings`, e.g. those of a strategy profile
+    pub async fn snipe_with_settings(
+        &self,
+        token_address: &str,
+        amount_sol: f64,
+        slippage: f64,
+        settings: &TradeSettings,
+    ) -> Result<String, SniperError> {
+        self.wallet.ensure_connected()?;
+
+        if amount_sol < settings.min_buy_amount || amount_sol > settings.max_buy_amount {
+            return Err(SniperError::InvalidRequest(format!(
+                "Buy amount must be between {} and {} SOL",
+                settings.min_buy_amount, settings.max_buy_amount
+            )));
+        }
+
+        // A keypair wallet's balance is only known on chain
+        let available = if self.wallet.has_keypair() {
+            self.query_balance().await?
+        } else {
+            self.wallet.balance
+        };
+        if amount_sol > available {
+            return Err(SniperError::InsufficientBalance { required: amount_sol, available });
+        }
+
+        console_log!("Sniping token: {} with {} SOL and {}% slippage", token_address, amount_sol, slippage);
+
+        let keypair = self.signer()?;
+        let mint = parse_mint(token_address)?;
+        let curve = self.bonding_curve(&mint).await?;
+
+        let lamports = (amount_sol * LAMPORTS_PER_SOL as f64) as u64;
+        let tokens = curve.buy_quote(lamports);
+        if tokens == 0 {
+            return Err(SniperError::InvalidRequest(format!("{} has no tokens left on its bonding curve", token_address)));
+        }
+
+        let instructions = [
+            pump_fun::create_ata_idempotent_instruction(&keypair.pubkey(), &mint)?,
+            pump_fun::buy_instruction(&mint, &keypair.pubkey(), tokens, with_slippage(lamports, slippage))?,
+        ];
+        let signature = self.send_transaction(keypair, &instructions).await?;
+        Ok(signature.to_string())
+    }
+    
+    /// Sell `percent` of the wallet's holdings of a token to its bonding curve
+    pub async fn sell(&self, token_address: &str, percent: f64, slippage: f64) -> Result<String, SniperError> {
+        self.wallet.ensure_connected()?;
+        if !(percent > 0.0 && percent <= 100.0) {
+            return Err(SniperError::InvalidRequest(format!("Sell percentage must be between 0 and 100 (got {})", percent)));
+        }
+        
+        console_log!("Selling {}% of token: {} with {}% slippage", percent, token_address, slippage);
+
+        let keypair = self.signer()?;
+        let mint = parse_mint(token_address)?;
+        let account = pump_fun::associated_token_address(&keypair.pubkey(), &mint)?;
+        let held = self.with_fallback(|rpc| async move { Ok(rpc.get_token_account_balance(&account).await?) }).await
+            .map_err(|e| match e {
+                SniperError::RpcUnavailable(_) => e,
+                _ => SniperError::NotFound(format!("No {} tokens in the wallet", token_address)),
+            })?;
+        let held: u64 = held.amount.parse()
+            .map_err(|_| anyhow!("Invalid token balance {:?} for {}", held.amount, token_address))?;
+
+        let tokens = (held as u128 * (percent * 100.0) as u128 / 10_000) as u64;
+        if tokens == 0 {
+            return Err(SniperError::NotFound(format!("No {} tokens in the wallet", token_address)));
+        }
+
+        let curve = self.bonding_curve(&mint).await?;
+        let min_sol_output = without_slippage(curve.sell_quote(tokens), slippage);
+        let instructions = [pump_fun::sell_instruction(&mint, &keypair.pubkey(), tokens, min_sol_output)?];
+        let signature = self.send_transaction(keypair, &instructions).await?;
+        Ok(signature.to_string())
     }
     
-    /// Send a transaction with max priority
-    async fn send_transaction(&self, transaction: Transaction) -> Result<Signature> {
-        // This would send the transaction to the Solana network
-        // For now, we'll just return a placeholder
-        Ok(Signature::default())
+    /// Wait for a sent transaction to reach confirmed status
+    ///
+    /// Fails with what went wrong if the transaction landed and failed, and
+    /// with `BlockhashExpired` once it can no longer land.
+    pub async fn confirm(&self, signature: &str) -> Result<(), SniperError> {
+        let signature = Signature::from_str(signature)
+            .map_err(|_| SniperError::InvalidRequest(format!("Invalid transaction signature: {}", signature)))?;
+        let started = Instant::now();
+
+        loop {
+            let statuses = self.with_fallback(|rpc| async move { Ok(rpc.get_signature_statuses(&[signature]).await?.value) }).await?;
+            if let Some(status) = statuses.into_iter().next().flatten() {
+                if let Some(err) = status.err {
+                    return Err(err.into());
+                }
+                if status.satisfies_commitment(CommitmentConfig::confirmed()) {
+                    return Ok(());
+                }
+            }
+            if started.elapsed() > CONFIRM_TIMEOUT {
+                return Err(SniperError::BlockhashExpired);
+            }
+            tokio::time::sleep(CONFIRM_POLL_INTERVAL).await;
+        }
+    }
+    
+    /// On-chain balance, retrying on the fallback endpoints while the RPC is unavailable
+    async fn query_balance(&self) -> Result<f64, SniperError> {
+        let pubkey = Pubkey::from_str(&self.wallet.address)
+            .map_err(|_| anyhow!("Invalid wallet address: {}", self.wallet.address))?;
+        let lamports = self.with_fallback(|rpc| async move { Ok(rpc.get_balance(&pubkey).await?) }).await?;
+        Ok(lamports as f64 / LAMPORTS_PER_SOL as f64)
+    }
+
+    /// Keypair orders are signed with; wallets connected through an adapter have none
+    fn signer(&self) -> Result<&Keypair, SniperError> {
+        self.wallet.get_keypair().map_err(|_| SniperError::InvalidRequest(
+            "Orders are signed with a keystore keypair, and the connected wallet has none".to_string(),
+        ))
+    }
+
+    /// Current state of a token's bonding curve, which orders are priced and sent against
+    async fn bonding_curve(&self, mint: &Pubkey) -> Result<BondingCurveState, SniperError> {
+        let address = pump_fun::bonding_curve_address(mint)?;
+        let data = self.with_fallback(|rpc| async move { Ok(rpc.get_account_data(&address).await?) }).await
+            .map_err(|e| match e {
+                SniperError::RpcUnavailable(_) => e,
+                _ => SniperError::NotFound(format!("No bonding curve for {}", mint)),
+            })?;
+        let curve = BondingCurveState::decode(&data)?;
+        if curve.complete {
+            return Err(SniperError::InvalidRequest(format!("{} has migrated off the bonding curve", mint)));
+        }
+        Ok(curve)
     }
     
-    /// Monitor a transaction for confirmation
-    async fn monitor_transaction(&self, signature: &Signature) -> Result<bool> {
-        // This would poll the Solana network for transaction confirmation
-        // For now, we'll just return a placeholder
-        Ok(true)
+    /// Sign `instructions` with the wallet's keypair and send them in one transaction
+    async fn send_transaction(&self, keypair: &Keypair, instructions: &[Instruction]) -> Result<Signature, SniperError> {
+        let blockhash = self.with_fallback(|rpc| async move { Ok(rpc.get_latest_blockhash().await?) }).await?;
+        let transaction = Transaction::new_signed_with_payer(instructions, Some(&keypair.pubkey()), &[keypair], blockhash);
+
+        let transaction = &transaction;
+        self.with_fallback(|rpc| async move { Ok(rpc.send_transaction(transaction).await?) }).await
+    }
+
+    /// Run an RPC call, retrying on the fallback endpoints while the RPC is unavailable
+    async fn with_fallback<T, F, Fut>(&self, call: F) -> Result<T, SniperError>
+    where
+        F: Fn(RpcClient) -> Fut,
+        Fut: Future<Output = Result<T, SniperError>>,
+    {
+        let endpoints: Vec<&str> = std::iter::once(self.rpc_url.as_str())
+            .chain(self.advanced.rpc_endpoints.iter().map(String::as_str))
+            .collect();
+
+        let mut attempt = 0;
+        loop {
+            let endpoint = endpoints[attempt % endpoints.len()];
+            match call(crate::telemetry::rpc_client(endpoint.to_string())).await {
+                Err(SniperError::RpcUnavailable(e)) if attempt < self.advanced.retry_attempts as usize => {
+                    log::warn!("RPC call failed, retrying on the next endpoint: {}", e);
+                    attempt += 1;
+                    tokio::time::sleep(Duration::from_millis(self.advanced.retry_delay_ms as u64)).await;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+fn parse_mint(token_address: &str) -> Result<Pubkey, SniperError> {
+    Pubkey::from_str(token_address)
+        .map_err(|_| SniperError::InvalidRequest(format!("Invalid mint address: {}", token_address)))
+}
+
+/// Most a buy of `lamports` may cost once the price moves by `slippage` percent
+fn with_slippage(lamports: u64, slippage: f64) -> u64 {
+    (lamports as f64 * (1.0 + slippage / 100.0)) as u64
+}
+
+/// Least a sell quoted at `lamports` may return once the price moves by `slippage` percent
+fn without_slippage(lamports: u64, slippage: f64) -> u64 {
+    (lamports as f64 * (1.0 - slippage / 100.0).max(0.0)) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slippage_bounds_the_order_price() {
+        assert_eq!(with_slippage(1_000_000_000, 1.0), 1_010_000_000);
+        assert_eq!(without_slippage(1_000_000_000, 1.0), 990_000_000);
+        assert_eq!(without_slippage(1_000_000_000, 150.0), 0);
+    }
+
+    #[test]
+    fn failed_buys_stop_counting_towards_the_daily_limit() {
+        let mut trader = Trader::new(Wallet::new(), String::new());
+        let settings = TradeSettings { max_trades_per_day: 2, ..TradeSettings::default() };
+
+        let first = trader.reserve_buy(Some(&settings)).unwrap();
+        trader.reserve_buy(Some(&settings)).unwrap();
+        assert!(matches!(trader.reserve_buy(Some(&settings)), Err(SniperError::LimitReached(_))));
+
+        trader.release_buy(first);
+        trader.reserve_buy(Some(&settings)).unwrap();
+        assert!(matches!(trader.reserve_buy(Some(&settings)), Err(SniperError::LimitReached(_))));
     }
 }
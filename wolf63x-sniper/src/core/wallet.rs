@@ -1,12 +1,14 @@
 use wasm_bindgen::prelude::*;
 use solana_sdk::{
+    native_token::LAMPORTS_PER_SOL,
     pubkey::Pubkey,
     signature::{Keypair, Signature},
+    signer::Signer,
     transaction::Transaction,
 };
 use anyhow::{Result, anyhow};
 use std::str::FromStr;
-use crate::console_log;
+use crate::{console_log, error::SniperError};
 
 /// Represents a wallet connection for the Solana blockchain
 #[derive(Debug)]
@@ -37,55 +39,23 @@ impl Wallet {
 
     /// Connect to a wallet (Phantom, Solflare, etc.)
     pub async fn connect(&mut self, wallet_type: &str) -> Result<String, JsValue> {
-        console_log!("Connecting to {} wallet...", wallet_type);
-        
-        // In a real implementation, this would use the Solana wallet adapter
-        // to connect to the actual wallet through the browser
-        #[cfg(target_arch = "wasm32")]
-        {
-            // Simulate wallet connection for demo
-            self.address = "HK4...7Yk9".to_string();
-            self.balance = 25.5;
-            self.connected = true;
-        }
-        
-        #[cfg(not(target_arch = "wasm32"))]
-        {
-            // For native app, we would use Tauri to bridge to the wallet
-            // This is a simplified implementation
-            self.address = "HK4...7Yk9".to_string();
-            self.balance = 25.5;
-            self.connected = true;
-        }
-        
-        Ok(self.address.clone())
+        Ok(self.connect_to(wallet_type).await?)
     }
     
     /// Disconnect from the wallet
     pub fn disconnect(&mut self) -> Result<(), JsValue> {
-        self.connected = false;
-        self.address = String::new();
-        self.balance = 0.0;
-        self.keypair = None;
-        
+        self.close();
         Ok(())
     }
     
     /// Get the current SOL balance
     pub async fn get_balance(&self) -> Result<f64, JsValue> {
-        if !self.connected {
-            return Err(JsValue::from_str("Wallet not connected"));
-        }
-        
-        // In a real implementation, this would query the Solana blockchain
-        Ok(self.balance)
+        Ok(self.connected_balance()?)
     }
     
     /// Sign a transaction
     pub fn sign_transaction(&self, transaction: &mut Transaction) -> Result<Signature, JsValue> {
-        if !self.connected {
-            return Err(JsValue::from_str("Wallet not connected"));
-        }
+        self.ensure_connected()?;
         
         // In a real implementation, this would use the wallet to sign
         // For now, we'll just return a dummy signature
@@ -94,9 +64,7 @@ impl Wallet {
     
     /// Convert the wallet address to a Pubkey
     pub fn pubkey(&self) -> Result<Pubkey, JsValue> {
-        if !self.connected {
-            return Err(JsValue::from_str("Wallet not connected"));
-        }
+        self.ensure_connected()?;
         
         match Pubkey::from_str(&self.address) {
             Ok(pubkey) => Ok(pubkey),
@@ -123,4 +91,86 @@ impl Wallet {
     pub fn get_keypair(&self) -> Result<&Keypair> {
         self.keypair.as_ref().ok_or_else(|| anyhow!("No keypair available"))
     }
+    
+    /// Whether the wallet holds its own keypair, e.g. one loaded from the keystore
+    pub fn has_keypair(&self) -> bool {
+        self.keypair.is_some()
+    }
+    
+    /// Connect to a wallet through its adapter, returning its address
+    pub async fn connect_to(&mut self, wallet_type: &str) -> Result<String, SniperError> {
+        console_log!("Connecting to {} wallet...", wallet_type);
+        
+        // In a real implementation, this would use the Solana wallet adapter
+        // to connect to the actual wallet through the browser
+        #[cfg(target_arch = "wasm32")]
+        {
+            // Simulate wallet connection for demo
+            self.address = "HK4...7Yk9".to_string();
+            self.balance = 25.5;
+            self.connected = true;
+        }
+        
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            // For native app, we would use Tauri to bridge to the wallet
+            // This is a simplified implementation
+            self.address = "HK4...7Yk9".to_string();
+            self.balance = 25.5;
+            self.connected = true;
+        }
+        
+        Ok(self.address.clone())
+    }
+    
+    /// Forget the connected wallet, including its keypair
+    pub fn close(&mut self) {
+        self.connected = false;
+        self.address = String::new();
+        self.balance = 0.0;
+        self.keypair = None;
+    }
+    
+    /// Last known SOL balance of the connected wallet
+    pub fn connected_balance(&self) -> Result<f64, SniperError> {
+        self.ensure_connected()?;
+        
+        // In a real implementation, this would query the Solana blockchain
+        Ok(self.balance)
+    }
+    
+    /// Fail with `WalletLocked` unless a wallet is connected
+    pub fn ensure_connected(&self) -> Result<(), SniperError> {
+        if self.connected {
+            Ok(())
+        } else {
+            Err(SniperError::WalletLocked)
+        }
+    }
+    
+    /// Query the on-chain SOL balance
+    pub async fn query_balance(&self, rpc_url: &str) -> Result<f64, SniperError> {
+        self.ensure_connected()?;
+        let pubkey = Pubkey::from_str(&self.address)
+            .map_err(|_| anyhow!("Invalid wallet address: {}", self.address))?;
+        let rpc = crate::telemetry::rpc_client(rpc_url.to_string());
+        let lamports = rpc.get_balance(&pubkey).await?;
+        Ok(lamports as f64 / LAMPORTS_PER_SOL as f64)
+    }
+    
+    /// Query the on-chain SOL balance and remember it
+    pub async fn fetch_balance(&mut self, rpc_url: &str) -> Result<f64, SniperError> {
+        self.balance = self.query_balance(rpc_url).await?;
+        Ok(self.balance)
+    }
+    
+    /// Copy of the connection for another component that signs with this wallet
+    pub fn session(&self) -> Self {
+        Self {
+            address: self.address.clone(),
+            balance: self.balance,
+            keypair: self.keypair.as_ref().and_then(|keypair| Keypair::from_bytes(&keypair.to_bytes()).ok()),
+            connected: self.connected,
+        }
+    }
 }
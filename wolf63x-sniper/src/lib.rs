@@ -95,10 +95,13 @@ pub fn get_active_trades() -> JsValue {
     serde_wasm_bindgen::to_value(&state.active_trades).unwrap()
 }
 
-// Helper macro for logging to the browser console
+// Helper macro for logging to the browser console, or the log crate on native targets
 #[macro_export]
 macro_rules! console_log {
-    ($($t:tt)*) => {
+    ($($t:tt)*) => {{
+        #[cfg(target_arch = "wasm32")]
         web_sys::console::log_1(&format!($($t)*).into());
-    }
+        #[cfg(not(target_arch = "wasm32"))]
+        log::info!($($t)*);
+    }}
 }
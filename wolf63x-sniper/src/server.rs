@@ -0,0 +1,843 @@
+//! HTTP API and dashboard, served over the same services as the CLI
+
+use axum::{
+    routing::{get, post},
+    http::{header, HeaderMap, HeaderValue, Method, StatusCode},
+    Json, Router,
+    extract::{ConnectInfo, Path, Query, Request, State},
+    extract::ws::{Message, WebSocket, WebSocketUpgrade},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    response::sse::{self, KeepAlive, Sse},
+};
+use std::collections::{HashMap, HashSet};
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use futures::StreamExt;
+use metrics_exporter_prometheus::{Matcher, PrometheusBuilder, PrometheusHandle};
+use tokio::net::TcpListener;
+use tower_http::{
+    cors::{Any, CorsLayer},
+    services::ServeDir,
+};
+use serde::{Serialize, Deserialize};
+use log::{info, warn};
+use wolf63x_core::{
+    wallet::Wallet,
+    config::{ApiRole, BotConfig},
+    error::SniperError,
+    events::{SequencedEvent, Subscription, Topic},
+    telemetry,
+};
+use crate::app::App;
+
+// App state shared between routes
+//
+// The services are actor handles; the remaining locks are only ever held
+// for synchronous work.
+struct AppState {
+    app: Arc<App>,
+    rate_limiter: RateLimiter,
+    metrics: PrometheusHandle,
+}
+
+/// How often histogram samples are folded into the exported buckets
+const METRICS_UPKEEP_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Record metrics for `/metrics`; install before starting the app so nothing is missed
+pub fn install_metrics() -> anyhow::Result<PrometheusHandle> {
+    let metrics = PrometheusBuilder::new()
+        .set_buckets_for_metric(Matcher::Suffix("_seconds".to_string()), telemetry::LATENCY_BUCKETS)?
+        .set_buckets_for_metric(Matcher::Full(telemetry::TRADE_PNL_PERCENT.to_string()), telemetry::PNL_PERCENT_BUCKETS)?
+        .install_recorder()?;
+    telemetry::describe();
+
+    let upkeep = metrics.clone();
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(METRICS_UPKEEP_INTERVAL);
+        loop {
+            ticker.tick().await;
+            upkeep.run_upkeep();
+        }
+    });
+    Ok(metrics)
+}
+
+/// Serve the API and dashboard until the process is stopped
+pub async fn serve(app: Arc<App>, metrics: PrometheusHandle) -> anyhow::Result<()> {
+    let server_settings = app.config.lock().unwrap().server_settings().clone();
+    
+    // Fail now rather than on the first request if a token's secret is missing
+    app.secrets.resolve_api_tokens(&server_settings.api_tokens)?;
+    if server_settings.api_tokens.is_empty() {
+        warn!("No API tokens configured, the API is open to anyone who can reach {}", server_settings.bind_address);
+    }
+    
+    app.scanner.listen();
+    let auto_start = app.config.lock().unwrap().config().auto_start;
+    if auto_start {
+        app.start_scanner().await?;
+    }
+    let _config_watcher = app.watch_config()?;
+    let app_state = Arc::new(AppState {
+        app,
+        rate_limiter: RateLimiter::default(),
+        metrics,
+    });
+    
+    // Build router with routes
+    let app = Router::new()
+        .route("/api/health", get(health_check))
+        .route("/metrics", get(render_metrics))
+        .route("/api/wallet/connect", post(connect_wallet))
+        .route("/api/wallet/disconnect", post(disconnect_wallet))
+        .route("/api/wallet/balance", get(get_balance))
+        .route("/api/snipe", post(snipe_token))
+        .route("/api/sell", post(sell_token))
+        .route("/api/scanner/start", post(start_scanner))
+        .route("/api/scanner/stop", post(stop_scanner))
+        .route("/api/scanner/opportunities", get(get_opportunities))
+        .route("/api/positions", get(get_positions))
+        .route("/api/trades", get(get_trades))
+        .route("/api/events", get(stream_events))
+        .route("/api/events/ws", get(stream_events_ws))
+        .route("/api/creators", get(list_creators))
+        .route("/api/creators/:address", get(get_creator).put(update_creator).delete(delete_creator))
+        .route("/api/config", get(get_config).post(update_config))
+        .route("/api/config/status", get(get_config_status))
+        .route("/api/config/versions", get(list_config_versions))
+        .route("/api/config/diff", get(diff_config_versions))
+        .route("/api/config/rollback/:version", post(rollback_config))
+        .route("/api/profiles", get(list_profiles))
+        .route("/api/profiles/:name/activate", post(activate_profile))
+        .fallback_service(ServeDir::new("public"))
+        .layer(middleware::from_fn_with_state(app_state.clone(), authorize))
+        .layer(cors_layer(&server_settings.cors_origins)?)
+        .with_state(app_state);
+    
+    // Start the server
+    let listener = TcpListener::bind(&server_settings.bind_address).await?;
+    info!("Server listening on http://{}", server_settings.bind_address);
+    axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>()).await?;
+    
+    Ok(())
+}
+
+/// CORS for the configured origins only; none means same-origin requests only
+fn cors_layer(origins: &[String]) -> anyhow::Result<CorsLayer> {
+    let cors = CorsLayer::new()
+        .allow_methods([Method::GET, Method::POST, Method::PUT, Method::DELETE])
+        .allow_headers([header::AUTHORIZATION, header::CONTENT_TYPE]);
+    
+    if origins.iter().any(|origin| origin == "*") {
+        warn!("CORS allows any origin");
+        return Ok(cors.allow_origin(Any));
+    }
+    
+    let origins = origins.iter()
+        .map(|origin| HeaderValue::from_str(origin.trim_end_matches('/')))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(cors.allow_origin(origins))
+}
+
+/// Requests allowed per caller in each one-minute window
+#[derive(Default)]
+struct RateLimiter {
+    windows: Mutex<HashMap<String, (Instant, u32)>>,
+}
+
+impl RateLimiter {
+    const WINDOW: Duration = Duration::from_secs(60);
+    
+    /// Count a request, or return how long until the caller may try again
+    fn check(&self, caller: &str, per_minute: u32) -> Result<(), Duration> {
+        self.check_at(caller, per_minute, Instant::now())
+    }
+    
+    fn check_at(&self, caller: &str, per_minute: u32, now: Instant) -> Result<(), Duration> {
+        let mut windows = self.windows.lock().unwrap();
+        windows.retain(|_, (started, _)| now.duration_since(*started) < Self::WINDOW);
+        
+        let (started, count) = windows.entry(caller.to_string()).or_insert((now, 0));
+        if *count >= per_minute {
+            return Err(Self::WINDOW - now.duration_since(*started));
+        }
+        *count += 1;
+        Ok(())
+    }
+}
+
+/// Token from `Authorization: Bearer`, or `access_token` for browser WebSocket and EventSource clients
+fn presented_token(request: &Request) -> Option<String> {
+    let bearer = request.headers().get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(|token| token.trim().to_string());
+    
+    bearer.or_else(|| {
+        url::form_urlencoded::parse(request.uri().query()?.as_bytes())
+            .find(|(key, _)| key == "access_token")
+            .map(|(_, token)| token.into_owned())
+    })
+}
+
+/// Compare without leaking how much of a token matched through timing
+fn tokens_match(expected: &str, presented: &str) -> bool {
+    expected.len() == presented.len()
+        && expected.bytes().zip(presented.bytes()).fold(0, |diff, (a, b)| diff | (a ^ b)) == 0
+}
+
+/// JSON error body: a readable `error` and a machine-readable `code`
+fn error_response(status: StatusCode, code: &str, error: &str) -> Response {
+    (status, Json(serde_json::json!({
+        "error": error,
+        "code": code
+    }))).into_response()
+}
+
+/// Respond to a failed operation; a `SniperError` picks the status and code
+///
+/// The message is redacted, as RPC errors can carry the endpoint's API key.
+fn api_error(state: &AppState, error: anyhow::Error) -> Response {
+    let message = state.app.secrets.redact(&error.to_string());
+    match error.downcast_ref::<SniperError>() {
+        Some(error) => error_response(error_status(error), error.code(), &message),
+        None => error_response(StatusCode::INTERNAL_SERVER_ERROR, "internal", &message),
+    }
+}
+
+fn error_status(error: &SniperError) -> StatusCode {
+    match error {
+        SniperError::InvalidRequest(_) => StatusCode::BAD_REQUEST,
+        SniperError::NotFound(_) => StatusCode::NOT_FOUND,
+        SniperError::SlippageExceeded => StatusCode::CONFLICT,
+        SniperError::WalletLocked => StatusCode::LOCKED,
+        SniperError::InsufficientBalance { .. }
+        | SniperError::FilterRejected(_)
+        | SniperError::TransactionFailed(_) => StatusCode::UNPROCESSABLE_ENTITY,
+        SniperError::LimitReached(_) => StatusCode::TOO_MANY_REQUESTS,
+        SniperError::RpcUnavailable(_) => StatusCode::BAD_GATEWAY,
+        SniperError::TradingHalted => StatusCode::SERVICE_UNAVAILABLE,
+        SniperError::BlockhashExpired => StatusCode::GATEWAY_TIMEOUT,
+        SniperError::Other(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
+/// Check the caller's token against the route's role, and rate limit changes
+///
+/// `GET` needs a read-only token, anything else a trader token; `/metrics`
+/// counts as a read. The health check and the static UI are public.
+async fn authorize(
+    State(state): State<Arc<AppState>>,
+    ConnectInfo(address): ConnectInfo<SocketAddr>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let path = request.uri().path();
+    if !(path.starts_with("/api/") || path == "/metrics") || path == "/api/health" {
+        return next.run(request).await;
+    }
+    
+    let mutating = !matches!(*request.method(), Method::GET | Method::HEAD);
+    let required = if mutating { ApiRole::Trader } else { ApiRole::ReadOnly };
+    let (tokens, per_minute) = {
+        let config_manager = state.app.config.lock().unwrap();
+        let settings = config_manager.server_settings();
+        (settings.api_tokens.clone(), settings.mutating_requests_per_minute)
+    };
+    
+    let caller = if tokens.is_empty() {
+        address.ip().to_string()
+    } else {
+        let Some(presented) = presented_token(&request) else {
+            return error_response(StatusCode::UNAUTHORIZED, "unauthorized", "API token required");
+        };
+        let tokens = match state.app.secrets.resolve_api_tokens(&tokens) {
+            Ok(tokens) => tokens,
+            Err(e) => {
+                warn!("API tokens cannot be checked: {}", e);
+                return error_response(StatusCode::INTERNAL_SERVER_ERROR, "internal", "API tokens cannot be checked");
+            }
+        };
+        let token = tokens.iter().find(|token| tokens_match(&token.token, &presented));
+        match token {
+            Some(token) if token.role >= required => token.name.clone(),
+            Some(token) => {
+                warn!("API token {} denied {} {}", token.name, request.method(), path);
+                return error_response(StatusCode::FORBIDDEN, "forbidden", "API token does not allow this action");
+            }
+            None => return error_response(StatusCode::UNAUTHORIZED, "unauthorized", "Invalid API token"),
+        }
+    };
+    
+    if mutating {
+        if let Err(retry_after) = state.rate_limiter.check(&caller, per_minute) {
+            let mut response = error_response(StatusCode::TOO_MANY_REQUESTS, "rate_limited", "Rate limit exceeded");
+            response.headers_mut().insert(header::RETRY_AFTER, HeaderValue::from(retry_after.as_secs().max(1)));
+            return response;
+        }
+    }
+    
+    next.run(request).await
+}
+
+/// JSON response body with every known secret masked
+fn redacted(state: &AppState, value: impl Serialize) -> Json<serde_json::Value> {
+    let mut value = serde_json::to_value(value).unwrap_or_default();
+    state.app.secrets.redact_json(&mut value);
+    Json(value)
+}
+
+// API route handlers
+
+async fn health_check() -> &'static str {
+    "Wolf63x Solana Sniper Bot is running"
+}
+
+/// Prometheus text format
+async fn render_metrics(
+    State(state): State<Arc<AppState>>,
+) -> impl IntoResponse {
+    ([(header::CONTENT_TYPE, "text/plain; version=0.0.4")], state.metrics.render())
+}
+
+#[derive(Deserialize)]
+struct WalletConnectRequest {
+    wallet_type: String,
+}
+
+#[derive(Serialize)]
+struct WalletConnectResponse {
+    address: String,
+    balance: f64,
+}
+
+async fn connect_wallet(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<WalletConnectRequest>,
+) -> Response {
+    match state.app.wallet.connect(&request.wallet_type).await {
+        Ok((address, balance, session)) => {
+            // Trades are signed by the connected wallet
+            state.app.trader.set_wallet(session);
+            
+            // The connected wallet pays for honeypot simulations
+            if let Err(e) = state.app.scanner.set_simulation_payer(&address).await {
+                warn!("Honeypot simulation disabled: {}", e);
+            }
+            
+            let response = WalletConnectResponse {
+                address,
+                balance,
+            };
+            
+            (StatusCode::OK, Json(serde_json::json!(response))).into_response()
+        },
+        Err(e) => api_error(&state, e),
+    }
+}
+
+async fn disconnect_wallet(
+    State(state): State<Arc<AppState>>,
+) -> Response {
+    match state.app.wallet.disconnect().await {
+        Ok(_) => {
+            state.app.trader.set_wallet(Wallet::new());
+            
+            (StatusCode::OK, Json(serde_json::json!({
+                "success": true
+            }))).into_response()
+        },
+        Err(e) => api_error(&state, e),
+    }
+}
+
+async fn get_balance(
+    State(state): State<Arc<AppState>>,
+) -> Response {
+    match state.app.wallet.balance().await {
+        Ok(balance) => {
+            (StatusCode::OK, Json(serde_json::json!({
+                "balance": balance
+            }))).into_response()
+        },
+        Err(e) => api_error(&state, e),
+    }
+}
+
+#[derive(Deserialize)]
+struct SnipeRequest {
+    token_address: String,
+    amount: f64,
+    slippage: f64,
+    /// Profile to trade under; defaults to the one the token was routed to, then the active one
+    #[serde(default)]
+    profile: Option<String>,
+}
+
+async fn snipe_token(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<SnipeRequest>,
+) -> Response {
+    let opportunity = state.app.scanner.opportunity(&request.token_address);
+    let profile = match state.app.resolve_profile(request.profile.as_deref(), opportunity.as_ref()) {
+        Ok(profile) => profile,
+        Err(e) => return api_error(&state, e),
+    };
+    
+    match state.app.buy(&request.token_address, request.amount, request.slippage, profile).await {
+        Ok(signature) => {
+            (StatusCode::OK, Json(serde_json::json!({
+                "signature": signature
+            }))).into_response()
+        },
+        Err(e) => api_error(&state, e),
+    }
+}
+
+#[derive(Deserialize)]
+struct SellRequest {
+    token_address: String,
+    amount: f64,
+    slippage: f64,
+}
+
+async fn sell_token(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<SellRequest>,
+) -> Response {
+    match state.app.sell(&request.token_address, request.amount, request.slippage).await {
+        Ok(signature) => {
+            (StatusCode::OK, Json(serde_json::json!({
+                "signature": signature
+            }))).into_response()
+        },
+        Err(e) => api_error(&state, e),
+    }
+}
+
+async fn start_scanner(
+    State(state): State<Arc<AppState>>,
+) -> Response {
+    match state.app.scanner.start().await {
+        Ok(_) => {
+            (StatusCode::OK, Json(serde_json::json!({
+                "success": true
+            }))).into_response()
+        },
+        Err(e) => api_error(&state, e),
+    }
+}
+
+async fn stop_scanner(
+    State(state): State<Arc<AppState>>,
+) -> Response {
+    match state.app.scanner.stop().await {
+        Ok(_) => {
+            (StatusCode::OK, Json(serde_json::json!({
+                "success": true
+            }))).into_response()
+        },
+        Err(e) => api_error(&state, e),
+    }
+}
+
+async fn get_opportunities(
+    State(state): State<Arc<AppState>>,
+) -> impl IntoResponse {
+    (StatusCode::OK, Json(serde_json::json!(state.app.scanner.opportunities())))
+}
+
+async fn get_positions(
+    State(state): State<Arc<AppState>>,
+) -> Response {
+    match state.app.positions.list().await {
+        Ok(positions) => {
+            (StatusCode::OK, Json(serde_json::json!(positions))).into_response()
+        },
+        Err(e) => api_error(&state, e),
+    }
+}
+
+async fn get_trades(
+    State(state): State<Arc<AppState>>,
+) -> Response {
+    match state.app.logger.logs().await {
+        Ok(logs) => {
+            (StatusCode::OK, Json(serde_json::json!(logs))).into_response()
+        },
+        Err(e) => api_error(&state, e),
+    }
+}
+
+#[derive(Deserialize)]
+struct EventStreamQuery {
+    /// Comma-separated topics, all when omitted
+    #[serde(default)]
+    topics: Option<String>,
+    /// Replay events after this sequence number
+    #[serde(default)]
+    since: Option<u64>,
+}
+
+impl EventStreamQuery {
+    fn topics(&self) -> anyhow::Result<HashSet<Topic>> {
+        self.topics.as_deref()
+            .map(|topics| topics.split(',').filter(|t| !t.trim().is_empty()).map(str::parse).collect())
+            .unwrap_or_else(|| Ok(HashSet::new()))
+    }
+}
+
+fn bad_request(error: impl ToString) -> Response {
+    error_response(StatusCode::BAD_REQUEST, "invalid_request", &error.to_string())
+}
+
+/// Sent as a `reset` event to clients resuming from an event id of an earlier run
+const STALE_EVENT_ID: &str = "Events since the given id are unknown, fetch the current state again";
+
+/// Server-Sent Events; a reconnecting browser resumes from its `Last-Event-ID`
+async fn stream_events(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<EventStreamQuery>,
+    headers: HeaderMap,
+) -> Response {
+    let topics = match query.topics() {
+        Ok(topics) => topics,
+        Err(e) => return bad_request(e),
+    };
+    let since = query.since.or_else(|| {
+        headers.get("last-event-id")
+            .and_then(|id| id.to_str().ok())
+            .and_then(|id| id.parse().ok())
+    });
+    
+    let subscription = state.app.events.subscribe(topics, since);
+    // Ids from before a restart cannot be resumed from; the browser has to refetch
+    let reset = subscription.is_stale().then(|| Ok(sse::Event::default().event("reset").data(STALE_EVENT_ID)));
+    let stream = futures::stream::unfold(subscription, |mut subscription| async move {
+        let event = subscription.next().await?;
+        let message = sse::Event::default()
+            .id(event.seq.to_string())
+            .event(event.event.name())
+            .json_data(&event)
+            .unwrap_or_else(|e| sse::Event::default().comment(e.to_string()));
+        Some((Ok::<_, Infallible>(message), subscription))
+    });
+    
+    Sse::new(futures::stream::iter(reset).chain(stream)).keep_alive(KeepAlive::default()).into_response()
+}
+
+/// Topic changes sent by a WebSocket client
+#[derive(Deserialize)]
+struct EventStreamCommand {
+    #[serde(default)]
+    subscribe: Vec<Topic>,
+    #[serde(default)]
+    unsubscribe: Vec<Topic>,
+}
+
+/// WebSocket stream of events; clients change topics by sending `{"subscribe": [...], "unsubscribe": [...]}`
+async fn stream_events_ws(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<EventStreamQuery>,
+    ws: WebSocketUpgrade,
+) -> Response {
+    let topics = match query.topics() {
+        Ok(topics) => topics,
+        Err(e) => return bad_request(e),
+    };
+    let subscription = state.app.events.subscribe(topics, query.since);
+    
+    ws.on_upgrade(move |socket| forward_events(socket, subscription))
+}
+
+async fn forward_events(mut socket: WebSocket, mut subscription: Subscription) {
+    if subscription.is_stale() {
+        let reset = serde_json::json!({ "type": "reset", "data": STALE_EVENT_ID });
+        if socket.send(Message::Text(reset.to_string())).await.is_err() {
+            return;
+        }
+    }
+    
+    loop {
+        tokio::select! {
+            event = subscription.next() => {
+                let Some(event) = event else { break };
+                if send_event(&mut socket, &event).await.is_err() {
+                    break;
+                }
+            }
+            message = socket.recv() => match message {
+                Some(Ok(Message::Text(text))) => match serde_json::from_str::<EventStreamCommand>(&text) {
+                    Ok(command) => {
+                        subscription.subscribe(command.subscribe);
+                        subscription.unsubscribe(command.unsubscribe);
+                    }
+                    Err(e) => {
+                        let error = serde_json::json!({ "error": format!("Invalid command: {}", e), "code": "invalid_request" });
+                        if socket.send(Message::Text(error.to_string())).await.is_err() {
+                            break;
+                        }
+                    }
+                },
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => {}
+            }
+        }
+    }
+}
+
+async fn send_event(socket: &mut WebSocket, event: &SequencedEvent) -> anyhow::Result<()> {
+    socket.send(Message::Text(serde_json::to_string(event)?)).await?;
+    Ok(())
+}
+
+async fn get_config(
+    State(state): State<Arc<AppState>>,
+) -> impl IntoResponse {
+    let config = state.app.config.lock().unwrap().config();
+    
+    (StatusCode::OK, redacted(&state, config))
+}
+
+async fn update_config(
+    State(state): State<Arc<AppState>>,
+    Json(config): Json<BotConfig>,
+) -> Response {
+    let updated = state.app.config.lock().unwrap().set_config(config.clone());
+    
+    match updated {
+        Ok(_) => {
+            if let Err(e) = state.app.apply_config(&config) {
+                warn!("Configuration saved but not applied: {}", e);
+            }
+            
+            (StatusCode::OK, Json(serde_json::json!({
+                "success": true
+            }))).into_response()
+        },
+        Err(e) => api_error(&state, e),
+    }
+}
+
+async fn get_config_status(
+    State(state): State<Arc<AppState>>,
+) -> impl IntoResponse {
+    let config_manager = state.app.config.lock().unwrap();
+    
+    (StatusCode::OK, redacted(&state, serde_json::json!({
+        "path": config_manager.source_path().map(|path| path.display().to_string()),
+        "last_reload_error": config_manager.last_reload_error(),
+    })))
+}
+
+async fn list_config_versions(
+    State(state): State<Arc<AppState>>,
+) -> Response {
+    let config_manager = state.app.config.lock().unwrap();
+    
+    match config_manager.versions() {
+        Ok(versions) => {
+            (StatusCode::OK, Json(serde_json::json!(versions))).into_response()
+        },
+        Err(e) => api_error(&state, e),
+    }
+}
+
+#[derive(Deserialize)]
+struct ConfigDiffQuery {
+    from: u64,
+    to: u64,
+}
+
+async fn diff_config_versions(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<ConfigDiffQuery>,
+) -> Response {
+    let config_manager = state.app.config.lock().unwrap();
+    
+    match config_manager.diff(query.from, query.to) {
+        Ok(changes) => {
+            (StatusCode::OK, redacted(&state, changes)).into_response()
+        },
+        Err(e) => api_error(&state, e),
+    }
+}
+
+async fn rollback_config(
+    State(state): State<Arc<AppState>>,
+    Path(version): Path<u64>,
+) -> Response {
+    let rolled_back = state.app.config.lock().unwrap().rollback(version);
+    
+    match rolled_back {
+        Ok(config) => {
+            if let Err(e) = state.app.apply_config(&config) {
+                warn!("Configuration saved but not applied: {}", e);
+            }
+            
+            (StatusCode::OK, Json(serde_json::json!({
+                "success": true
+            }))).into_response()
+        },
+        Err(e) => api_error(&state, e),
+    }
+}
+
+async fn list_profiles(
+    State(state): State<Arc<AppState>>,
+) -> impl IntoResponse {
+    let config = state.app.config.lock().unwrap().config();
+    
+    (StatusCode::OK, redacted(&state, serde_json::json!({
+        "profiles": config.profiles,
+        "routes": config.profile_routes,
+        "active": config.active_profile,
+    })))
+}
+
+async fn activate_profile(
+    State(state): State<Arc<AppState>>,
+    Path(name): Path<String>,
+) -> Response {
+    let activated = state.app.config.lock().unwrap().activate_profile(&name);
+    
+    match activated {
+        Ok(config) => {
+            if let Err(e) = state.app.apply_config(&config) {
+                warn!("Configuration saved but not applied: {}", e);
+            }
+            
+            (StatusCode::OK, Json(serde_json::json!({
+                "success": true,
+                "active": name
+            }))).into_response()
+        },
+        Err(e) => api_error(&state, e),
+    }
+}
+
+async fn list_creators(
+    State(state): State<Arc<AppState>>,
+) -> impl IntoResponse {
+    let reputation = state.app.reputation.lock().unwrap();
+    
+    (StatusCode::OK, Json(serde_json::json!(reputation.list())))
+}
+
+async fn get_creator(
+    State(state): State<Arc<AppState>>,
+    Path(address): Path<String>,
+) -> Response {
+    let reputation = state.app.reputation.lock().unwrap();
+    
+    match reputation.get(&address) {
+        Some(record) => {
+            (StatusCode::OK, Json(serde_json::json!(record))).into_response()
+        },
+        None => error_response(StatusCode::NOT_FOUND, "not_found", "Creator not found"),
+    }
+}
+
+#[derive(Deserialize)]
+struct CreatorUpdateRequest {
+    blacklisted: bool,
+    note: Option<String>,
+}
+
+async fn update_creator(
+    State(state): State<Arc<AppState>>,
+    Path(address): Path<String>,
+    Json(request): Json<CreatorUpdateRequest>,
+) -> impl IntoResponse {
+    let mut reputation = state.app.reputation.lock().unwrap();
+    reputation.set_blacklisted(&address, request.blacklisted, request.note);
+    
+    (StatusCode::OK, Json(serde_json::json!({
+        "success": true
+    })))
+}
+
+async fn delete_creator(
+    State(state): State<Arc<AppState>>,
+    Path(address): Path<String>,
+) -> Response {
+    let mut reputation = state.app.reputation.lock().unwrap();
+    
+    if reputation.remove(&address) {
+        (StatusCode::OK, Json(serde_json::json!({
+            "success": true
+        }))).into_response()
+    } else {
+        error_response(StatusCode::NOT_FOUND, "not_found", "Creator not found")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn typed_errors_map_to_statuses() {
+        let failed = SniperError::TransactionFailed("InstructionError(2, Custom(6001))".to_string());
+        assert_eq!(error_status(&failed), StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(failed.code(), "transaction_failed");
+
+        assert_eq!(error_status(&SniperError::InvalidRequest("bad".into())), StatusCode::BAD_REQUEST);
+        assert_eq!(error_status(&SniperError::NotFound("No config version 7".into())), StatusCode::NOT_FOUND);
+        assert_eq!(error_status(&SniperError::WalletLocked), StatusCode::LOCKED);
+        assert_eq!(error_status(&SniperError::RpcUnavailable("timeout".into())), StatusCode::BAD_GATEWAY);
+        assert_eq!(error_status(&SniperError::BlockhashExpired), StatusCode::GATEWAY_TIMEOUT);
+    }
+
+    #[test]
+    fn rate_limit_is_per_caller_and_window() {
+        let limiter = RateLimiter::default();
+        let start = Instant::now();
+
+        assert!(limiter.check_at("dashboard", 2, start).is_ok());
+        assert!(limiter.check_at("dashboard", 2, start).is_ok());
+        let retry_after = limiter.check_at("dashboard", 2, start + Duration::from_secs(20)).unwrap_err();
+        assert_eq!(retry_after, Duration::from_secs(40));
+
+        // Other callers have their own window
+        assert!(limiter.check_at("bot", 2, start + Duration::from_secs(20)).is_ok());
+
+        // A new window opens once the old one has passed
+        assert!(limiter.check_at("dashboard", 2, start + RateLimiter::WINDOW).is_ok());
+    }
+
+    #[test]
+    fn zero_per_minute_refuses_everything() {
+        assert!(RateLimiter::default().check("dashboard", 0).is_err());
+    }
+
+    #[test]
+    fn tokens_match_only_when_identical() {
+        assert!(tokens_match("secret-token", "secret-token"));
+        assert!(!tokens_match("secret-token", "secret-tokem"));
+        assert!(!tokens_match("secret-token", "secret-token2"));
+        assert!(!tokens_match("secret-token", "secret"));
+        assert!(!tokens_match("secret-token", ""));
+    }
+
+    #[test]
+    fn token_is_read_from_bearer_header_or_query() {
+        let request = Request::builder()
+            .header(header::AUTHORIZATION, "Bearer  abc123 ")
+            .body(axum::body::Body::empty())
+            .unwrap();
+        assert_eq!(presented_token(&request).as_deref(), Some("abc123"));
+
+        let request = Request::builder()
+            .uri("/api/events?topics=orders&access_token=a%2Bb")
+            .body(axum::body::Body::empty())
+            .unwrap();
+        assert_eq!(presented_token(&request).as_deref(), Some("a+b"));
+
+        let request = Request::builder().uri("/api/events").body(axum::body::Body::empty()).unwrap();
+        assert_eq!(presented_token(&request), None);
+    }
+}
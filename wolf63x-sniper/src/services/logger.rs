@@ -0,0 +1,179 @@
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use anyhow::Result;
+use metrics::gauge;
+use tokio::sync::{mpsc, oneshot};
+use wolf63x_core::logger::{Logger, TradeLog};
+use wolf63x_core::telemetry;
+use super::request;
+use super::shared_file::{self, FileLock};
+
+const SERVICE: &str = "Logger";
+
+/// How often trades logged by other processes are picked up
+const REFRESH_INTERVAL: Duration = Duration::from_secs(5);
+
+enum Command {
+    Record(Box<TradeLog>),
+    Logs {
+        reply: oneshot::Sender<Vec<TradeLog>>,
+    },
+}
+
+/// Handle to the task keeping the trade log
+#[derive(Clone)]
+pub struct LoggerService {
+    commands: mpsc::UnboundedSender<Command>,
+}
+
+impl LoggerService {
+    /// Keep the trade log, and the realized PnL gauge derived from it
+    ///
+    /// With a `path`, trades are kept in that file, so they survive restarts
+    /// and trades made by other processes, such as CLI commands, are counted.
+    pub fn spawn(path: Option<PathBuf>) -> Result<Self> {
+        let trades = match &path {
+            Some(path) => shared_file::load(path)?.unwrap_or_default(),
+            None => Vec::new(),
+        };
+        let (commands, receiver) = mpsc::unbounded_channel();
+        tokio::spawn(run(TradeBook::new(trades, path), receiver));
+        Ok(Self { commands })
+    }
+
+    pub fn record(&self, trade_log: TradeLog) {
+        let _ = self.commands.send(Command::Record(Box::new(trade_log)));
+    }
+
+    pub async fn logs(&self) -> Result<Vec<TradeLog>> {
+        request(&self.commands, SERVICE, |reply| Command::Logs { reply }).await
+    }
+}
+
+/// The trade log, kept in step with the shared file when there is one
+struct TradeBook {
+    logger: Logger,
+    path: Option<PathBuf>,
+    /// Last write to the file this process has seen
+    seen: Option<SystemTime>,
+}
+
+impl TradeBook {
+    fn new(trades: Vec<TradeLog>, path: Option<PathBuf>) -> Self {
+        let seen = path.as_deref().and_then(shared_file::modified);
+        let book = Self { logger: logger_of(trades), path, seen };
+        book.publish_pnl();
+        book
+    }
+
+    /// Add a trade, through the file when there is one so other processes' trades are kept
+    fn record(&mut self, trade_log: TradeLog) {
+        let Some(path) = self.path.clone() else {
+            self.logger.record(trade_log);
+            self.publish_pnl();
+            return;
+        };
+        let _lock = FileLock::acquire(&path)
+            .inspect_err(|e| log::warn!("Trade logged without the file lock: {}", e));
+
+        self.reload(&path);
+        self.logger.record(trade_log);
+        if let Err(e) = shared_file::save(&path, &self.logger.trade_logs()) {
+            log::warn!("Failed to save the trade log to {}: {}", path.display(), e);
+        }
+        self.seen = shared_file::modified(&path);
+        self.publish_pnl();
+    }
+
+    /// Reload the file if another process wrote to it
+    fn refresh(&mut self) {
+        let Some(path) = self.path.clone() else { return };
+        let modified = shared_file::modified(&path);
+        if modified != self.seen {
+            self.reload(&path);
+            self.seen = modified;
+            self.publish_pnl();
+        }
+    }
+
+    fn reload(&mut self, path: &Path) {
+        match shared_file::load::<Vec<TradeLog>>(path) {
+            Ok(Some(trades)) => self.logger = logger_of(trades),
+            Ok(None) => {}
+            Err(e) => log::warn!("Failed to reload the trade log: {}", e),
+        }
+    }
+
+    fn publish_pnl(&self) {
+        gauge!(telemetry::REALIZED_PNL_SOL).set(self.logger.realized_pnl());
+    }
+}
+
+fn logger_of(trades: Vec<TradeLog>) -> Logger {
+    let mut logger = Logger::new();
+    for trade in trades {
+        logger.record(trade);
+    }
+    logger
+}
+
+async fn run(mut book: TradeBook, mut commands: mpsc::UnboundedReceiver<Command>) {
+    let mut ticker = tokio::time::interval(REFRESH_INTERVAL);
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+    loop {
+        tokio::select! {
+            command = commands.recv() => match command {
+                Some(Command::Record(trade_log)) => book.record(*trade_log),
+                Some(Command::Logs { reply }) => {
+                    book.refresh();
+                    let _ = reply.send(book.logger.trade_logs().to_vec());
+                }
+                None => break,
+            },
+            _ = ticker.tick() => book.refresh(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use wolf63x_core::logger::TradeType;
+
+    fn sell(id: &str, profit_loss: f64) -> TradeLog {
+        TradeLog {
+            id: id.to_string(),
+            token_address: "mint".to_string(),
+            token_symbol: "MINT".to_string(),
+            token_name: String::new(),
+            trade_type: TradeType::Sell,
+            amount_in: 1.0,
+            amount_out: 0.0,
+            price: 0.0,
+            timestamp: Utc::now(),
+            tx_signature: id.to_string(),
+            profit_loss: Some(profit_loss),
+            profit_loss_percent: None,
+            time_held: None,
+            profile: None,
+        }
+    }
+
+    #[test]
+    fn trades_logged_by_other_processes_are_kept() {
+        let path = std::env::temp_dir().join(format!("wolf63x-trades-{}.json", std::process::id()));
+        let mut server = TradeBook::new(Vec::new(), Some(path.clone()));
+        let mut cli = TradeBook::new(Vec::new(), Some(path.clone()));
+
+        server.record(sell("a", 0.5));
+        cli.record(sell("b", -0.2));
+        server.record(sell("c", 0.1));
+        std::fs::remove_file(&path).unwrap();
+
+        let ids: Vec<&str> = server.logger.trade_logs().iter().map(|trade| trade.id.as_str()).collect();
+        assert_eq!(ids, ["a", "b", "c"]);
+        assert!((server.logger.realized_pnl() - 0.4).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,38 @@
+//! Async service actors owning the bot's core components
+//!
+//! Each service runs as its own task and takes commands over a channel, so
+//! handlers never hold a lock across `.await` and a slow call on one service
+//! cannot stall the others.
+
+pub mod wallet;
+pub mod trader;
+pub mod scanner;
+pub mod positions;
+pub mod logger;
+pub mod notifier;
+mod shared_file;
+
+pub use wallet::WalletService;
+pub use trader::TraderService;
+pub use scanner::ScannerService;
+pub use positions::{Position, PositionService};
+pub use logger::LoggerService;
+pub use notifier::NotifierService;
+
+use anyhow::anyhow;
+use tokio::sync::{mpsc, oneshot};
+
+/// Send a command carrying a reply channel and wait for the service to answer
+async fn request<C, R>(
+    commands: &mpsc::UnboundedSender<C>,
+    service: &str,
+    command: impl FnOnce(oneshot::Sender<R>) -> C,
+) -> anyhow::Result<R> {
+    let (reply, response) = oneshot::channel();
+    commands.send(command(reply)).map_err(|_| stopped(service))?;
+    response.await.map_err(|_| stopped(service))
+}
+
+fn stopped(service: &str) -> anyhow::Error {
+    anyhow!("{} service has stopped", service)
+}
@@ -0,0 +1,82 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+use log::warn;
+use tokio::sync::mpsc;
+use wolf63x_core::{
+    config::NotificationSettings,
+    events::{Event, EventBus, Topic},
+    logger::TradeType,
+};
+
+/// Handle to the task posting trades and exits to the notification webhook
+#[derive(Clone)]
+pub struct NotifierService {
+    settings: mpsc::UnboundedSender<NotificationSettings>,
+}
+
+impl NotifierService {
+    pub fn spawn(events: Arc<EventBus>) -> Self {
+        let (settings, receiver) = mpsc::unbounded_channel();
+        tokio::spawn(run(events, receiver));
+        Self { settings }
+    }
+
+    /// Notification settings from the configuration, with the webhook's secret already resolved
+    pub fn apply_settings(&self, settings: &NotificationSettings) {
+        let _ = self.settings.send(settings.clone());
+    }
+}
+
+async fn run(events: Arc<EventBus>, mut updates: mpsc::UnboundedReceiver<NotificationSettings>) {
+    let client = reqwest::Client::new();
+    let mut settings: Option<NotificationSettings> = None;
+    let mut subscription = events.subscribe(HashSet::from([Topic::Orders, Topic::Positions]), None);
+
+    loop {
+        tokio::select! {
+            update = updates.recv() => match update {
+                Some(update) => settings = Some(update),
+                None => break,
+            },
+            event = subscription.next() => {
+                let Some(event) = event else { break };
+                let Some(settings) = &settings else { continue };
+                let (Some(webhook_url), Some(text)) = (&settings.webhook_url, message(settings, &event.event)) else {
+                    continue;
+                };
+
+                // Discord reads `content`, Slack reads `text`
+                let body = serde_json::json!({ "content": text, "text": text });
+                match client.post(webhook_url).json(&body).send().await.and_then(|response| response.error_for_status()) {
+                    Ok(_) => {}
+                    // Without the URL, which is itself the webhook's credential
+                    Err(e) => warn!("Notification not delivered: {}", e.without_url()),
+                }
+            }
+        }
+    }
+}
+
+/// Text to post for an event, `None` when the settings leave it out
+fn message(settings: &NotificationSettings, event: &Event) -> Option<String> {
+    match event {
+        Event::OrderConfirmed { token_address, side, signature } if settings.notify_on_trade => {
+            Some(format!("{} {} confirmed: {}", side_name(*side), token_address, signature))
+        }
+        Event::OrderFailed { token_address, side, error } if settings.notify_on_trade => {
+            Some(format!("{} {} failed: {}", side_name(*side), token_address, error))
+        }
+        Event::ExitTriggered { token_address, trigger, change_percent, .. } => {
+            let wanted = if *change_percent >= 0.0 { settings.notify_on_profit } else { settings.notify_on_loss };
+            wanted.then(|| format!("{:?} hit for {} at {:+.1}%", trigger, token_address, change_percent))
+        }
+        _ => None,
+    }
+}
+
+fn side_name(side: TradeType) -> &'static str {
+    match side {
+        TradeType::Buy => "Buy",
+        TradeType::Sell => "Sell",
+    }
+}
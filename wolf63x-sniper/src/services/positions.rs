@@ -0,0 +1,438 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Serialize, Deserialize};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+use tokio::sync::{mpsc, oneshot};
+use wolf63x_core::{
+    events::{Event, EventBus, ExitTrigger},
+    profiles::ExitPlan,
+    pump_fun,
+    telemetry,
+};
+use super::request;
+use super::shared_file::{self, FileLock};
+
+const SERVICE: &str = "Positions";
+
+/// How often open positions are re-priced from their bonding curves and checked against their exit plans
+const PRICE_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// An open buy, kept until the token is sold
+///
+/// Prices are in SOL per token, read from the token's bonding curve whether
+/// or not the scanner still tracks it. A position opened without a price
+/// takes the first one read as its entry.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Position {
+    pub token_address: String,
+    pub token_symbol: String,
+    /// Profile the buy was made under
+    pub profile: Option<String>,
+    pub amount_sol: f64,
+    pub entry_price: f64,
+    pub opened_at: DateTime<Utc>,
+    pub exit_plan: Option<ExitPlan>,
+    pub last_price: f64,
+    /// Highest price since entry, for the trailing stop
+    #[serde(default)]
+    pub high_price: f64,
+    /// Exit rule that fired, reported once per position
+    pub exit_triggered: Option<ExitTrigger>,
+}
+
+impl Position {
+    /// Price change since entry, in percent
+    pub fn change_percent(&self) -> f64 {
+        self.pnl_at(self.last_price).1
+    }
+
+    /// Profit or loss if sold at `price`, in SOL and in percent
+    pub fn pnl_at(&self, price: f64) -> (f64, f64) {
+        if self.entry_price <= 0.0 {
+            return (0.0, 0.0);
+        }
+        let percent = (price - self.entry_price) / self.entry_price * 100.0;
+        (self.amount_sol * percent / 100.0, percent)
+    }
+
+    /// First exit rule of the plan the position crosses at `now`
+    fn exit_trigger(&self, now: DateTime<Utc>) -> Option<ExitTrigger> {
+        let plan = self.exit_plan.as_ref()?;
+        let change = self.change_percent();
+        let from_high = if self.high_price > 0.0 {
+            (self.last_price - self.high_price) / self.high_price * 100.0
+        } else {
+            0.0
+        };
+        let held = now - self.opened_at;
+
+        if change >= plan.take_profit_percent {
+            Some(ExitTrigger::TakeProfit)
+        } else if change <= -plan.stop_loss_percent {
+            Some(ExitTrigger::StopLoss)
+        } else if plan.trailing_stop_percent.is_some_and(|trailing| from_high <= -trailing) {
+            Some(ExitTrigger::TrailingStop)
+        } else if plan.max_hold_minutes.is_some_and(|minutes| held >= chrono::Duration::minutes(minutes as i64)) {
+            Some(ExitTrigger::MaxHold)
+        } else {
+            None
+        }
+    }
+}
+
+enum Command {
+    Open(Box<Position>),
+    Take {
+        token_address: String,
+        percent: f64,
+        reply: oneshot::Sender<Option<Position>>,
+    },
+    List {
+        reply: oneshot::Sender<Vec<Position>>,
+    },
+    ClearExit(String),
+    SetRpcUrl(String),
+}
+
+/// Handle to the task tracking open positions
+#[derive(Clone)]
+pub struct PositionService {
+    commands: mpsc::UnboundedSender<Command>,
+}
+
+impl PositionService {
+    /// Track positions, publishing price changes and exits on `events`
+    ///
+    /// With a `path`, positions are kept in that file, so they survive
+    /// restarts and are shared with the other processes using it, such as
+    /// CLI commands. Prices are read once an RPC endpoint is set.
+    pub fn spawn(events: Arc<EventBus>, path: Option<PathBuf>) -> Result<Self> {
+        let positions = match &path {
+            Some(path) => by_token(shared_file::load(path)?.unwrap_or_default()),
+            None => HashMap::new(),
+        };
+        let (commands, receiver) = mpsc::unbounded_channel();
+        tokio::spawn(run(Book::new(positions, path), events, receiver));
+        Ok(Self { commands })
+    }
+
+    /// Record a buy; buying a token already held adds to the position
+    pub fn open(&self, position: Position) {
+        let _ = self.commands.send(Command::Open(Box::new(position)));
+    }
+
+    /// Remove a percentage of a position, returning the part removed
+    pub async fn take(&self, token_address: &str, percent: f64) -> Result<Option<Position>> {
+        let token_address = token_address.to_string();
+        request(&self.commands, SERVICE, |reply| Command::Take { token_address, percent, reply }).await
+    }
+
+    pub async fn get(&self, token_address: &str) -> Result<Option<Position>> {
+        Ok(self.list().await?.into_iter().find(|position| position.token_address == token_address))
+    }
+
+    /// Open positions, oldest first
+    pub async fn list(&self) -> Result<Vec<Position>> {
+        request(&self.commands, SERVICE, |reply| Command::List { reply }).await
+    }
+
+    /// Let the position's exit rules fire again, e.g. after selling on them failed
+    pub fn clear_exit(&self, token_address: &str) {
+        let _ = self.commands.send(Command::ClearExit(token_address.to_string()));
+    }
+
+    /// Endpoint bonding curves are read from
+    pub fn set_rpc_url(&self, rpc_url: &str) {
+        let _ = self.commands.send(Command::SetRpcUrl(rpc_url.to_string()));
+    }
+}
+
+fn by_token(positions: Vec<Position>) -> HashMap<String, Position> {
+    positions.into_iter().map(|position| (position.token_address.clone(), position)).collect()
+}
+
+/// Open positions, kept in step with the shared file when there is one
+struct Book {
+    positions: HashMap<String, Position>,
+    path: Option<PathBuf>,
+    /// Last write to the file this process has seen
+    seen: Option<SystemTime>,
+}
+
+impl Book {
+    fn new(positions: HashMap<String, Position>, path: Option<PathBuf>) -> Self {
+        let seen = path.as_deref().and_then(shared_file::modified);
+        Self { positions, path, seen }
+    }
+
+    /// Apply a change, through the file when there is one
+    ///
+    /// The file is reloaded under its lock first, so positions opened or sold
+    /// by other processes are kept rather than overwritten.
+    fn commit<R>(&mut self, change: impl FnOnce(&mut HashMap<String, Position>) -> R) -> R {
+        let Some(path) = self.path.clone() else {
+            return change(&mut self.positions);
+        };
+        let _lock = FileLock::acquire(&path)
+            .inspect_err(|e| log::warn!("Positions changed without the file lock: {}", e));
+
+        self.reload(&path);
+        let result = change(&mut self.positions);
+
+        let mut saved: Vec<&Position> = self.positions.values().collect();
+        saved.sort_by_key(|position| position.opened_at);
+        if let Err(e) = shared_file::save(&path, &saved) {
+            log::warn!("Failed to save positions to {}: {}", path.display(), e);
+        }
+        self.seen = shared_file::modified(&path);
+        result
+    }
+
+    /// Reload the file if another process wrote to it
+    fn refresh(&mut self) {
+        let Some(path) = self.path.clone() else { return };
+        let modified = shared_file::modified(&path);
+        if modified != self.seen {
+            self.reload(&path);
+            self.seen = modified;
+        }
+    }
+
+    /// Take the saved positions as they are, keeping what only this process knows
+    ///
+    /// That is the live price, and an entry price or exit read from it that
+    /// has not been saved yet.
+    fn reload(&mut self, path: &Path) {
+        let mut saved = match shared_file::load::<Vec<Position>>(path) {
+            Ok(Some(saved)) => by_token(saved),
+            Ok(None) => return,
+            Err(e) => {
+                log::warn!("Failed to reload positions: {}", e);
+                return;
+            }
+        };
+
+        for (token_address, position) in &mut saved {
+            let Some(known) = self.positions.get(token_address) else { continue };
+            if known.last_price > 0.0 {
+                position.last_price = known.last_price;
+            }
+            position.high_price = position.high_price.max(known.high_price);
+            if position.entry_price <= 0.0 {
+                position.entry_price = known.entry_price;
+            }
+            position.exit_triggered = position.exit_triggered.or(known.exit_triggered);
+        }
+        self.positions = saved;
+    }
+}
+
+async fn run(mut book: Book, events: Arc<EventBus>, mut commands: mpsc::UnboundedReceiver<Command>) {
+    let mut ticker = tokio::time::interval(PRICE_CHECK_INTERVAL);
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+    // Curves are read off the actor, so a slow RPC call cannot hold up commands
+    let mut rpc: Option<Arc<RpcClient>> = None;
+    let (priced, mut prices) = mpsc::unbounded_channel::<Vec<(String, f64)>>();
+    let mut pricing = false;
+
+    loop {
+        // Whether an entry price or exit was read that the file does not have yet
+        let unsaved = tokio::select! {
+            command = commands.recv() => match command {
+                Some(Command::Open(position)) => {
+                    book.commit(|positions| {
+                        positions.entry(position.token_address.clone())
+                            .and_modify(|held| held.amount_sol += position.amount_sol)
+                            .or_insert(*position);
+                    });
+                    false
+                }
+                Some(Command::Take { token_address, percent, reply }) => {
+                    let taken = book.commit(|positions| take(positions, &token_address, percent));
+                    let _ = reply.send(taken);
+                    false
+                }
+                Some(Command::List { reply }) => {
+                    let mut open: Vec<Position> = book.positions.values().cloned().collect();
+                    open.sort_by_key(|position| position.opened_at);
+                    let _ = reply.send(open);
+                    false
+                }
+                Some(Command::ClearExit(token_address)) => {
+                    book.commit(|positions| {
+                        if let Some(position) = positions.get_mut(&token_address) {
+                            position.exit_triggered = None;
+                        }
+                    });
+                    false
+                }
+                Some(Command::SetRpcUrl(url)) => {
+                    rpc = Some(Arc::new(telemetry::rpc_client(url)));
+                    false
+                }
+                None => break,
+            },
+            Some(quotes) = prices.recv() => {
+                pricing = false;
+                let now = Utc::now();
+                let mut unsaved = false;
+                for (token_address, price) in quotes {
+                    // Sold while the price was being read
+                    let Some(position) = book.positions.get_mut(&token_address) else { continue };
+                    unsaved |= update_price(position, price, &events);
+                    unsaved |= check_exit(position, &events, now);
+                }
+                unsaved
+            }
+            _ = ticker.tick() => {
+                book.refresh();
+
+                if let (false, Some(rpc)) = (pricing, &rpc) {
+                    if !book.positions.is_empty() {
+                        pricing = true;
+                        tokio::spawn(fetch_prices(rpc.clone(), book.positions.keys().cloned().collect(), priced.clone()));
+                    }
+                }
+
+                // Checked on every tick, so the maximum hold time fires for tokens that stopped trading
+                let now = Utc::now();
+                let mut triggered = false;
+                for position in book.positions.values_mut() {
+                    triggered |= check_exit(position, &events, now);
+                }
+                triggered
+            }
+        };
+
+        if unsaved {
+            book.commit(|_| ());
+        }
+    }
+}
+
+/// Read the spot price of each token from its bonding curve
+///
+/// Tokens that migrated off their curve are left out and keep their last price.
+async fn fetch_prices(rpc: Arc<RpcClient>, tokens: Vec<String>, priced: mpsc::UnboundedSender<Vec<(String, f64)>>) {
+    let tokens: Vec<(String, Pubkey)> = tokens.into_iter()
+        .filter_map(|token| Pubkey::from_str(&token).ok().map(|mint| (token, mint)))
+        .collect();
+    let mints: Vec<Pubkey> = tokens.iter().map(|(_, mint)| *mint).collect();
+
+    let prices = match pump_fun::fetch_bonding_curves(&rpc, &mints).await {
+        Ok(curves) => tokens.into_iter()
+            .zip(curves)
+            .filter_map(|((token, _), curve)| curve.filter(|curve| !curve.complete).map(|curve| (token, curve.price_sol())))
+            .collect(),
+        Err(e) => {
+            log::debug!("Failed to price open positions: {}", e);
+            Vec::new()
+        }
+    };
+    let _ = priced.send(prices);
+}
+
+fn take(positions: &mut HashMap<String, Position>, token_address: &str, percent: f64) -> Option<Position> {
+    if percent >= 100.0 {
+        return positions.remove(token_address);
+    }
+
+    let held = positions.get_mut(token_address)?;
+    let mut taken = held.clone();
+    taken.amount_sol = held.amount_sol * percent.max(0.0) / 100.0;
+    held.amount_sol -= taken.amount_sol;
+    Some(taken)
+}
+
+/// Re-price a position, publishing the new price
+///
+/// Returns whether the price became the position's entry, which is worth
+/// saving; later prices are not.
+fn update_price(position: &mut Position, price: f64, events: &EventBus) -> bool {
+    if price <= 0.0 || price == position.last_price {
+        return false;
+    }
+
+    let entered = position.entry_price <= 0.0;
+    if entered {
+        position.entry_price = price;
+    }
+    position.last_price = price;
+    position.high_price = position.high_price.max(price);
+    events.publish(Event::PositionPrice {
+        token_address: position.token_address.clone(),
+        price,
+        change_percent: position.change_percent(),
+    });
+    entered
+}
+
+/// Publish the first exit rule a position crosses, once; returns whether a rule fired
+fn check_exit(position: &mut Position, events: &EventBus, now: DateTime<Utc>) -> bool {
+    if position.exit_triggered.is_some() {
+        return false;
+    }
+    let Some(trigger) = position.exit_trigger(now) else {
+        return false;
+    };
+
+    position.exit_triggered = Some(trigger);
+    events.publish(Event::ExitTriggered {
+        token_address: position.token_address.clone(),
+        trigger,
+        price: position.last_price,
+        change_percent: position.change_percent(),
+    });
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn position(token_address: &str, amount_sol: f64) -> Position {
+        Position {
+            token_address: token_address.to_string(),
+            token_symbol: String::new(),
+            profile: None,
+            amount_sol,
+            entry_price: 0.0,
+            opened_at: Utc::now(),
+            exit_plan: None,
+            last_price: 0.0,
+            high_price: 0.0,
+            exit_triggered: None,
+        }
+    }
+
+    #[test]
+    fn processes_sharing_the_file_keep_each_others_changes() {
+        let path = std::env::temp_dir().join(format!("wolf63x-positions-{}.json", std::process::id()));
+        let mut server = Book::new(HashMap::new(), Some(path.clone()));
+        let mut cli = Book::new(HashMap::new(), Some(path.clone()));
+
+        server.commit(|positions| positions.insert("a".to_string(), position("a", 1.0)));
+        cli.commit(|positions| positions.insert("b".to_string(), position("b", 2.0)));
+
+        // The server priced its position before noticing the CLI's buy
+        server.positions.get_mut("a").unwrap().last_price = 0.5;
+        server.commit(|positions| take(positions, "a", 50.0));
+
+        let mut held: Vec<(String, f64, f64)> = server.positions.values()
+            .map(|position| (position.token_address.clone(), position.amount_sol, position.last_price))
+            .collect();
+        held.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(held, vec![("a".to_string(), 0.5, 0.5), ("b".to_string(), 2.0, 0.0)]);
+
+        cli.reload(&path);
+        assert_eq!(cli.positions["a"].amount_sol, 0.5);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,101 @@
+use std::sync::{Arc, Mutex};
+use anyhow::Result;
+use tokio::sync::{mpsc, oneshot};
+use wolf63x_core::{
+    config::ScannerSettings,
+    scanner::{Scanner, TokenOpportunity},
+};
+use super::request;
+
+const SERVICE: &str = "Scanner";
+
+enum Command {
+    Listen,
+    Start {
+        reply: oneshot::Sender<Result<()>>,
+    },
+    Stop {
+        reply: oneshot::Sender<Result<()>>,
+    },
+    ApplySettings {
+        settings: ScannerSettings,
+        rpc_url: String,
+    },
+    SetSimulationPayer {
+        payer: String,
+        reply: oneshot::Sender<Result<()>>,
+    },
+}
+
+/// Handle to the task owning the scanner and its Pump.fun websocket
+#[derive(Clone)]
+pub struct ScannerService {
+    commands: mpsc::UnboundedSender<Command>,
+    /// Written by the scan loop and websocket, read here without going through the actor
+    opportunities: Arc<Mutex<Vec<TokenOpportunity>>>,
+}
+
+impl ScannerService {
+    pub fn spawn(scanner: Scanner) -> Self {
+        let (commands, receiver) = mpsc::unbounded_channel();
+        let opportunities = scanner.opportunities.clone();
+        tokio::spawn(run(scanner, receiver));
+        Self { commands, opportunities }
+    }
+
+    /// Listen for new Pump.fun pairs on the websocket
+    pub fn listen(&self) {
+        let _ = self.commands.send(Command::Listen);
+    }
+
+    pub async fn start(&self) -> Result<()> {
+        request(&self.commands, SERVICE, |reply| Command::Start { reply }).await?
+    }
+
+    pub async fn stop(&self) -> Result<()> {
+        request(&self.commands, SERVICE, |reply| Command::Stop { reply }).await?
+    }
+
+    pub fn apply_settings(&self, settings: &ScannerSettings, rpc_url: &str) {
+        let _ = self.commands.send(Command::ApplySettings {
+            settings: settings.clone(),
+            rpc_url: rpc_url.to_string(),
+        });
+    }
+
+    /// Wallet paying for honeypot simulations
+    pub async fn set_simulation_payer(&self, payer: &str) -> Result<()> {
+        let payer = payer.to_string();
+        request(&self.commands, SERVICE, |reply| Command::SetSimulationPayer { payer, reply }).await?
+    }
+
+    pub fn opportunities(&self) -> Vec<TokenOpportunity> {
+        self.opportunities.lock().unwrap().clone()
+    }
+
+    pub fn opportunity(&self, token_address: &str) -> Option<TokenOpportunity> {
+        self.opportunities.lock().unwrap()
+            .iter()
+            .find(|t| t.address == token_address)
+            .cloned()
+    }
+}
+
+async fn run(mut scanner: Scanner, mut commands: mpsc::UnboundedReceiver<Command>) {
+    while let Some(command) = commands.recv().await {
+        match command {
+            Command::Listen => scanner.start_pump_fun_ws().await,
+            Command::Start { reply } => {
+                let _ = reply.send(scanner.start_scanning().map_err(Into::into));
+            }
+            Command::Stop { reply } => {
+                scanner.stop_scanning();
+                let _ = reply.send(Ok(()));
+            }
+            Command::ApplySettings { settings, rpc_url } => scanner.apply_settings(&settings, &rpc_url),
+            Command::SetSimulationPayer { payer, reply } => {
+                let _ = reply.send(scanner.set_simulation_payer(&payer));
+            }
+        }
+    }
+}
@@ -0,0 +1,127 @@
+//! State files shared by every process running from the same directory
+//!
+//! The server, the TUI and one-shot CLI commands all read and write the same
+//! positions and trade count, so each change reloads the file under a lock
+//! rather than overwriting it with one process's view.
+
+use std::fs::OpenOptions;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use anyhow::{Result, anyhow};
+use serde::{de::DeserializeOwned, Serialize};
+
+/// Wait for another process to finish its change before giving up
+const LOCK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A lock older than this was left behind by a process that died holding it
+const STALE_LOCK_AGE: Duration = Duration::from_secs(30);
+
+const LOCK_RETRY_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Exclusive hold on a shared file, released when dropped
+pub struct FileLock {
+    path: PathBuf,
+}
+
+impl FileLock {
+    /// Take the lock on `path`, held as a `.lock` file beside it
+    pub fn acquire(path: &Path) -> Result<Self> {
+        let lock_path = path.with_extension(lock_extension(path));
+        let started = SystemTime::now();
+
+        loop {
+            match OpenOptions::new().write(true).create_new(true).open(&lock_path) {
+                Ok(_) => return Ok(Self { path: lock_path }),
+                Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+                    if is_stale(&lock_path) {
+                        log::warn!("Removing stale lock {}", lock_path.display());
+                        let _ = std::fs::remove_file(&lock_path);
+                        continue;
+                    }
+                    if started.elapsed().unwrap_or_default() > LOCK_TIMEOUT {
+                        return Err(anyhow!("Timed out waiting for the lock on {}", path.display()));
+                    }
+                    std::thread::sleep(LOCK_RETRY_INTERVAL);
+                }
+                Err(e) => return Err(anyhow!("Cannot lock {}: {}", path.display(), e)),
+            }
+        }
+    }
+}
+
+impl Drop for FileLock {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+fn lock_extension(path: &Path) -> String {
+    match path.extension() {
+        Some(extension) => format!("{}.lock", extension.to_string_lossy()),
+        None => "lock".to_string(),
+    }
+}
+
+fn is_stale(lock_path: &Path) -> bool {
+    std::fs::metadata(lock_path)
+        .and_then(|metadata| metadata.modified())
+        .is_ok_and(|modified| modified.elapsed().unwrap_or_default() > STALE_LOCK_AGE)
+}
+
+/// Read a JSON state file, `None` if it does not exist yet
+pub fn load<T: DeserializeOwned>(path: &Path) -> Result<Option<T>> {
+    let json = match std::fs::read_to_string(path) {
+        Ok(json) => json,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(anyhow!("Cannot read {}: {}", path.display(), e)),
+    };
+    serde_json::from_str(&json)
+        .map(Some)
+        .map_err(|e| anyhow!("Invalid state file {}: {}", path.display(), e))
+}
+
+/// Write a JSON state file through a temporary file and a rename, so readers never see half of it
+pub fn save(path: &Path, value: &impl Serialize) -> Result<()> {
+    let json = serde_json::to_string_pretty(value)?;
+    let tmp = path.with_extension(format!("{}.tmp", path.extension().unwrap_or_default().to_string_lossy()));
+    std::fs::write(&tmp, json)?;
+    std::fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+/// When a state file was last written, to notice changes made by other processes
+pub fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|metadata| metadata.modified()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("wolf63x-{}-{}.json", name, std::process::id()))
+    }
+
+    #[test]
+    fn lock_is_exclusive_until_dropped() {
+        let path = temp_path("lock");
+        let lock = FileLock::acquire(&path).unwrap();
+        assert!(path.with_extension("json.lock").exists());
+
+        drop(lock);
+        assert!(!path.with_extension("json.lock").exists());
+        FileLock::acquire(&path).unwrap();
+    }
+
+    #[test]
+    fn saves_and_loads_through_a_rename() {
+        let path = temp_path("state");
+        assert_eq!(load::<Vec<u32>>(&path).unwrap(), None);
+
+        save(&path, &vec![1, 2, 3]).unwrap();
+        assert_eq!(load::<Vec<u32>>(&path).unwrap(), Some(vec![1, 2, 3]));
+        assert!(!path.with_extension("json.tmp").exists());
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,285 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::Instant;
+use anyhow::Result;
+use chrono::NaiveDate;
+use serde::{Serialize, Deserialize};
+use metrics::{counter, histogram};
+use tokio::sync::{mpsc, oneshot};
+use wolf63x_core::{
+    config::{AdvancedSettings, TradeSettings},
+    error::SniperError,
+    events::{Event, EventBus},
+    logger::TradeType,
+    profiles::ExitPlan,
+    telemetry,
+    trader::Trader,
+    wallet::Wallet,
+};
+use super::request;
+use super::shared_file::{self, FileLock};
+
+const SERVICE: &str = "Trader";
+
+enum Command {
+    Snipe {
+        token_address: String,
+        amount_sol: f64,
+        slippage: f64,
+        /// Limits of the trade's profile, `None` for the trader's own settings
+        settings: Option<TradeSettings>,
+        exit_plan: Option<ExitPlan>,
+        pending: PendingOrder,
+        reply: oneshot::Sender<Result<String>>,
+    },
+    Sell {
+        token_address: String,
+        /// SOL the sold share of the position cost, as reported on the order
+        amount: f64,
+        percent: f64,
+        slippage: f64,
+        pending: PendingOrder,
+        reply: oneshot::Sender<Result<String>>,
+    },
+    SetWallet(Wallet),
+    ApplySettings {
+        settings: TradeSettings,
+        advanced: AdvancedSettings,
+        rpc_url: String,
+    },
+}
+
+/// Handle to the task that builds and sends trades
+///
+/// Each order is sent from a task of its own, so a slow confirmation does not
+/// hold up the next order; only the daily buy count is kept by the service.
+/// An order completes, and is published as confirmed, once it is confirmed on chain.
+#[derive(Clone)]
+pub struct TraderService {
+    commands: mpsc::UnboundedSender<Command>,
+    /// Orders sent to the actor that have not completed yet
+    pending: Arc<AtomicU32>,
+    /// `advanced.maxPendingTx`; orders beyond it are refused rather than queued
+    max_pending: Arc<AtomicU32>,
+}
+
+/// Counts an order as pending until dropped, once the order's task is done
+struct PendingOrder(Arc<AtomicU32>);
+
+impl Drop for PendingOrder {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+impl TraderService {
+    /// Take over the trader, publishing every order's progress on `events`
+    ///
+    /// With a `count_path`, the daily buy count is kept in that file and
+    /// shared with the other processes using it, such as CLI commands.
+    pub fn spawn(trader: Trader, events: Arc<EventBus>, count_path: Option<PathBuf>) -> Self {
+        let (commands, receiver) = mpsc::unbounded_channel();
+        tokio::spawn(run(trader, count_path, events, receiver));
+        Self {
+            commands,
+            pending: Arc::new(AtomicU32::new(0)),
+            max_pending: Arc::new(AtomicU32::new(AdvancedSettings::default().max_pending_tx)),
+        }
+    }
+
+    /// Count an order as pending, refusing it once `max_pending` are in flight
+    fn reserve(&self) -> Result<PendingOrder> {
+        let max_pending = self.max_pending.load(Ordering::SeqCst);
+        self.pending.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |pending| (pending < max_pending).then_some(pending + 1))
+            .map_err(|_| SniperError::LimitReached(format!("{} pending transactions", max_pending)))?;
+        Ok(PendingOrder(self.pending.clone()))
+    }
+
+    /// Buy a token, then arm the exit plan if one is given
+    pub async fn snipe(
+        &self,
+        token_address: &str,
+        amount_sol: f64,
+        slippage: f64,
+        settings: Option<TradeSettings>,
+        exit_plan: Option<ExitPlan>,
+    ) -> Result<String> {
+        let pending = self.reserve()?;
+        let token_address = token_address.to_string();
+        request(&self.commands, SERVICE, |reply| Command::Snipe {
+            token_address,
+            amount_sol,
+            slippage,
+            settings,
+            exit_plan,
+            pending,
+            reply,
+        }).await?
+    }
+
+    /// Sell `percent` of the wallet's holdings of a token, `amount` SOL of its position
+    pub async fn sell(&self, token_address: &str, amount: f64, percent: f64, slippage: f64) -> Result<String> {
+        let pending = self.reserve()?;
+        let token_address = token_address.to_string();
+        request(&self.commands, SERVICE, |reply| Command::Sell { token_address, amount, percent, slippage, pending, reply }).await?
+    }
+
+    /// Sign with a newly connected wallet, or `Wallet::new()` after a disconnect
+    pub fn set_wallet(&self, wallet: Wallet) {
+        let _ = self.commands.send(Command::SetWallet(wallet));
+    }
+
+    pub fn apply_settings(&self, settings: &TradeSettings, advanced: &AdvancedSettings, rpc_url: &str) {
+        self.max_pending.store(advanced.max_pending_tx, Ordering::SeqCst);
+        let _ = self.commands.send(Command::ApplySettings {
+            settings: settings.clone(),
+            advanced: advanced.clone(),
+            rpc_url: rpc_url.to_string(),
+        });
+    }
+}
+
+async fn run(
+    mut trader: Trader,
+    count_path: Option<PathBuf>,
+    events: Arc<EventBus>,
+    mut commands: mpsc::UnboundedReceiver<Command>,
+) {
+    let count_path = count_path.as_deref();
+    // Buys that failed after being counted towards the daily limit
+    let (release, mut released) = mpsc::unbounded_channel::<NaiveDate>();
+
+    loop {
+        let command = tokio::select! {
+            command = commands.recv() => match command {
+                Some(command) => command,
+                None => break,
+            },
+            Some(day) = released.recv() => {
+                with_buy_count(&mut trader, count_path, |trader| trader.release_buy(day));
+                continue;
+            }
+        };
+
+        match command {
+            Command::Snipe { token_address, amount_sol, slippage, settings, exit_plan, pending, reply } => {
+                let day = match with_buy_count(&mut trader, count_path, |trader| trader.reserve_buy(settings.as_ref())) {
+                    Ok(day) => day,
+                    Err(e) => {
+                        let _ = reply.send(Err(e.into()));
+                        continue;
+                    }
+                };
+                let order = trader.session();
+                let events = events.clone();
+                let release = release.clone();
+                tokio::spawn(async move {
+                    let _pending = pending;
+                    submit(&events, &token_address, TradeType::Buy, amount_sol);
+                    let sent = order.buy(&token_address, amount_sol, slippage, settings.as_ref()).await;
+                    let sniped = confirm(&order, TradeType::Buy, sent).await.map_err(Into::into);
+                    publish_outcome(&events, &token_address, TradeType::Buy, &sniped);
+
+                    match (&sniped, &exit_plan) {
+                        (Ok(_), Some(plan)) => {
+                            let _ = order.set_take_profit(&token_address, plan.take_profit_percent);
+                            let _ = order.set_stop_loss(&token_address, plan.stop_loss_percent);
+                        }
+                        (Ok(_), None) => {}
+                        (Err(_), _) => {
+                            let _ = release.send(day);
+                        }
+                    }
+                    let _ = reply.send(sniped);
+                });
+            }
+            Command::Sell { token_address, amount, percent, slippage, pending, reply } => {
+                let order = trader.session();
+                let events = events.clone();
+                tokio::spawn(async move {
+                    let _pending = pending;
+                    submit(&events, &token_address, TradeType::Sell, amount);
+                    let sent = order.sell(&token_address, percent, slippage).await;
+                    let sold = confirm(&order, TradeType::Sell, sent).await.map_err(Into::into);
+                    publish_outcome(&events, &token_address, TradeType::Sell, &sold);
+                    let _ = reply.send(sold);
+                });
+            }
+            Command::SetWallet(wallet) => trader.set_wallet(wallet),
+            Command::ApplySettings { settings, advanced, rpc_url } => trader.apply_settings(&settings, &advanced, &rpc_url),
+        }
+    }
+}
+
+/// Daily buy count as saved for other processes
+#[derive(Serialize, Deserialize)]
+struct BuyCount {
+    day: NaiveDate,
+    buys: u32,
+}
+
+/// Change the daily buy count, through the shared file when there is one
+fn with_buy_count<R>(trader: &mut Trader, path: Option<&Path>, change: impl FnOnce(&mut Trader) -> R) -> R {
+    let Some(path) = path else {
+        return change(trader);
+    };
+    let _lock = FileLock::acquire(path)
+        .inspect_err(|e| log::warn!("Buy count changed without the file lock: {}", e));
+
+    match shared_file::load::<BuyCount>(path) {
+        Ok(Some(count)) => trader.set_buys_today(count.day, count.buys),
+        Ok(None) => {}
+        Err(e) => log::warn!("Failed to reload the buy count: {}", e),
+    }
+    let result = change(trader);
+
+    let (day, buys) = trader.buys_today();
+    if let Err(e) = shared_file::save(path, &BuyCount { day, buys }) {
+        log::warn!("Failed to save the buy count to {}: {}", path.display(), e);
+    }
+    result
+}
+
+/// Announce an order before it is built and sent
+fn submit(events: &EventBus, token_address: &str, side: TradeType, amount: f64) {
+    events.publish(Event::OrderSubmitted {
+        token_address: token_address.to_string(),
+        side,
+        amount,
+    });
+}
+
+/// Wait for an order that was sent to be confirmed on chain
+///
+/// Only orders that reached the network count as sent, and only confirmed
+/// ones as landed, timed from send to confirmation.
+async fn confirm(order: &Trader, side: TradeType, sent: Result<String, SniperError>) -> Result<String, SniperError> {
+    let signature = sent?;
+    counter!(telemetry::ORDERS_SENT_TOTAL, "side" => side_label(side)).increment(1);
+    let sent_at = Instant::now();
+
+    order.confirm(&signature).await?;
+    counter!(telemetry::ORDERS_LANDED_TOTAL, "side" => side_label(side)).increment(1);
+    histogram!(telemetry::SEND_TO_CONFIRM_SECONDS, "side" => side_label(side)).record(sent_at.elapsed().as_secs_f64());
+    Ok(signature)
+}
+
+/// Publish how an order ended: confirmed on chain, or failed
+fn publish_outcome(events: &EventBus, token_address: &str, side: TradeType, outcome: &Result<String>) {
+    let token_address = token_address.to_string();
+    if outcome.is_err() {
+        counter!(telemetry::ORDERS_FAILED_TOTAL, "side" => side_label(side)).increment(1);
+    }
+    events.publish(match outcome {
+        Ok(signature) => Event::OrderConfirmed { token_address, side, signature: signature.clone() },
+        Err(e) => Event::OrderFailed { token_address, side, error: e.to_string() },
+    });
+}
+
+fn side_label(side: TradeType) -> &'static str {
+    match side {
+        TradeType::Buy => "buy",
+        TradeType::Sell => "sell",
+    }
+}
@@ -0,0 +1,122 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+use anyhow::Result;
+use tokio::sync::{mpsc, oneshot};
+use wolf63x_core::{
+    events::{Event, EventBus, Topic},
+    wallet::Wallet,
+};
+use super::request;
+
+const SERVICE: &str = "Wallet";
+
+enum Command {
+    Connect {
+        wallet_type: String,
+        reply: oneshot::Sender<Result<(String, f64, Wallet)>>,
+    },
+    Disconnect {
+        reply: oneshot::Sender<Result<()>>,
+    },
+    Balance {
+        reply: oneshot::Sender<Result<f64>>,
+    },
+    SetRpcUrl(String),
+}
+
+/// Handle to the task owning the user's wallet
+#[derive(Clone)]
+pub struct WalletService {
+    commands: mpsc::UnboundedSender<Command>,
+}
+
+impl WalletService {
+    /// Take over the wallet, publishing balance changes on `events`
+    ///
+    /// A keystore wallet's balance is read again after every confirmed order.
+    pub fn spawn(wallet: Wallet, events: Arc<EventBus>) -> Self {
+        let (commands, receiver) = mpsc::unbounded_channel();
+        tokio::spawn(run(wallet, events, receiver));
+        Self { commands }
+    }
+
+    /// Connect a wallet, returning its address, balance and a session for signing elsewhere
+    pub async fn connect(&self, wallet_type: &str) -> Result<(String, f64, Wallet)> {
+        let wallet_type = wallet_type.to_string();
+        request(&self.commands, SERVICE, |reply| Command::Connect { wallet_type, reply }).await?
+    }
+
+    pub async fn disconnect(&self) -> Result<()> {
+        request(&self.commands, SERVICE, |reply| Command::Disconnect { reply }).await?
+    }
+
+    /// Balance of the connected wallet, queried on chain for a keystore wallet
+    pub async fn balance(&self) -> Result<f64> {
+        request(&self.commands, SERVICE, |reply| Command::Balance { reply }).await?
+    }
+
+    /// Endpoint balances are queried from
+    pub fn set_rpc_url(&self, rpc_url: &str) {
+        let _ = self.commands.send(Command::SetRpcUrl(rpc_url.to_string()));
+    }
+}
+
+async fn run(mut wallet: Wallet, events: Arc<EventBus>, mut commands: mpsc::UnboundedReceiver<Command>) {
+    let mut rpc_url = String::new();
+    let mut orders = events.subscribe(HashSet::from([Topic::Orders]), None);
+    let mut last_balance = None;
+    let mut balance_changed = |address: &str, balance: f64| {
+        if last_balance != Some(balance) {
+            last_balance = Some(balance);
+            events.publish(Event::WalletBalance { address: address.to_string(), balance });
+        }
+    };
+    
+    loop {
+        let command = tokio::select! {
+            command = commands.recv() => match command {
+                Some(command) => command,
+                None => break,
+            },
+            Some(order) = orders.next() => {
+                if matches!(order.event, Event::OrderConfirmed { .. }) && wallet.has_keypair() && !rpc_url.is_empty() {
+                    match wallet.fetch_balance(&rpc_url).await {
+                        Ok(balance) => balance_changed(&wallet.address, balance),
+                        Err(e) => log::debug!("Balance not refreshed after order: {}", e),
+                    }
+                }
+                continue;
+            }
+        };
+
+        match command {
+            Command::Connect { wallet_type, reply } => {
+                let connected = match wallet.connect_to(&wallet_type).await {
+                    Ok(address) => {
+                        let balance = wallet.connected_balance().unwrap_or(0.0);
+                        balance_changed(&address, balance);
+                        Ok((address, balance, wallet.session()))
+                    }
+                    Err(e) => Err(e.into()),
+                };
+                let _ = reply.send(connected);
+            }
+            Command::Disconnect { reply } => {
+                wallet.close();
+                let _ = reply.send(Ok(()));
+            }
+            Command::Balance { reply } => {
+                let balance = if wallet.has_keypair() && !rpc_url.is_empty() {
+                    wallet.fetch_balance(&rpc_url).await
+                } else {
+                    wallet.ensure_connected().map(|_| wallet.balance)
+                }.map_err(Into::into);
+                if let Ok(balance) = balance {
+                    balance_changed(&wallet.address, balance);
+                }
+                let _ = reply.send(balance);
+            }
+            Command::SetRpcUrl(url) => rpc_url = url,
+        }
+    }
+}
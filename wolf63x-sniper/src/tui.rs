@@ -0,0 +1,518 @@
+//! Terminal dashboard for running and watching the bot, e.g. over SSH
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::Duration;
+use anyhow::Result;
+use chrono::{DateTime, Local, Utc};
+use crossterm::event::{Event as TermEvent, EventStream, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use futures::StreamExt;
+use log::info;
+use ratatui::{
+    layout::{Constraint, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table, TableState},
+    DefaultTerminal, Frame,
+};
+use tokio::sync::mpsc;
+use wolf63x_core::{
+    events::{Event, SequencedEvent, Topic},
+    logger::TradeType,
+    scanner::TokenOpportunity,
+};
+use crate::app::App;
+use crate::services::Position;
+
+/// Log file while the dashboard owns the terminal
+pub const LOG_PATH: &str = "wolf63x-tui.log";
+
+/// How often the wallet balance is queried
+const BALANCE_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Orders kept on screen
+const MAX_ORDERS: usize = 50;
+
+/// Scan, trade and show it all until the user quits
+pub async fn run(app: Arc<App>) -> Result<()> {
+    let _config_watcher = app.watch_config()?;
+    let auto_trader = app.spawn_auto_trader();
+    let balance_refresh = spawn_balance_refresh(app.clone());
+    app.scanner.listen();
+    app.start_scanner().await?;
+    info!("Dashboard started");
+
+    let mut terminal = ratatui::init();
+    let result = Dashboard::new(app.clone()).run(&mut terminal).await;
+    ratatui::restore();
+
+    auto_trader.abort();
+    balance_refresh.abort();
+    app.scanner.stop().await?;
+    result
+}
+
+/// Balance changes reach the dashboard as wallet events
+fn spawn_balance_refresh(app: Arc<App>) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(BALANCE_INTERVAL);
+        loop {
+            ticker.tick().await;
+            if let Err(e) = app.wallet.balance().await {
+                log::warn!("Failed to refresh wallet balance: {}", e);
+            }
+        }
+    })
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Focus {
+    Opportunities,
+    Positions,
+}
+
+#[derive(Debug, PartialEq)]
+enum OrderState {
+    Pending,
+    Confirmed(String),
+    Failed(String),
+}
+
+struct Order {
+    token_address: String,
+    side: TradeType,
+    amount: f64,
+    state: OrderState,
+    at: DateTime<Utc>,
+}
+
+struct Dashboard {
+    app: Arc<App>,
+    focus: Focus,
+    opportunities: Vec<TokenOpportunity>,
+    positions: Vec<Position>,
+    /// Newest first
+    orders: VecDeque<Order>,
+    wallet: Option<(String, f64)>,
+    auto_snipe: bool,
+    opportunity_table: TableState,
+    position_table: TableState,
+    /// Outcome of the last hotkey action
+    status: String,
+}
+
+impl Dashboard {
+    fn new(app: Arc<App>) -> Self {
+        Self {
+            app,
+            focus: Focus::Opportunities,
+            opportunities: Vec::new(),
+            positions: Vec::new(),
+            orders: VecDeque::new(),
+            wallet: None,
+            auto_snipe: false,
+            opportunity_table: TableState::default().with_selected(0),
+            position_table: TableState::default().with_selected(0),
+            status: "Ready".to_string(),
+        }
+    }
+
+    async fn run(&mut self, terminal: &mut DefaultTerminal) -> Result<()> {
+        let mut input = EventStream::new();
+        // From the start, so orders and a balance published before the screen came up are shown
+        let mut events = self.app.events.subscribe([Topic::Orders, Topic::Wallet].into_iter().collect(), Some(0));
+        // Prices, opportunities and positions are redrawn at the rate the configuration started with
+        let refresh_rate_ms = self.app.config.lock().unwrap().config().ui_settings.refresh_rate_ms;
+        let mut ticker = tokio::time::interval(Duration::from_millis(refresh_rate_ms as u64));
+        // Trades run in the background so the screen keeps updating; they report back here
+        let (status, mut statuses) = mpsc::unbounded_channel();
+
+        loop {
+            terminal.draw(|frame| self.draw(frame))?;
+
+            tokio::select! {
+                _ = ticker.tick() => self.refresh().await,
+                event = events.next() => match event {
+                    Some(event) => self.record(event),
+                    None => return Ok(()),
+                },
+                Some(message) = statuses.recv() => self.status = message,
+                input = input.next() => match input {
+                    Some(Ok(TermEvent::Key(key))) if key.kind == KeyEventKind::Press => {
+                        if !self.handle_key(key, &status).await {
+                            return Ok(());
+                        }
+                    }
+                    Some(Ok(_)) => {}
+                    Some(Err(e)) => return Err(e.into()),
+                    None => return Ok(()),
+                },
+            }
+        }
+    }
+
+    async fn refresh(&mut self) {
+        self.opportunities = self.app.scanner.opportunities();
+        self.opportunities.sort_by_key(|token| std::cmp::Reverse(token.score));
+        match self.app.positions.list().await {
+            Ok(positions) => self.positions = positions,
+            Err(e) => self.status = e.to_string(),
+        }
+        self.auto_snipe = self.app.config.lock().unwrap().config().auto_snipe;
+
+        clamp(&mut self.opportunity_table, self.opportunities.len());
+        clamp(&mut self.position_table, self.positions.len());
+    }
+
+    fn record(&mut self, event: SequencedEvent) {
+        match event.event {
+            Event::OrderSubmitted { token_address, side, amount } => {
+                self.orders.push_front(Order {
+                    token_address,
+                    side,
+                    amount,
+                    state: OrderState::Pending,
+                    at: event.at,
+                });
+                self.orders.truncate(MAX_ORDERS);
+            }
+            Event::OrderConfirmed { token_address, side, signature } => {
+                settle_order(&mut self.orders, &token_address, side, OrderState::Confirmed(signature));
+            }
+            Event::OrderFailed { token_address, side, error } => {
+                settle_order(&mut self.orders, &token_address, side, OrderState::Failed(error));
+            }
+            Event::WalletBalance { address, balance } => self.wallet = Some((address, balance)),
+            _ => {}
+        }
+    }
+
+    /// Act on a key press, returning `false` to quit
+    async fn handle_key(&mut self, key: KeyEvent, status: &mpsc::UnboundedSender<String>) -> bool {
+        let table = match self.focus {
+            Focus::Opportunities => &mut self.opportunity_table,
+            Focus::Positions => &mut self.position_table,
+        };
+
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return false,
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => return false,
+            KeyCode::Tab => {
+                self.focus = match self.focus {
+                    Focus::Opportunities => Focus::Positions,
+                    Focus::Positions => Focus::Opportunities,
+                };
+            }
+            KeyCode::Up | KeyCode::Char('k') => table.select_previous(),
+            KeyCode::Down | KeyCode::Char('j') => table.select_next(),
+            KeyCode::Char('b') => self.buy_selected(status),
+            KeyCode::Char('s') => self.sell_selected(status),
+            KeyCode::Char('a') => {
+                self.status = match self.app.set_auto_snipe(!self.auto_snipe) {
+                    Ok(()) => {
+                        self.auto_snipe = !self.auto_snipe;
+                        format!("Auto-snipe {}", if self.auto_snipe { "on" } else { "off" })
+                    }
+                    Err(e) => format!("Auto-snipe not changed: {}", e),
+                };
+            }
+            KeyCode::Char('K') => {
+                let halted = self.app.is_halted();
+                let switched = if halted { self.app.resume().await } else { self.app.halt().await };
+                self.status = match switched {
+                    Ok(()) if halted => "Kill switch released, trading resumed".to_string(),
+                    Ok(()) => "Kill switch engaged, scanning stopped and buys refused".to_string(),
+                    Err(e) => e.to_string(),
+                };
+            }
+            _ => {}
+        }
+        true
+    }
+
+    fn buy_selected(&mut self, status: &mpsc::UnboundedSender<String>) {
+        let Some(token) = self.opportunity_table.selected().and_then(|i| self.opportunities.get(i)) else {
+            self.status = "Select an opportunity to buy".to_string();
+            return;
+        };
+
+        let (app, status, token_address, symbol) = (self.app.clone(), status.clone(), token.address.clone(), token.symbol.clone());
+        self.status = format!("Buying {}...", symbol);
+        tokio::spawn(async move {
+            let message = match app.buy_default(&token_address).await {
+                Ok(signature) => format!("Bought {}: {}", symbol, signature),
+                Err(e) => format!("Buy of {} failed: {}", symbol, e),
+            };
+            let _ = status.send(message);
+        });
+    }
+
+    fn sell_selected(&mut self, status: &mpsc::UnboundedSender<String>) {
+        let Some(position) = self.position_table.selected().and_then(|i| self.positions.get(i)) else {
+            self.status = "Select a position to sell".to_string();
+            return;
+        };
+
+        let (app, status, token_address, symbol) = (self.app.clone(), status.clone(), position.token_address.clone(), position.token_symbol.clone());
+        let slippage = self.app.config.lock().unwrap().config().trade_settings.default_slippage;
+        self.status = format!("Selling {}...", symbol);
+        tokio::spawn(async move {
+            let message = match app.sell_percent(&token_address, 100.0, slippage).await {
+                Ok(signature) => format!("Sold {}: {}", symbol, signature),
+                Err(e) => format!("Sell of {} failed: {}", symbol, e),
+            };
+            let _ = status.send(message);
+        });
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [header, body, footer] = Layout::vertical([
+            Constraint::Length(3),
+            Constraint::Min(8),
+            Constraint::Length(4),
+        ]).areas(frame.area());
+        let [left, right] = Layout::horizontal([Constraint::Percentage(55), Constraint::Percentage(45)]).areas(body);
+        let [positions, orders] = Layout::vertical([Constraint::Percentage(50), Constraint::Percentage(50)]).areas(right);
+
+        frame.render_widget(self.header(), header);
+        frame.render_stateful_widget(self.opportunity_view(), left, &mut self.opportunity_table);
+        frame.render_stateful_widget(self.position_view(), positions, &mut self.position_table);
+        frame.render_widget(self.order_view(), orders);
+        frame.render_widget(self.footer(), footer);
+    }
+
+    fn header(&self) -> Paragraph<'static> {
+        let wallet = match &self.wallet {
+            Some((address, balance)) => format!("{}  {:.4} SOL", short(address), balance),
+            None => "balance pending".to_string(),
+        };
+        let auto_snipe = if self.auto_snipe {
+            Span::styled("AUTO-SNIPE ON", Style::new().fg(Color::Green))
+        } else {
+            Span::styled("AUTO-SNIPE OFF", Style::new().fg(Color::DarkGray))
+        };
+        let trading = if self.app.is_halted() {
+            Span::styled("HALTED", Style::new().fg(Color::White).bg(Color::Red).add_modifier(Modifier::BOLD))
+        } else {
+            Span::styled("TRADING", Style::new().fg(Color::Green))
+        };
+
+        Paragraph::new(Line::from(vec![
+            Span::raw(format!("Wallet {}", wallet)),
+            Span::raw("  │  "),
+            auto_snipe,
+            Span::raw("  │  "),
+            trading,
+        ])).block(Block::default().borders(Borders::ALL).title(" Wolf63x Sniper "))
+    }
+
+    fn opportunity_view(&self) -> Table<'static> {
+        let rows = self.opportunities.iter().map(|token| {
+            let verdict = match &token.verdict {
+                Some(verdict) if verdict.passed => Cell::from("PASS").style(Style::new().fg(Color::Green)),
+                Some(verdict) => Cell::from(format!("FAIL ({})", verdict.failures().count())).style(Style::new().fg(Color::Red)),
+                None => Cell::from("-"),
+            };
+            Row::new(vec![
+                Cell::from(token.symbol.clone()),
+                Cell::from(token.score.to_string()),
+                verdict,
+                Cell::from(format!("{:.0}", token.market_cap)),
+                Cell::from(token.profile.clone().unwrap_or_default()),
+                Cell::from(token.source.clone()),
+            ])
+        });
+
+        Table::new(rows, [
+            Constraint::Length(10),
+            Constraint::Length(5),
+            Constraint::Length(9),
+            Constraint::Length(12),
+            Constraint::Length(12),
+            Constraint::Min(8),
+        ])
+        .header(header_row(["Token", "Score", "Verdict", "Mcap", "Profile", "Source"]))
+        .block(self.panel("Opportunities", Focus::Opportunities))
+        .row_highlight_style(highlight(self.focus == Focus::Opportunities))
+    }
+
+    fn position_view(&self) -> Table<'static> {
+        let rows = self.positions.iter().map(|position| {
+            let change = position.change_percent();
+            let pnl = Style::new().fg(if change >= 0.0 { Color::Green } else { Color::Red });
+            let exit = match position.exit_triggered {
+                Some(trigger) => format!("{:?}", trigger),
+                None => String::new(),
+            };
+            Row::new(vec![
+                Cell::from(position.token_symbol.clone()),
+                Cell::from(format!("{:.3}", position.amount_sol)),
+                Cell::from(format!("{:.8}", position.entry_price)),
+                Cell::from(format!("{:.8}", position.last_price)),
+                Cell::from(format!("{:+.2}%", change)).style(pnl),
+                Cell::from(format!("{:+.4}", position.amount_sol * change / 100.0)).style(pnl),
+                Cell::from(exit),
+            ])
+        });
+
+        Table::new(rows, [
+            Constraint::Length(10),
+            Constraint::Length(7),
+            Constraint::Length(11),
+            Constraint::Length(11),
+            Constraint::Length(9),
+            Constraint::Length(9),
+            Constraint::Min(6),
+        ])
+        .header(header_row(["Token", "SOL", "Entry", "Last", "PnL", "PnL SOL", "Exit"]))
+        .block(self.panel("Positions", Focus::Positions))
+        .row_highlight_style(highlight(self.focus == Focus::Positions))
+    }
+
+    fn order_view(&self) -> Table<'static> {
+        let rows = self.orders.iter().map(|order| {
+            let state = match &order.state {
+                OrderState::Pending => Cell::from("pending").style(Style::new().fg(Color::Yellow)),
+                OrderState::Confirmed(signature) => Cell::from(format!("confirmed {}", short(signature))).style(Style::new().fg(Color::Green)),
+                OrderState::Failed(error) => Cell::from(format!("failed: {}", error)).style(Style::new().fg(Color::Red)),
+            };
+            Row::new(vec![
+                Cell::from(order.at.with_timezone(&Local).format("%H:%M:%S").to_string()),
+                Cell::from(format!("{:?}", order.side)),
+                Cell::from(short(&order.token_address)),
+                Cell::from(format!("{:.3}", order.amount)),
+                state,
+            ])
+        });
+
+        Table::new(rows, [
+            Constraint::Length(8),
+            Constraint::Length(4),
+            Constraint::Length(11),
+            Constraint::Length(7),
+            Constraint::Min(10),
+        ])
+        .header(header_row(["Time", "Side", "Token", "SOL", "State"]))
+        .block(Block::default().borders(Borders::ALL).title(" Orders "))
+    }
+
+    fn footer(&self) -> Paragraph<'static> {
+        Paragraph::new(vec![
+            Line::from(self.status.clone()),
+            Line::styled(
+                "↑↓ select  Tab switch panel  b buy  s sell  a auto-snipe  K kill switch  q quit",
+                Style::new().fg(Color::DarkGray),
+            ),
+        ]).block(Block::default().borders(Borders::ALL))
+    }
+
+    fn panel(&self, title: &str, focus: Focus) -> Block<'static> {
+        let border = if self.focus == focus { Color::Cyan } else { Color::Reset };
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::new().fg(border))
+            .title(format!(" {} ", title))
+    }
+}
+
+fn header_row<const N: usize>(titles: [&'static str; N]) -> Row<'static> {
+    Row::new(titles).style(Style::new().add_modifier(Modifier::BOLD))
+}
+
+fn highlight(focused: bool) -> Style {
+    if focused {
+        Style::new().add_modifier(Modifier::REVERSED)
+    } else {
+        Style::new().add_modifier(Modifier::DIM)
+    }
+}
+
+/// Update the newest pending order for a token and side
+fn settle_order(orders: &mut VecDeque<Order>, token_address: &str, side: TradeType, state: OrderState) {
+    let pending = orders.iter_mut().find(|order| {
+        order.token_address == token_address && order.side == side && matches!(order.state, OrderState::Pending)
+    });
+    if let Some(order) = pending {
+        order.state = state;
+    }
+}
+
+/// Keep the selection on a row that still exists
+fn clamp(table: &mut TableState, rows: usize) {
+    match (table.selected(), rows) {
+        (_, 0) => table.select(None),
+        (None, _) => table.select(Some(0)),
+        (Some(i), rows) if i >= rows => table.select(Some(rows - 1)),
+        _ => {}
+    }
+}
+
+/// First and last four characters of an address or signature
+fn short(address: &str) -> String {
+    if address.len() <= 11 {
+        return address.to_string();
+    }
+    format!("{}…{}", &address[..4], &address[address.len() - 4..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pending(token_address: &str, side: TradeType) -> Order {
+        Order {
+            token_address: token_address.to_string(),
+            side,
+            amount: 0.1,
+            state: OrderState::Pending,
+            at: Utc::now(),
+        }
+    }
+
+    fn states(orders: &VecDeque<Order>) -> Vec<&OrderState> {
+        orders.iter().map(|order| &order.state).collect()
+    }
+
+    #[test]
+    fn settles_the_newest_pending_order_of_the_token_and_side() {
+        let mut orders: VecDeque<Order> = [
+            pending("mint", TradeType::Sell),
+            pending("mint", TradeType::Buy),
+            pending("mint", TradeType::Buy),
+            pending("other", TradeType::Buy),
+        ].into_iter().collect();
+
+        settle_order(&mut orders, "mint", TradeType::Buy, OrderState::Confirmed("sig".to_string()));
+        settle_order(&mut orders, "mint", TradeType::Buy, OrderState::Failed("expired".to_string()));
+        // Nothing left pending for it, so a late outcome changes nothing
+        settle_order(&mut orders, "mint", TradeType::Buy, OrderState::Failed("late".to_string()));
+
+        assert_eq!(states(&orders), [
+            &OrderState::Pending,
+            &OrderState::Confirmed("sig".to_string()),
+            &OrderState::Failed("expired".to_string()),
+            &OrderState::Pending,
+        ]);
+    }
+
+    #[test]
+    fn selection_stays_within_the_rows() {
+        let mut table = TableState::default();
+        clamp(&mut table, 3);
+        assert_eq!(table.selected(), Some(0));
+
+        table.select(Some(5));
+        clamp(&mut table, 3);
+        assert_eq!(table.selected(), Some(2));
+
+        clamp(&mut table, 0);
+        assert_eq!(table.selected(), None);
+    }
+
+    #[test]
+    fn long_addresses_are_shortened() {
+        assert_eq!(short("So11111111111111111111111111111111111111112"), "So11…1112");
+        assert_eq!(short("short"), "short");
+        assert_eq!(short("12345678901"), "12345678901");
+    }
+}
@@ -7,6 +7,7 @@ use crate::{
     logger::Logger,
     config::ConfigManager,
 };
+use std::sync::{Arc, Mutex};
 
 // Global state for WASM bindings
 #[wasm_bindgen]
@@ -27,10 +28,13 @@ impl WasmBindings {
         
         let wallet = Wallet::new();
         let trader = Trader::new(Wallet::new(), "https://api.mainnet-beta.solana.com".to_string());
-        let scanner = Scanner::new();
+        let mut scanner = Scanner::new();
         let filter = TokenFilter::new();
         let logger = Logger::new();
-        let config = ConfigManager::new();
+        
+        // Config, filter and scanner all read the same filter settings
+        let config = ConfigManager::with_filter_settings(filter.shared_settings());
+        scanner.set_filter(Arc::new(Mutex::new(TokenFilter::with_settings(filter.shared_settings()))));
         
         Self {
             wallet,
@@ -85,8 +89,8 @@ impl WasmBindings {
     }
     
     #[wasm_bindgen]
-    pub async fn sell_token(&self, token_address: &str, amount: f64, slippage: f64) -> Result<JsValue, JsValue> {
-        let signature = self.trader.sell_token(token_address, amount, slippage).await?;
+    pub async fn sell_token(&self, token_address: &str, percent: f64, slippage: f64) -> Result<JsValue, JsValue> {
+        let signature = self.trader.sell_token(token_address, percent, slippage).await?;
         
         let response = serde_wasm_bindgen::to_value(&serde_json::json!({
             "signature": signature,
@@ -163,7 +167,7 @@ impl WasmBindings {
     }
     
     #[wasm_bindgen]
-    pub fn validate_token(&self, token: JsValue) -> Result<bool, JsValue> {
+    pub fn validate_token(&self, token: JsValue) -> Result<JsValue, JsValue> {
         self.filter.validate(token)
     }
     